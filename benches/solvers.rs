@@ -0,0 +1,92 @@
+//! Criterion benchmarks of all implemented days.
+//!
+//! The benchmarks use the inputs cached by `aoc`, so run the solver of a day
+//! once before benchmarking it. Days without a cached input are skipped. The
+//! cache is selected with the same environment variables as for `aoc`
+//! (`AOC_CACHE_DIR`, `AOC_CACHE_BACKEND`, and `AOC_PROFILE`), and the days can
+//! be filtered as usual with `cargo bench -- 2023-05`.
+
+use std::{path::PathBuf, sync::Arc};
+
+use aoc::{
+    solvers::{self, solver_days, BenchFns},
+    store::{self, Backend, Namespace, Store},
+};
+use clap::ValueEnum;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Opens the cache `aoc` would use with the current environment.
+fn open_store() -> anyhow::Result<Arc<dyn Store>> {
+    let backend = match std::env::var(store::CACHE_BACKEND_ENV_VAR) {
+        Ok(name) => Backend::from_str(&name, true).map_err(anyhow::Error::msg)?,
+        Err(_) => Backend::default(),
+    };
+    let profile = std::env::var(store::PROFILE_ENV_VAR).ok();
+    backend.open(&store::root(
+        std::env::var_os(store::CACHE_DIR_ENV_VAR).map(PathBuf::from),
+        profile.as_deref(),
+    ))
+}
+
+fn cached_input(store: &dyn Store, year: i32, day: u32) -> anyhow::Result<Option<String>> {
+    let Some(input) = store::read(store, Namespace::Inputs, &store::input_key(year, day))? else {
+        return Ok(None);
+    };
+    Ok(Some(String::from_utf8(solvers::normalize_input(
+        year, day, input,
+    ))?))
+}
+
+fn bench_day(
+    c: &mut Criterion,
+    year: i32,
+    day: u32,
+    bench_fns: BenchFns,
+    input: &str,
+) -> anyhow::Result<()> {
+    let mut group = c.benchmark_group(format!("{year}-{day:02}"));
+    group.bench_function("parse", |b| {
+        b.iter_custom(|iterations| (bench_fns.parse)(input, iterations).unwrap())
+    });
+
+    let mut solver = (bench_fns.new)(input)?;
+    solver.precompute()?;
+    let part_1 = solver.solve_part_1()?;
+    group.bench_function("part 1", |b| b.iter(|| solver.solve_part_1().unwrap()));
+    if solver.solve_part_2_with(&part_1)?.is_some() {
+        group.bench_function("part 2", |b| {
+            b.iter(|| solver.solve_part_2_with(&part_1).unwrap())
+        });
+    }
+    group.finish();
+    Ok(())
+}
+
+fn bench_solvers(c: &mut Criterion) {
+    let store = match open_store() {
+        Ok(store) => store,
+        Err(err) => {
+            eprintln!("cannot open cache: {err:#}");
+            return;
+        }
+    };
+    for (year, day) in solver_days() {
+        let Some(bench_fns) = solvers::bench_fns(year, day) else {
+            continue;
+        };
+        let input = match cached_input(store.as_ref(), year, day) {
+            Ok(Some(input)) => input,
+            Ok(None) => continue,
+            Err(err) => {
+                eprintln!("cannot read input of {year}, day {day}: {err:#}");
+                continue;
+            }
+        };
+        if let Err(err) = bench_day(c, year, day, bench_fns, &input) {
+            eprintln!("cannot benchmark {year}, day {day}: {err:#}");
+        }
+    }
+}
+
+criterion_group!(benches, bench_solvers);
+criterion_main!(benches);
@@ -0,0 +1,209 @@
+//! Scans `src/solvers` for solver modules and examples and writes them to a
+//! manifest in `OUT_DIR`, which the `solver-dispatch` macros read to generate
+//! the dispatch tables. Cargo reruns the scan whenever something in
+//! `src/solvers` changes, so that new days are picked up without forcing a
+//! rebuild.
+
+use std::{
+    fmt::Write,
+    fs::{self, FileType},
+    path::{Path, PathBuf},
+};
+
+const SOLVERS_DIR: &str = "src/solvers";
+/// Template of the solver modules written by `aoc create`.
+const TEMPLATE_PATH: &str = "src/day.rs.template";
+
+struct File {
+    file_type: FileType,
+    file_name: String,
+}
+
+fn filter_by_file_type_and_name<P: AsRef<Path>, F: Fn(&File) -> bool + 'static>(
+    path: P,
+    predicate: F,
+) -> impl Iterator<Item = File> {
+    fs::read_dir(path.as_ref())
+        .expect("cannot list directory")
+        .filter_map(move |path| {
+            let path = path.as_ref().expect("cannot read directory entry");
+            let file_type = path.file_type().expect("cannot determine file type");
+            let file_name = path
+                .file_name()
+                .into_string()
+                .expect("invalid characters in file name");
+            let file = File {
+                file_type,
+                file_name,
+            };
+            if predicate(&file) {
+                Some(file)
+            } else {
+                None
+            }
+        })
+}
+
+/// Whether the default solver in the file registers itself with
+/// `register_solver` instead of relying on the directory scan.
+fn is_registered(path: &Path) -> bool {
+    let source = fs::read_to_string(path).expect("cannot read solver module");
+    solver_registration::registers_default_solver(&source)
+        .unwrap_or_else(|err| panic!("cannot parse {}: {err}", path.display()))
+}
+
+/// Parses the day of a solver module, which is either a `day<DD>.rs` file or a
+/// `day<DD>` directory with a `mod.rs` and helper modules.
+fn parse_day_module(year_path: &Path, file: &File) -> Option<(u32, PathBuf)> {
+    let day = file.file_name.strip_prefix("day")?;
+    let (day, path) = if file.file_type.is_file() {
+        (day.strip_suffix(".rs")?, year_path.join(&file.file_name))
+    } else if file.file_type.is_dir() {
+        let path = year_path.join(&file.file_name).join("mod.rs");
+        (day, path.is_file().then_some(path)?)
+    } else {
+        return None;
+    };
+    let day = day
+        .parse::<u32>()
+        .expect("module names should be in format 'day<DD>.rs' or 'day<DD>/mod.rs'");
+    Some((day, path))
+}
+
+/// Finds the solver modules that do not register themselves, sorted by year
+/// and day.
+fn find_solver_modules<P: AsRef<Path>>(base_path: P) -> Vec<(i32, u32, PathBuf)> {
+    let base_path = base_path.as_ref().to_path_buf();
+    let years = filter_by_file_type_and_name(&base_path, |file| {
+        file.file_type.is_dir() && file.file_name.starts_with("year")
+    })
+    .map(|file| {
+        file.file_name[4..]
+            .parse::<i32>()
+            .expect("directory names in format 'year<YYYY>'")
+    });
+    let mut modules: Vec<_> = years
+        .flat_map(|year| {
+            let year_path = base_path.join(format!("year{}", year));
+            filter_by_file_type_and_name(year_path.clone(), |file| {
+                file.file_name.starts_with("day")
+            })
+            .filter_map(move |file| parse_day_module(&year_path, &file))
+            .filter(|(_, path)| !is_registered(path))
+            .map(move |(day, path)| (year, day, path))
+        })
+        .collect();
+    modules.sort_unstable();
+    modules
+}
+
+/// Whether the solver module of `day` is still the unmodified template.
+fn is_unmodified_template(path: &Path, day: u32, template: &str) -> bool {
+    let source = fs::read_to_string(path).expect("cannot read solver module");
+    source == template.replace("{{day}}", &day.to_string())
+}
+
+struct Example {
+    year: i32,
+    day: u32,
+    label: String,
+    input_path: String,
+    expected_path: Option<String>,
+}
+
+/// Parses example file names of the form `day<DD>-<label>.example`.
+fn parse_example_file_name(file_name: &str) -> Option<(u32, &str)> {
+    let (day, label) = file_name
+        .strip_prefix("day")?
+        .strip_suffix(".example")?
+        .split_once('-')?;
+    Some((day.parse().ok()?, label))
+}
+
+/// Finds all `day<DD>-<label>.example` files of the given years, sorted by
+/// year, day, and label.
+fn find_examples<P: AsRef<Path>>(base_path: P, mut years: Vec<i32>) -> Vec<Example> {
+    let base_path = base_path.as_ref().to_path_buf();
+    years.dedup();
+
+    let mut examples: Vec<Example> = years
+        .into_iter()
+        .flat_map(|year| {
+            let year_path = base_path.join(format!("year{}", year));
+            filter_by_file_type_and_name(year_path.clone(), |file| {
+                file.file_type.is_file() && parse_example_file_name(&file.file_name).is_some()
+            })
+            .map(move |file| {
+                let (day, label) = parse_example_file_name(&file.file_name).unwrap();
+                let input_path = year_path.join(&file.file_name);
+                let expected_path = input_path.with_extension("expected");
+                Example {
+                    year,
+                    day,
+                    label: label.to_string(),
+                    input_path: input_path.to_string_lossy().into_owned(),
+                    expected_path: expected_path
+                        .exists()
+                        .then(|| expected_path.to_string_lossy().into_owned()),
+                }
+            })
+            .collect::<Vec<_>>()
+        })
+        .collect();
+    examples.sort_by(|a, b| (a.year, a.day, &a.label).cmp(&(b.year, b.day, &b.label)));
+    examples
+}
+
+/// Renders the manifest with one tab-separated record per line:
+/// `solver <year> <day>` for each solver module,
+/// `template <year> <day> <path>` for each solver module that is still the
+/// unmodified template, and
+/// `example <year> <day> <label> <input path> <expected path or ->` for each
+/// example.
+fn render_manifest(
+    days: &[(i32, u32)],
+    templates: &[(i32, u32, PathBuf)],
+    examples: &[Example],
+) -> String {
+    let mut manifest = String::new();
+    for (year, day) in days {
+        writeln!(manifest, "solver\t{year}\t{day}").unwrap();
+    }
+    for (year, day, path) in templates {
+        writeln!(manifest, "template\t{year}\t{day}\t{}", path.display()).unwrap();
+    }
+    for example in examples {
+        writeln!(
+            manifest,
+            "example\t{}\t{}\t{}\t{}\t{}",
+            example.year,
+            example.day,
+            example.label,
+            example.input_path,
+            example.expected_path.as_deref().unwrap_or("-")
+        )
+        .unwrap();
+    }
+    manifest
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={SOLVERS_DIR}");
+    println!("cargo:rerun-if-changed={TEMPLATE_PATH}");
+
+    let modules = find_solver_modules(SOLVERS_DIR);
+    let template = fs::read_to_string(TEMPLATE_PATH).expect("cannot read solver template");
+    let templates: Vec<_> = modules
+        .iter()
+        .filter(|(_, day, path)| is_unmodified_template(path, *day, &template))
+        .cloned()
+        .collect();
+    let days: Vec<_> = modules.iter().map(|&(year, day, _)| (year, day)).collect();
+    let examples = find_examples(SOLVERS_DIR, days.iter().map(|&(year, _)| year).collect());
+    let out_dir = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    fs::write(
+        out_dir.join("solver_manifest.tsv"),
+        render_manifest(&days, &templates, &examples),
+    )
+    .expect("cannot write solver manifest");
+}
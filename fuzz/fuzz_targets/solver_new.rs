@@ -0,0 +1,38 @@
+//! Feeds arbitrary inputs into the constructors of all solvers to find inputs
+//! that make them panic instead of returning an error.
+//!
+//! The first byte selects the day. Run with `cargo fuzz run solver_new`, or
+//! fuzz a single day with `AOC_FUZZ_DAY=2023-19 cargo fuzz run solver_new`.
+
+#![no_main]
+
+use aoc::solvers;
+use libfuzzer_sys::fuzz_target;
+use std::sync::LazyLock;
+
+static DAYS: LazyLock<Vec<(i32, u32)>> = LazyLock::new(|| {
+    let days = solvers::solver_days();
+    match std::env::var("AOC_FUZZ_DAY") {
+        Ok(selected) => {
+            let (year, day) = selected
+                .split_once('-')
+                .and_then(|(year, day)| Some((year.parse().ok()?, day.parse().ok()?)))
+                .expect("AOC_FUZZ_DAY must have the format YYYY-DD");
+            assert!(days.contains(&(year, day)), "no solver for {selected}");
+            vec![(year, day)]
+        }
+        Err(_) => days,
+    }
+});
+
+fuzz_target!(|data: &[u8]| {
+    let Some((&selector, input)) = data.split_first() else {
+        return;
+    };
+    let (year, day) = DAYS[usize::from(selector) % DAYS.len()];
+    // Errors are the expected outcome for malformed inputs, only panics are
+    // of interest. Inputs take the same path as in the runner, so solvers
+    // working on `&str` reject invalid UTF-8 with an error.
+    let input = solvers::normalize_input(year, day, input.to_vec());
+    let _ = solvers::new_solver_from_bytes(year, day, &input);
+});
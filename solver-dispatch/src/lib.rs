@@ -1,11 +1,8 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote, ToTokens};
-use std::{
-    convert::TryFrom,
-    fs::{self, FileType},
-    path::Path,
-};
-use syn::{parse::Parse, parse_macro_input, token::Comma, Expr, Ident};
+use solver_registration::RegistrationArgs;
+use std::{convert::TryFrom, fs, path::Path};
+use syn::{parse::Parse, parse_macro_input, token::Comma, DeriveInput, Expr, Ident};
 
 extern crate proc_macro;
 
@@ -13,6 +10,8 @@ struct Solver<'ident> {
     year: i32,
     day: u32,
     input_expr: &'ident Expr,
+    /// Either `new` or `from_bytes`.
+    constructor: &'ident Ident,
 }
 
 impl<'ident> ToTokens for Solver<'ident> {
@@ -21,11 +20,112 @@ impl<'ident> ToTokens for Solver<'ident> {
             year,
             day,
             input_expr,
+            constructor,
         } = *self;
         let year_mod = format_ident!("year{}", u32::try_from(year).expect("a non-negative year"));
         let day_mod = format_ident!("day{}", day);
+        let cfg = year_cfg(year);
         tokens.extend(quote!(
-            (#year, #day) => Ok(Box::new(crate::solvers::#year_mod::#day_mod::SolverImpl::new(&#input_expr)?) as Box<dyn crate::solvers::Solver>),
+            #cfg
+            (#year, #day) => crate::solvers::#year_mod::#day_mod::SolverImpl::#constructor(&#input_expr)
+                .map(|solver| Box::new(solver) as Box<dyn crate::solvers::Solver>),
+        ));
+    }
+}
+
+/// Attribute compiling code for a year only if the year's feature is enabled.
+fn year_cfg(year: i32) -> proc_macro2::TokenStream {
+    let feature = format!("year{}", year);
+    quote!(#[cfg(feature = #feature)])
+}
+
+/// Expands to a `&[(i32, u32)]` slice of the `(year, day)` pairs found by
+/// scanning the solver modules, sorted in ascending order, so that the days
+/// can be iterated at runtime without scanning the directories again.
+#[proc_macro]
+pub fn solver_manifest(args: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(proc_macro2::Span::call_site(), "expected no arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let days = read_manifest().days.into_iter().map(|(year, day)| {
+        let cfg = year_cfg(year);
+        quote!(#cfg (#year, #day))
+    });
+    quote!(
+        &[#(#days),*]
+    )
+    .into()
+}
+
+struct Normalization {
+    year: i32,
+    day: u32,
+}
+
+impl ToTokens for Normalization {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Normalization { year, day } = *self;
+        let year_mod = format_ident!("year{}", u32::try_from(year).expect("a non-negative year"));
+        let day_mod = format_ident!("day{}", day);
+        let cfg = year_cfg(year);
+        tokens.extend(quote!(
+            #cfg
+            (#year, #day) => <crate::solvers::#year_mod::#day_mod::SolverImpl as crate::solvers::Solver>::normalization(),
+        ));
+    }
+}
+
+struct Info {
+    year: i32,
+    day: u32,
+}
+
+impl ToTokens for Info {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Info { year, day } = *self;
+        let year_mod = format_ident!("year{}", u32::try_from(year).expect("a non-negative year"));
+        let day_mod = format_ident!("day{}", day);
+        let cfg = year_cfg(year);
+        tokens.extend(quote!(
+            #cfg
+            (#year, #day) => Some(crate::solvers::SolverInfo {
+                year: #year,
+                day: #day,
+                title: <crate::solvers::#year_mod::#day_mod::SolverImpl as crate::solvers::Solver>::title(),
+                tags: <crate::solvers::#year_mod::#day_mod::SolverImpl as crate::solvers::Solver>::tags(),
+            }),
+        ));
+    }
+}
+
+struct BenchFns {
+    year: i32,
+    day: u32,
+}
+
+impl ToTokens for BenchFns {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let BenchFns { year, day } = *self;
+        let year_mod = format_ident!("year{}", u32::try_from(year).expect("a non-negative year"));
+        let day_mod = format_ident!("day{}", day);
+        let cfg = year_cfg(year);
+        tokens.extend(quote!(
+            #cfg
+            (#year, #day) => Some(crate::solvers::BenchFns {
+                parse: |input, iterations| {
+                    crate::solvers::time_parsing(
+                        crate::solvers::#year_mod::#day_mod::SolverImpl::new,
+                        input,
+                        iterations,
+                    )
+                },
+                new: |input| {
+                    Ok(Box::new(crate::solvers::#year_mod::#day_mod::SolverImpl::new(input)?))
+                },
+            }),
         ));
     }
 }
@@ -62,81 +162,412 @@ impl Parse for SolverDispatchInput {
     }
 }
 
-struct File {
-    file_type: FileType,
-    file_name: String,
-}
-
-fn filter_by_file_type_and_name<P: AsRef<Path>, F: Fn(&File) -> bool + 'static>(
-    path: P,
-    predicate: F,
-) -> impl Iterator<Item = File> {
-    fs::read_dir(path.as_ref())
-        .expect("cannot list directory")
-        .filter_map(move |path| {
-            let path = path.as_ref().expect("cannot read directory entry");
-            let file_type = path.file_type().expect("cannot determine file type");
-            let file_name = path
-                .file_name()
-                .into_string()
-                .expect("invalid characters in file name");
-            let file = File {
-                file_type,
-                file_name,
-            };
-            if predicate(&file) {
-                Some(file)
-            } else {
-                None
-            }
-        })
+/// Solver modules and examples found by the `build.rs` of the solvers crate.
+struct Manifest {
+    /// Days with a solver module that does not register itself, sorted.
+    days: Vec<(i32, u32)>,
+    /// Solver modules that are still the unmodified template.
+    templates: Vec<UnmodifiedTemplate>,
+    /// Examples sorted by year, day, and label.
+    examples: Vec<Example>,
+}
+
+/// Reads the manifest that the `build.rs` of the solvers crate wrote to
+/// `OUT_DIR`, so that cargo tracks the scanned directory and reruns the scan
+/// when days are added.
+fn read_manifest() -> Manifest {
+    let out_dir = std::env::var_os("OUT_DIR")
+        .expect("OUT_DIR is not set, the crate needs a build.rs writing the solver manifest");
+    let manifest = fs::read_to_string(Path::new(&out_dir).join("solver_manifest.tsv"))
+        .expect("cannot read solver manifest");
+    let mut days = Vec::new();
+    let mut templates = Vec::new();
+    let mut examples = Vec::new();
+    for line in manifest.lines() {
+        let fields: Vec<_> = line.split('\t').collect();
+        let year = fields[1].parse().expect("year in solver manifest");
+        let day = fields[2].parse().expect("day in solver manifest");
+        match fields[0] {
+            "solver" => days.push((year, day)),
+            "template" => templates.push(UnmodifiedTemplate {
+                year,
+                path: fields[3].to_string(),
+            }),
+            "example" => examples.push(Example {
+                year,
+                day,
+                label: fields[3].to_string(),
+                input_path: fields[4].to_string(),
+                expected_path: (fields[5] != "-").then(|| fields[5].to_string()),
+            }),
+            kind => panic!("unknown record '{kind}' in solver manifest"),
+        }
+    }
+    Manifest {
+        days,
+        templates,
+        examples,
+    }
 }
 
+/// Creates the solver for a day from a `&str` input with `Solver::new`.
 #[proc_macro]
 pub fn solver_dispatch(args: TokenStream) -> TokenStream {
-    let SolverDispatchInput {
+    let input = parse_macro_input!(args as SolverDispatchInput);
+    dispatch(input, format_ident!("new"))
+}
+
+/// Creates the solver for a day from a `&[u8]` input with
+/// `Solver::from_bytes`.
+#[proc_macro]
+pub fn solver_dispatch_bytes(args: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(args as SolverDispatchInput);
+    dispatch(input, format_ident!("from_bytes"))
+}
+
+fn dispatch(
+    SolverDispatchInput {
         input_expr,
         year_ident,
         day_ident,
-    } = parse_macro_input!(args as SolverDispatchInput);
-
-    let base_path = Path::new("src/solvers");
-    let years = filter_by_file_type_and_name(&base_path, |file| {
-        file.file_type.is_dir() && file.file_name.starts_with("year")
-    })
-    .map(|file| {
-        file.file_name[4..]
-            .parse::<i32>()
-            .expect("directory names in format 'year<YYYY>'")
-    });
-    let years_with_days = years.flat_map(|year| {
-        filter_by_file_type_and_name(base_path.join(format!("year{}", year)), |file| {
-            file.file_type.is_file()
-                && file.file_name.starts_with("day")
-                && file.file_name.ends_with(".rs")
-        })
-        .map(move |file| {
-            (
-                year,
-                file.file_name[3..file.file_name.len() - 3]
-                    .parse::<u32>()
-                    .expect("module names should be in format 'day<DD>.rs'"),
-            )
-        })
-    });
-
-    let solvers: Vec<Solver<'_>> = years_with_days
+    }: SolverDispatchInput,
+    constructor: Ident,
+) -> TokenStream {
+    let solvers: Vec<Solver<'_>> = read_manifest()
+        .days
+        .into_iter()
         .map(|(year, day)| Solver {
             year,
             day,
             input_expr: &input_expr,
+            constructor: &constructor,
         })
         .collect();
 
+    quote!(
+        crate::solvers::locate_input_error(
+            match (#year_ident, #day_ident) {
+                #(#solvers)*
+                _ => match crate::solvers::Registration::find(#year_ident, #day_ident) {
+                    Some(registration) => (registration.#constructor)(&#input_expr),
+                    None => Err(anyhow::anyhow!("no solver for day {} of year {}", day, year)),
+                }
+            },
+            &#input_expr,
+        )
+    )
+    .into()
+}
+
+struct NormalizationDispatchInput {
+    year_ident: Ident,
+    day_ident: Ident,
+}
+
+impl Parse for NormalizationDispatchInput {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let year_ident = Ident::parse(input)?;
+        Comma::parse(input)?;
+        let day_ident = Ident::parse(input)?;
+        if !input.is_empty() {
+            Comma::parse(input)?;
+        }
+        Ok(Self {
+            year_ident,
+            day_ident,
+        })
+    }
+}
+
+#[proc_macro]
+pub fn normalization_dispatch(args: TokenStream) -> TokenStream {
+    let NormalizationDispatchInput {
+        year_ident,
+        day_ident,
+    } = parse_macro_input!(args as NormalizationDispatchInput);
+
+    let normalizations: Vec<Normalization> = read_manifest()
+        .days
+        .into_iter()
+        .map(|(year, day)| Normalization { year, day })
+        .collect();
+
+    quote!(
+        match (#year_ident, #day_ident) {
+            #(#normalizations)*
+            _ => crate::solvers::Registration::find(#year_ident, #day_ident)
+                .map_or(crate::solvers::Normalization::STANDARD, |registration| {
+                    (registration.normalization)()
+                }),
+        }
+    )
+    .into()
+}
+
+#[proc_macro]
+pub fn solver_info_dispatch(args: TokenStream) -> TokenStream {
+    let NormalizationDispatchInput {
+        year_ident,
+        day_ident,
+    } = parse_macro_input!(args as NormalizationDispatchInput);
+
+    let infos: Vec<Info> = read_manifest()
+        .days
+        .into_iter()
+        .map(|(year, day)| Info { year, day })
+        .collect();
+
     quote!(
         match (#year_ident, #day_ident) {
-            #(#solvers)*
-            _ => Err(anyhow::anyhow!("no solver for day {} of year {}", day, year))
+            #(#infos)*
+            _ => crate::solvers::Registration::find(#year_ident, #day_ident)
+                .map(crate::solvers::Registration::info),
+        }
+    )
+    .into()
+}
+
+/// Functions to parse the input and construct the solver of a day for a
+/// benchmark harness, covering every day without manual registration.
+#[proc_macro]
+pub fn solver_bench_dispatch(args: TokenStream) -> TokenStream {
+    let NormalizationDispatchInput {
+        year_ident,
+        day_ident,
+    } = parse_macro_input!(args as NormalizationDispatchInput);
+
+    let bench_fns: Vec<BenchFns> = read_manifest()
+        .days
+        .into_iter()
+        .map(|(year, day)| BenchFns { year, day })
+        .collect();
+
+    quote!(
+        match (#year_ident, #day_ident) {
+            #(#bench_fns)*
+            _ => crate::solvers::Registration::find(#year_ident, #day_ident)
+                .map(crate::solvers::Registration::bench_fns),
+        }
+    )
+    .into()
+}
+
+struct Example {
+    year: i32,
+    day: u32,
+    label: String,
+    input_path: String,
+    expected_path: Option<String>,
+}
+
+impl ToTokens for Example {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Example {
+            year,
+            day,
+            label,
+            input_path,
+            expected_path,
+        } = self;
+        let expected = match expected_path {
+            Some(path) => quote!(Some(include_str!(
+                concat!(env!("CARGO_MANIFEST_DIR"), "/", #path)
+            ))),
+            None => quote!(None),
+        };
+        let cfg = year_cfg(*year);
+        tokens.extend(quote!(
+            #cfg
+            crate::solvers::Example {
+                year: #year,
+                day: #day,
+                label: #label,
+                input: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #input_path)),
+                expected: #expected,
+            },
+        ));
+    }
+}
+
+#[proc_macro]
+pub fn examples_dispatch(args: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(proc_macro2::Span::call_site(), "expected no arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let examples = read_manifest().examples;
+    quote!(
+        &[#(#examples)*]
+    )
+    .into()
+}
+
+/// Test checking the answers of an example against its `.expected` sidecar.
+struct ExampleTest<'example>(&'example Example);
+
+impl<'example> ToTokens for ExampleTest<'example> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Example {
+            year, day, label, ..
+        } = self.0;
+        let sanitized_label: String = label
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let test_name = format_ident!("example_{}", format!("{year}_{day:02}_{sanitized_label}"));
+        let cfg = year_cfg(*year);
+        tokens.extend(quote!(
+            #cfg
+            #[test]
+            fn #test_name() -> anyhow::Result<()> {
+                crate::solvers::check_example(#year, #day, #label)
+            }
+        ));
+    }
+}
+
+/// Reads a file of the crate, given relative to its manifest directory.
+fn read_crate_file(path: &str) -> String {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set");
+    fs::read_to_string(Path::new(&manifest_dir).join(path))
+        .unwrap_or_else(|err| panic!("cannot read {path}: {err}"))
+}
+
+/// Compile error for an example that was created by `aoc create` but not filled
+/// in, i.e. with an empty input or `TODO` answers in its sidecar.
+struct IncompleteExample<'example>(&'example Example);
+
+impl<'example> ToTokens for IncompleteExample<'example> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let example = self.0;
+        let message = if read_crate_file(&example.input_path).trim().is_empty() {
+            format!(
+                "example {} is empty, paste the example input from the puzzle",
+                example.input_path
+            )
+        } else {
+            match &example.expected_path {
+                Some(path) if read_crate_file(path).contains("\"TODO\"") => {
+                    format!("{path} contains TODO answers, fill in the answers to the example")
+                }
+                _ => return,
+            }
+        };
+        let cfg = year_cfg(example.year);
+        tokens.extend(quote!(
+            #cfg
+            compile_error!(#message);
+        ));
+    }
+}
+
+/// Solver module that was created by `aoc create` but not implemented.
+struct UnmodifiedTemplate {
+    year: i32,
+    path: String,
+}
+
+impl ToTokens for UnmodifiedTemplate {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let message = format!(
+            "{} is still the template created by `aoc create`, implement the solver",
+            self.path
+        );
+        let cfg = year_cfg(self.year);
+        tokens.extend(quote!(
+            #cfg
+            compile_error!(#message);
+        ));
+    }
+}
+
+/// Generates a test for each example with an `.expected` sidecar. Examples
+/// that are empty or have `TODO` answers and solver modules that are still
+/// the template are reported as compile errors.
+#[proc_macro]
+pub fn example_tests(args: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return syn::Error::new(proc_macro2::Span::call_site(), "expected no arguments")
+            .to_compile_error()
+            .into();
+    }
+
+    let Manifest {
+        templates,
+        examples,
+        ..
+    } = read_manifest();
+    let incomplete = examples.iter().map(IncompleteExample);
+    let tests = examples
+        .iter()
+        .filter(|example| example.expected_path.is_some())
+        .map(ExampleTest);
+    quote!(
+        #(#templates)*
+        #(#incomplete)*
+        #(#tests)*
+    )
+    .into()
+}
+
+/// Registers a `SolverImpl` at link time, e.g. with
+/// `#[register_solver(year = 2024, day = 7)]`, so that it does not need to be
+/// in a `src/solvers/year<YYYY>/day<DD>.rs` file or `day<DD>/mod.rs` to be
+/// found. Modules using the attribute are skipped when scanning for solvers.
+///
+/// With `algo = "<name>"`, the solver is registered as an alternative
+/// algorithm for the day instead, selected with `--algo <name>`.
+#[proc_macro_attribute]
+pub fn register_solver(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut registration = RegistrationArgs::default();
+    let args_parser = syn::meta::parser(|meta| registration.parse_meta(meta));
+    parse_macro_input!(args with args_parser);
+    let item = parse_macro_input!(item as DeriveInput);
+    let RegistrationArgs { year, day, algo } = registration;
+    let (Some(year), Some(day)) = (year, day) else {
+        return syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected `year = <YYYY>, day = <DD>`",
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    let algo = match algo {
+        Some(algo) => quote!(Some(#algo)),
+        None => quote!(None),
+    };
+    let ident = &item.ident;
+    // Associated functions without `self` are the same for all lifetimes.
+    let lifetimes: Vec<_> = item.generics.lifetimes().map(|_| quote!('static)).collect();
+    let ty = if lifetimes.is_empty() {
+        quote!(#ident)
+    } else {
+        quote!(#ident<#(#lifetimes),*>)
+    };
+    quote!(
+        #item
+
+        inventory::submit! {
+            crate::solvers::Registration {
+                year: #year,
+                day: #day,
+                algo: #algo,
+                new: |input| Ok(Box::new(<#ident as crate::solvers::Solver>::new(input)?)),
+                parse: |input, iterations| crate::solvers::time_parsing(
+                    <#ident as crate::solvers::Solver>::new,
+                    input,
+                    iterations,
+                ),
+                from_bytes: |input| {
+                    Ok(Box::new(<#ident as crate::solvers::Solver>::from_bytes(input)?))
+                },
+                title: <#ty as crate::solvers::Solver<'static>>::title,
+                tags: <#ty as crate::solvers::Solver<'static>>::tags,
+                normalization: <#ty as crate::solvers::Solver<'static>>::normalization,
+            }
         }
     )
     .into()
@@ -0,0 +1,118 @@
+//! Parsing of `#[register_solver]` arguments, shared by the attribute macro in
+//! `solver-dispatch` and the build script of `aoc`, which skips modules of
+//! self-registering solvers when scanning for solvers.
+
+use syn::{meta::ParseNestedMeta, Attribute, Item, LitInt, LitStr};
+
+#[derive(Default)]
+pub struct RegistrationArgs {
+    pub year: Option<i32>,
+    pub day: Option<u32>,
+    pub algo: Option<LitStr>,
+}
+
+impl RegistrationArgs {
+    pub fn parse_meta(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("year") {
+            self.year = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("day") {
+            self.day = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("algo") {
+            self.algo = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `year`, `day`, or `algo`"))
+        }
+    }
+}
+
+/// Whether one of the items in the Rust source registers the default solver of a
+/// day. Registrations of alternative algorithms do not count.
+pub fn registers_default_solver(source: &str) -> syn::Result<bool> {
+    let file = syn::parse_file(source)?;
+    items_register_default_solver(&file.items)
+}
+
+fn items_register_default_solver(items: &[Item]) -> syn::Result<bool> {
+    for item in items {
+        let registers = match item {
+            Item::Struct(item) => attrs_register_default_solver(&item.attrs)?,
+            Item::Enum(item) => attrs_register_default_solver(&item.attrs)?,
+            Item::Union(item) => attrs_register_default_solver(&item.attrs)?,
+            Item::Mod(item) => match &item.content {
+                Some((_, items)) => items_register_default_solver(items)?,
+                None => false,
+            },
+            _ => false,
+        };
+        if registers {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+fn attrs_register_default_solver(attrs: &[Attribute]) -> syn::Result<bool> {
+    for attr in attrs {
+        let is_register_solver = attr
+            .path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "register_solver");
+        if !is_register_solver {
+            continue;
+        }
+        let mut args = RegistrationArgs::default();
+        attr.parse_nested_meta(|meta| args.parse_meta(meta))?;
+        if args.algo.is_none() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod test {
+    use super::registers_default_solver;
+
+    #[test]
+    fn test_registers_default_solver() {
+        let source = r#"
+            #[register_solver(year = 2023, day = 1)]
+            pub struct SolverImpl;
+        "#;
+        assert!(registers_default_solver(source).unwrap());
+    }
+
+    #[test]
+    fn test_alternative_algorithm_does_not_count() {
+        let source = r#"
+            #[solver_dispatch::register_solver(year = 2023, day = 1, algo = "fast")]
+            pub struct FastSolver;
+        "#;
+        assert!(!registers_default_solver(source).unwrap());
+    }
+
+    #[test]
+    fn test_mentions_outside_of_attributes_do_not_count() {
+        let source = r##"
+            // #[register_solver(year = 2023, day = 1)]
+            const DOC: &str = "#[register_solver(year = 2023, day = 1)]";
+            pub struct SolverImpl;
+        "##;
+        assert!(!registers_default_solver(source).unwrap());
+    }
+
+    #[test]
+    fn test_registration_in_inline_module() {
+        let source = r#"
+            mod solver {
+                #[register_solver(year = 2023, day = 1)]
+                pub enum SolverImpl {}
+            }
+        "#;
+        assert!(registers_default_solver(source).unwrap());
+    }
+}
@@ -0,0 +1,474 @@
+//! Store of verified answers used as a regression baseline.
+//!
+//! Each verified answer remembers where it came from (commit, time, and a
+//! hash of the input it was computed on). When a newly computed answer
+//! disagrees with the verified one, the new value is kept as a pending
+//! conflict until it is explicitly resolved, so the baseline is never
+//! silently overwritten.
+//!
+//! Alternatively, known-correct answers can be kept in an `answers.toml` per
+//! year, for example committed next to the solvers (see [`GoldenAnswers`]).
+
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, fmt::Display, path::Path, process, str::FromStr, sync::Arc};
+
+use aoc::store::{Namespace, Store};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnswerKey {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+}
+
+impl Display for AnswerKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, day {}, part {}", self.year, self.day, self.part)
+    }
+}
+
+impl FromStr for AnswerKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(3, '-');
+        let mut next = || {
+            parts
+                .next()
+                .ok_or_else(|| anyhow!("invalid answer key '{s}'"))
+        };
+        Ok(Self {
+            year: next()?.parse()?,
+            day: next()?.parse()?,
+            part: next()?.parse()?,
+        })
+    }
+}
+
+impl Serialize for AnswerKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!(
+            "{:04}-{:02}-{}",
+            self.year, self.day, self.part
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for AnswerKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Commit of the solver implementation that produced the answer, if known.
+    pub commit: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+    /// SHA-256 of the puzzle input the answer was computed on.
+    pub input_hash: String,
+}
+
+impl Provenance {
+    pub fn for_input(input: &[u8]) -> Self {
+        Self {
+            commit: current_commit(),
+            recorded_at: Utc::now(),
+            input_hash: hash_input(input),
+        }
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "recorded {} from commit {} on input {}",
+            self.recorded_at.format("%Y-%m-%d %H:%M UTC"),
+            self.commit.as_deref().unwrap_or("<unknown>"),
+            &self.input_hash[..self.input_hash.len().min(12)]
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedAnswer {
+    pub answer: String,
+    pub provenance: Provenance,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Entry {
+    verified: RecordedAnswer,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    conflict: Option<RecordedAnswer>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check<'a> {
+    Unverified,
+    Correct,
+    Conflict { verified: &'a RecordedAnswer },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    AcceptNew,
+    KeepOld,
+}
+
+pub struct AnswerStore {
+    store: Arc<dyn Store>,
+    entries: BTreeMap<AnswerKey, Entry>,
+}
+
+/// Key of the answer store in the answers namespace.
+pub const STORE_KEY: &str = "answers.json";
+
+impl AnswerStore {
+    pub fn load(store: Arc<dyn Store>) -> anyhow::Result<Self> {
+        let entries = match store
+            .get(Namespace::Answers, STORE_KEY)
+            .context("reading answer store")?
+        {
+            Some(entry) => {
+                serde_json::from_slice(&entry.content).context("parsing answer store")?
+            }
+            None => BTreeMap::new(),
+        };
+        Ok(Self { store, entries })
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.store
+            .put(
+                Namespace::Answers,
+                STORE_KEY,
+                serde_json::to_string_pretty(&self.entries)?.as_bytes(),
+            )
+            .context("writing answer store")
+    }
+
+    /// Compares a newly computed answer to the verified one. A differing answer
+    /// is remembered as a pending conflict to be resolved later.
+    pub fn check(&mut self, key: AnswerKey, answer: &str, provenance: Provenance) -> Check<'_> {
+        match self.entries.get_mut(&key) {
+            None => Check::Unverified,
+            Some(entry) if entry.verified.answer == answer => Check::Correct,
+            Some(entry) => {
+                entry.conflict = Some(RecordedAnswer {
+                    answer: answer.into(),
+                    provenance,
+                });
+                Check::Conflict {
+                    verified: &entry.verified,
+                }
+            }
+        }
+    }
+
+    /// Records an answer as verified unless a different answer has already
+    /// been verified. Returns whether the answer was recorded.
+    pub fn record(&mut self, key: AnswerKey, answer: &str, provenance: Provenance) -> bool {
+        if let Some(entry) = self.entries.get(&key) {
+            return entry.verified.answer == answer;
+        }
+        self.entries.insert(
+            key,
+            Entry {
+                verified: RecordedAnswer {
+                    answer: answer.into(),
+                    provenance,
+                },
+                conflict: None,
+            },
+        );
+        true
+    }
+
+    /// Merges the verified answers of a serialized answer store, e.g. one
+    /// imported from a bundle. An answer differing from the verified one is
+    /// kept as a pending conflict, or replaces it if `overwrite` is set.
+    /// Returns the keys of the differing answers.
+    pub fn merge(&mut self, content: &[u8], overwrite: bool) -> anyhow::Result<Vec<AnswerKey>> {
+        let other: BTreeMap<AnswerKey, Entry> =
+            serde_json::from_slice(content).context("parsing imported answer store")?;
+        let mut conflicts = Vec::new();
+        for (key, other) in other {
+            let Some(entry) = self.entries.get_mut(&key) else {
+                self.entries.insert(
+                    key,
+                    Entry {
+                        verified: other.verified,
+                        conflict: None,
+                    },
+                );
+                continue;
+            };
+            if entry.verified.answer == other.verified.answer {
+                continue;
+            }
+            conflicts.push(key);
+            if overwrite {
+                entry.verified = other.verified;
+                entry.conflict = None;
+            } else {
+                entry.conflict = Some(other.verified);
+            }
+        }
+        Ok(conflicts)
+    }
+
+    pub fn verified(&self, key: &AnswerKey) -> Option<&RecordedAnswer> {
+        self.entries.get(key).map(|entry| &entry.verified)
+    }
+
+    pub fn conflicts(&self) -> impl Iterator<Item = (AnswerKey, &RecordedAnswer, &RecordedAnswer)> {
+        self.entries.iter().filter_map(|(key, entry)| {
+            entry
+                .conflict
+                .as_ref()
+                .map(|conflict| (*key, &entry.verified, conflict))
+        })
+    }
+
+    pub fn resolve(&mut self, key: AnswerKey, resolution: Resolution) -> anyhow::Result<()> {
+        let entry = self
+            .entries
+            .get_mut(&key)
+            .ok_or_else(|| anyhow!("no verified answer for {key}"))?;
+        let conflict = entry
+            .conflict
+            .take()
+            .ok_or_else(|| anyhow!("no conflict to resolve for {key}"))?;
+        if resolution == Resolution::AcceptNew {
+            entry.verified = conflict;
+        }
+        Ok(())
+    }
+}
+
+/// Known-correct answers of a year read from an `answers.toml` with a table per
+/// day:
+///
+/// ```toml
+/// [1]
+/// part1 = 54388
+/// part2 = "53515"
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GoldenAnswers {
+    answers: BTreeMap<AnswerKey, String>,
+}
+
+impl GoldenAnswers {
+    /// File name of the answers of a year within `dir`.
+    pub fn path(dir: &Path, year: i32) -> std::path::PathBuf {
+        dir.join(format!("year{year}")).join("answers.toml")
+    }
+
+    /// Loads the answers of a year from `dir`. A missing file has no answers.
+    pub fn load(dir: &Path, year: i32) -> anyhow::Result<Self> {
+        let path = Self::path(dir, year);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                Self::parse(year, &content).with_context(|| format!("parsing {}", path.display()))
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err).with_context(|| format!("reading {}", path.display())),
+        }
+    }
+
+    pub fn parse(year: i32, content: &str) -> anyhow::Result<Self> {
+        let days: BTreeMap<String, BTreeMap<String, toml::Value>> = toml::from_str(content)?;
+        let mut answers = BTreeMap::new();
+        for (day, parts) in days {
+            let day: u32 = day
+                .parse()
+                .map_err(|_| anyhow!("expected a table per day, got {day}"))?;
+            for (name, value) in parts {
+                let part = name
+                    .strip_prefix("part")
+                    .and_then(|part| part.parse().ok())
+                    .filter(|part| matches!(part, 1 | 2))
+                    .ok_or_else(|| anyhow!("day {day}: expected part1 or part2, got {name}"))?;
+                let answer = match value {
+                    toml::Value::Integer(answer) => answer.to_string(),
+                    toml::Value::String(answer) => answer,
+                    value => {
+                        return Err(anyhow!(
+                            "day {day}, part {part}: expected a number or string, got {value}"
+                        ))
+                    }
+                };
+                answers.insert(AnswerKey { year, day, part }, answer);
+            }
+        }
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, key: &AnswerKey) -> Option<&str> {
+        self.answers.get(key).map(String::as_str)
+    }
+
+    pub fn extend(&mut self, other: Self) {
+        self.answers.extend(other.answers);
+    }
+}
+
+pub fn hash_input(input: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(input))
+}
+
+fn current_commit() -> Option<String> {
+    let output = process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|commit| commit.trim().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AnswerKey, AnswerStore, Check, GoldenAnswers, Provenance, Resolution};
+    use aoc::store::FileStore;
+    use std::{collections::BTreeMap, sync::Arc};
+
+    const KEY: AnswerKey = AnswerKey {
+        year: 2023,
+        day: 2,
+        part: 1,
+    };
+
+    fn store() -> AnswerStore {
+        AnswerStore {
+            // Never written to by the tests.
+            store: Arc::new(FileStore::new("answers-test")),
+            entries: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_answer_key_round_trip() -> anyhow::Result<()> {
+        let json = serde_json::to_string(&KEY)?;
+        assert_eq!(json, r#""2023-02-1""#);
+        assert_eq!(serde_json::from_str::<AnswerKey>(&json)?, KEY);
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_against_recorded_answer() {
+        let mut store = store();
+        assert_eq!(
+            store.check(KEY, "8", Provenance::for_input(b"a")),
+            Check::Unverified
+        );
+        assert!(store.record(KEY, "8", Provenance::for_input(b"a")));
+        assert_eq!(
+            store.check(KEY, "8", Provenance::for_input(b"a")),
+            Check::Correct
+        );
+        assert!(matches!(
+            store.check(KEY, "9", Provenance::for_input(b"b")),
+            Check::Conflict { verified } if verified.answer == "8"
+        ));
+        assert_eq!(store.conflicts().count(), 1);
+    }
+
+    #[test]
+    fn test_record_does_not_overwrite_verified_answer() {
+        let mut store = store();
+        assert!(store.record(KEY, "8", Provenance::for_input(b"a")));
+        assert!(!store.record(KEY, "9", Provenance::for_input(b"a")));
+        assert_eq!(
+            store.check(KEY, "8", Provenance::for_input(b"a")),
+            Check::Correct
+        );
+    }
+
+    #[test]
+    fn test_resolve_conflict() -> anyhow::Result<()> {
+        for (resolution, expected) in [(Resolution::KeepOld, "8"), (Resolution::AcceptNew, "9")] {
+            let mut store = store();
+            store.record(KEY, "8", Provenance::for_input(b"a"));
+            store.check(KEY, "9", Provenance::for_input(b"b"));
+            store.resolve(KEY, resolution)?;
+            assert_eq!(store.conflicts().count(), 0);
+            assert_eq!(
+                store.check(KEY, expected, Provenance::for_input(b"b")),
+                Check::Correct
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() -> anyhow::Result<()> {
+        let other_key = AnswerKey { part: 2, ..KEY };
+        let mut other = store();
+        other.record(KEY, "9", Provenance::for_input(b"b"));
+        other.record(other_key, "7", Provenance::for_input(b"b"));
+        let content = serde_json::to_vec(&other.entries)?;
+
+        for (overwrite, expected) in [(false, "8"), (true, "9")] {
+            let mut store = store();
+            store.record(KEY, "8", Provenance::for_input(b"a"));
+            assert_eq!(store.merge(&content, overwrite)?, vec![KEY]);
+            assert_eq!(store.verified(&KEY).unwrap().answer, expected);
+            assert_eq!(store.verified(&other_key).unwrap().answer, "7");
+            assert_eq!(store.conflicts().count(), usize::from(!overwrite));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_without_conflict_fails() {
+        let mut store = store();
+        store.record(KEY, "8", Provenance::for_input(b"a"));
+        assert!(store.resolve(KEY, Resolution::AcceptNew).is_err());
+    }
+
+    #[test]
+    fn test_parse_golden_answers() -> anyhow::Result<()> {
+        let answers = GoldenAnswers::parse(
+            2023,
+            "[2]\npart1 = 8\npart2 = \"2286\"\n\n[25]\npart1 = \"abc\"\n",
+        )?;
+        assert_eq!(answers.get(&KEY), Some("8"));
+        assert_eq!(answers.get(&AnswerKey { part: 2, ..KEY }), Some("2286"));
+        assert_eq!(answers.get(&AnswerKey { day: 25, ..KEY }), Some("abc"));
+        assert_eq!(answers.get(&AnswerKey { day: 3, ..KEY }), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_golden_answers_rejects_unknown_parts() {
+        assert!(GoldenAnswers::parse(2023, "[2]\npart3 = 8\n").is_err());
+        assert!(GoldenAnswers::parse(2023, "[2]\npart1 = 1.5\n").is_err());
+    }
+
+    #[test]
+    fn test_load_missing_golden_answers() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        assert_eq!(
+            GoldenAnswers::load(dir.path(), 2023)?,
+            GoldenAnswers::default()
+        );
+
+        let path = GoldenAnswers::path(dir.path(), 2023);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, "[2]\npart1 = 8\n")?;
+        assert_eq!(GoldenAnswers::load(dir.path(), 2023)?.get(&KEY), Some("8"));
+        Ok(())
+    }
+}
@@ -1,65 +1,837 @@
 use anyhow::{anyhow, Context};
 use bytes::Bytes;
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
 use futures_core::Stream;
+use regex::Regex;
 use reqwest::{
     self,
     header::{HeaderMap, HeaderValue},
-    Client, ClientBuilder, Url,
+    Certificate, Client, ClientBuilder, Proxy, Response, StatusCode, Url,
 };
 use secrecy::{ExposeSecret, SecretBox};
+use std::{
+    fmt::Display, future::Future, ops::RangeInclusive, path::PathBuf, pin::Pin, time::Duration,
+};
 use tokio_stream::StreamExt;
+use tracing::{debug, info};
+
+use aoc::solvers::unix_line_endings;
+
+use crate::{
+    leaderboard::Leaderboard,
+    puzzle::{unescape_html, YearProgress},
+};
+
+/// Errors of the client that callers may want to handle specifically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientError {
+    PuzzleNotReleased {
+        year: i32,
+        day: u32,
+        unlocks_at: DateTime<FixedOffset>,
+    },
+    /// The session cookie was rejected or the response is the page shown to
+    /// visitors that are not logged in.
+    SessionInvalid,
+    /// The server failed with a 5xx status, e.g. during an outage.
+    ServerError { status: u16 },
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PuzzleNotReleased {
+                year,
+                day,
+                unlocks_at,
+            } => {
+                write!(f, "puzzle for day {day} of year {year} ")?;
+                match unlocks_at.signed_duration_since(Utc::now()).to_std() {
+                    Ok(remaining) if !remaining.is_zero() => {
+                        write!(f, "unlocks in {}", format_remaining(remaining))?
+                    }
+                    _ => write!(f, "is not available yet")?,
+                }
+                write!(f, " ({})", unlocks_at.format("%Y-%m-%d %H:%M EST"))
+            }
+            Self::SessionInvalid => write!(
+                f,
+                "the session ID is invalid or expired, run `aoc set-session-id` to set a new one"
+            ),
+            Self::ServerError { status } => write!(
+                f,
+                "the Advent of Code server failed with HTTP status {status}, try again later"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// Days with a puzzle in the event of `year`. Since 2025, the event has 12
+/// instead of 25 puzzles.
+pub fn puzzle_days(year: i32) -> RangeInclusive<u32> {
+    if year >= 2025 {
+        1..=12
+    } else {
+        1..=25
+    }
+}
+
+/// Time at which a puzzle is released, midnight EST (UTC-5) on the day of
+/// the puzzle in December, or `None` if there is no such puzzle.
+pub fn unlock_time(year: i32, day: u32) -> Option<DateTime<FixedOffset>> {
+    if !puzzle_days(year).contains(&day) {
+        return None;
+    }
+    let est = FixedOffset::west_opt(5 * 60 * 60).unwrap();
+    let midnight = NaiveDate::from_ymd_opt(year, 12, day)?.and_hms_opt(0, 0, 0)?;
+    est.from_local_datetime(&midnight).single()
+}
+
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    let (days, hours, minutes) = (seconds / 86400, seconds / 3600 % 24, seconds / 60 % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {}s", seconds % 60)
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// Normalizes line endings of a downloaded input to LF and strips a byte
+/// order mark. Fails for content that looks like an HTML page, e.g. an error
+/// page returned instead of the input.
+pub fn sanitize_input(input: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    const BYTE_ORDER_MARK: &[u8] = "\u{feff}".as_bytes();
+    let start = input.trim_ascii_start();
+    let is_html = start
+        .get(.."<!DOCTYPE html>".len())
+        .is_some_and(|doctype| doctype.eq_ignore_ascii_case(b"<!DOCTYPE html>"))
+        || start.starts_with(b"<html");
+    if is_html {
+        return Err(anyhow!("received an HTML page instead of the input"));
+    }
+    let input = match input.strip_prefix(BYTE_ORDER_MARK) {
+        Some(stripped) => stripped.to_vec(),
+        None => input,
+    };
+    Ok(unix_line_endings(input))
+}
+
+/// Extracts the user name shown in the page header.
+fn username_from_html(html: &str) -> Option<String> {
+    lazy_static! {
+        static ref USER: Regex = Regex::new(r#"<div class="user">([^<]*)"#).unwrap();
+    }
+    USER.captures(html)
+        .map(|captures| unescape_html(captures[1].trim()))
+        .filter(|name| !name.is_empty())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    WaitBeforeRetry(Duration),
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Parses the verdict from the HTML page returned after submitting an
+    /// answer.
+    pub fn from_html(html: &str) -> anyhow::Result<Self> {
+        lazy_static! {
+            static ref ARTICLE: Regex = Regex::new(r"(?s)<article>(.*?)</article>").unwrap();
+            static ref WAIT: Regex =
+                Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        }
+
+        let message = ARTICLE
+            .captures(html)
+            .map_or(html, |captures| captures.get(1).unwrap().as_str());
+        if message.contains("That's the right answer") {
+            Ok(Self::Correct)
+        } else if message.contains("your answer is too high") {
+            Ok(Self::TooHigh)
+        } else if message.contains("your answer is too low") {
+            Ok(Self::TooLow)
+        } else if message.contains("That's not the right answer") {
+            Ok(Self::Incorrect)
+        } else if message.contains("You gave an answer too recently") {
+            let wait = WAIT.captures(message).map_or(Ok(0), |captures| {
+                let minutes = captures
+                    .get(1)
+                    .map_or(Ok(0), |m| m.as_str().parse::<u64>())?;
+                let seconds = captures[2].parse::<u64>()?;
+                anyhow::Ok(minutes * 60 + seconds)
+            })?;
+            Ok(Self::WaitBeforeRetry(Duration::from_secs(wait)))
+        } else if message.contains("Did you already complete it") {
+            Ok(Self::AlreadySolved)
+        } else {
+            Err(anyhow!("unrecognized response to answer submission"))
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "correct"),
+            Self::Incorrect => write!(f, "incorrect"),
+            Self::TooHigh => write!(f, "incorrect, too high"),
+            Self::TooLow => write!(f, "incorrect, too low"),
+            Self::WaitBeforeRetry(duration) => {
+                write!(f, "answered too recently, wait {}s", duration.as_secs())
+            }
+            Self::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// User-Agent sent with all requests. AoC asks automated tools to include
+/// contact information, so it can be overridden at build time with the
+/// `AOC_USER_AGENT` environment variable, e.g. to add an email address.
+pub const DEFAULT_USER_AGENT: &str = match option_env!("AOC_USER_AGENT") {
+    Some(user_agent) => user_agent,
+    None => concat!(
+        env!("CARGO_PKG_REPOSITORY"),
+        " by ",
+        env!("CARGO_PKG_AUTHORS"),
+        " (",
+        env!("CARGO_PKG_NAME"),
+        "/",
+        env!("CARGO_PKG_VERSION"),
+        ")"
+    ),
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com/";
+
+/// Maximum number of concurrent requests to the AoC website, to keep the load
+/// on the servers low.
+pub const MAX_CONCURRENT_REQUESTS: usize = 3;
+
+/// Settings of the underlying HTTP client.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    pub user_agent: String,
+    /// Proxy for all requests. If unset, the proxy is taken from the
+    /// `HTTPS_PROXY`, `HTTP_PROXY`, and `ALL_PROXY` environment variables.
+    pub proxy: Option<Url>,
+    /// PEM files with additional root certificates to trust, e.g. of a
+    /// corporate TLS-intercepting proxy.
+    pub root_certificates: Vec<PathBuf>,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            user_agent: DEFAULT_USER_AGENT.into(),
+            proxy: None,
+            root_certificates: vec![],
+        }
+    }
+}
+
+pub type BodyStream = Pin<Box<dyn Stream<Item = anyhow::Result<Bytes>> + Send>>;
+
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub body: BodyStream,
+}
+
+impl HttpResponse {
+    /// Returns the body if the status indicates success.
+    fn into_body(self, url: &Url) -> anyhow::Result<BodyStream> {
+        // AoC responds to a malformed or unknown session cookie with this.
+        if self.status == StatusCode::BAD_REQUEST {
+            return Err(ClientError::SessionInvalid.into());
+        }
+        if self.status.is_server_error() {
+            return Err(ClientError::ServerError {
+                status: self.status.as_u16(),
+            }
+            .into());
+        }
+        if !self.status.is_success() {
+            return Err(anyhow!("HTTP status {} for {url}", self.status));
+        }
+        Ok(self.body)
+    }
+
+    async fn text(self, url: &Url) -> anyhow::Result<String> {
+        let mut body = self.into_body(url)?;
+        let mut text = Vec::new();
+        while let Some(bytes) = body.next().await {
+            text.extend_from_slice(&bytes?);
+        }
+        String::from_utf8(text).context("HTTP response is not valid UTF-8")
+    }
 
+    /// Returns the HTML of a page that requires being logged in.
+    async fn html(self, url: &Url) -> anyhow::Result<String> {
+        let html = self.text(url).await?;
+        // Only shown in the header when not logged in.
+        if html.contains(r#"/auth/login">"#) {
+            return Err(ClientError::SessionInvalid.into());
+        }
+        Ok(html)
+    }
+}
+
+/// Sends the HTTP requests of an [`AocClient`]. Allows to replace the network
+/// with canned responses in tests.
+pub trait HttpTransport {
+    fn get(&self, url: Url) -> impl Future<Output = anyhow::Result<HttpResponse>> + Send;
+
+    fn post_form(
+        &self,
+        url: Url,
+        form: &[(&str, &str)],
+    ) -> impl Future<Output = anyhow::Result<HttpResponse>> + Send;
+}
+
+/// Transport over the network, sending the session cookie with each request.
 #[derive(Debug, Clone)]
-pub struct AocClient {
+pub struct ReqwestTransport {
     client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new(session_id: &SecretBox<String>, options: &ClientOptions) -> anyhow::Result<Self> {
+        let mut headers = HeaderMap::with_capacity(1);
+        let mut session_id =
+            HeaderValue::from_bytes(format!("session={}", session_id.expose_secret()).as_bytes())
+                .map_err(|_| anyhow!("invalid bytes in session ID"))?;
+        session_id.set_sensitive(true);
+        headers.insert("Cookie", session_id);
+        let user_agent = HeaderValue::from_str(&options.user_agent)
+            .map_err(|_| anyhow!("invalid User-Agent"))?;
+
+        let mut builder = ClientBuilder::new()
+            .default_headers(headers)
+            .user_agent(user_agent);
+        if let Some(proxy) = &options.proxy {
+            builder = builder.proxy(Proxy::all(proxy.clone()).context("proxy URL")?);
+        }
+        for path in &options.root_certificates {
+            let pem = std::fs::read(path)
+                .with_context(|| format!("reading root certificate {}", path.display()))?;
+            let certificates = Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("parsing root certificate {}", path.display()))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        Ok(Self {
+            client: builder.build().context("initializing HTTP client")?,
+        })
+    }
+
+    fn into_http_response(response: Response) -> HttpResponse {
+        HttpResponse {
+            status: response.status(),
+            body: Box::pin(
+                response
+                    .bytes_stream()
+                    .map(|bytes| bytes.context("reading HTTP response")),
+            ),
+        }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    async fn get(&self, url: Url) -> anyhow::Result<HttpResponse> {
+        let response = self.client.get(url).send().await.context("HTTP GET")?;
+        Ok(Self::into_http_response(response))
+    }
+
+    async fn post_form(&self, url: Url, form: &[(&str, &str)]) -> anyhow::Result<HttpResponse> {
+        let response = self
+            .client
+            .post(url)
+            .form(form)
+            .send()
+            .await
+            .context("HTTP POST")?;
+        Ok(Self::into_http_response(response))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AocClient<T = ReqwestTransport> {
+    transport: T,
     base_url: Url,
 }
 
 impl AocClient {
-    pub fn new(mut base_url: Url, session_id: SecretBox<String>) -> anyhow::Result<Self> {
+    pub fn new(
+        base_url: Url,
+        session_id: &SecretBox<String>,
+        options: &ClientOptions,
+    ) -> anyhow::Result<Self> {
+        Self::with_transport(base_url, ReqwestTransport::new(session_id, options)?)
+    }
+}
+
+impl<T: HttpTransport> AocClient<T> {
+    pub fn with_transport(mut base_url: Url, transport: T) -> anyhow::Result<Self> {
         if base_url.cannot_be_a_base() {
             return Err(anyhow!("base URL is not a valid base"));
         }
         if base_url.path_segments().unwrap().next_back() != Some("") {
             base_url.path_segments_mut().unwrap().push("");
         }
-        let mut headers = HeaderMap::with_capacity(1);
-        let mut session_id =
-            HeaderValue::from_bytes(format!("session={}", session_id.expose_secret()).as_bytes())
-                .map_err(|_| anyhow!("invalid bytes in session ID"))?;
-        session_id.set_sensitive(true);
-        headers.insert("Cookie", session_id);
         Ok(Self {
-            client: ClientBuilder::new()
-                .default_headers(headers)
-                .build()
-                .expect("couldn't initialize HTTP client"),
+            transport,
             base_url,
         })
     }
 
-    pub async fn get_input(
+    pub async fn get_input(&self, year: i32, day: u32) -> anyhow::Result<BodyStream> {
+        let not_released = || -> anyhow::Error {
+            match unlock_time(year, day) {
+                Some(unlocks_at) => ClientError::PuzzleNotReleased {
+                    year,
+                    day,
+                    unlocks_at,
+                }
+                .into(),
+                None => anyhow!("there is no puzzle for day {day} of year {year}"),
+            }
+        };
+        // Avoid requests that are known to fail.
+        if unlock_time(year, day).is_none_or(|unlocks_at| unlocks_at > Utc::now()) {
+            return Err(not_released());
+        }
+
+        let (url, response) = self
+            .send_get(&[&year.to_string(), "day", &day.to_string(), "input"])
+            .await?;
+        if response.status == StatusCode::NOT_FOUND {
+            return Err(not_released());
+        }
+        response.into_body(&url)
+    }
+
+    /// Fetches the puzzle description page, which also shows the accepted
+    /// answers.
+    pub async fn get_puzzle_page(&self, year: i32, day: u32) -> anyhow::Result<BodyStream> {
+        let (url, response) = self
+            .send_get(&[&year.to_string(), "day", &day.to_string()])
+            .await?;
+        // Buffered to not cache the page shown when not logged in.
+        let html = response.html(&url).await?;
+        Ok(Box::pin(tokio_stream::once(Ok(Bytes::from(html)))))
+    }
+
+    /// Fetches the settings page to confirm that the session is valid and
+    /// returns the name of the logged in user.
+    pub async fn whoami(&self) -> anyhow::Result<String> {
+        let (url, response) = self.send_get(&["settings"]).await?;
+        username_from_html(&response.html(&url).await?)
+            .ok_or_else(|| anyhow!("cannot find the user name on the settings page"))
+    }
+
+    /// Fetches the calendar page of the year to determine the collected
+    /// stars.
+    pub async fn get_year_progress(&self, year: i32) -> anyhow::Result<YearProgress> {
+        let (url, response) = self.send_get(&[&year.to_string()]).await?;
+        Ok(YearProgress::from_html(year, &response.html(&url).await?))
+    }
+
+    /// Fetches a private leaderboard. Use a
+    /// [`LeaderboardCache`](crate::leaderboard::LeaderboardCache) to respect
+    /// the recommended polling interval.
+    pub async fn get_private_leaderboard(
         &self,
         year: i32,
-        day: u32,
-    ) -> anyhow::Result<impl Stream<Item = anyhow::Result<Bytes>>> {
+        owner_id: u64,
+    ) -> anyhow::Result<Leaderboard> {
+        let (url, response) = self
+            .send_get(&[
+                &year.to_string(),
+                "leaderboard",
+                "private",
+                "view",
+                &format!("{owner_id}.json"),
+            ])
+            .await?;
+        // Without a valid session, AoC redirects to the start page.
+        let json = response.html(&url).await?;
+        serde_json::from_str(&json).context("parsing leaderboard")
+    }
+
+    fn url(&self, path_segments: &[&str]) -> Url {
         // path_segments_mut cannot error because pre-conditions are checked
         // on instantiation
         let mut url = self.base_url.clone();
-        url.path_segments_mut().unwrap().extend(&[
-            &year.to_string(),
-            "day",
-            &day.to_string(),
-            "input",
-        ]);
-        Ok(self
-            .client
-            .get(url)
-            .send()
-            .await
-            .context("HTTP GET")?
-            .error_for_status()?
-            .bytes_stream()
-            .map(|bytes| bytes.context("reading HTTP response")))
+        url.path_segments_mut()
+            .unwrap()
+            .pop_if_empty()
+            .extend(path_segments);
+        url
+    }
+
+    async fn send_get(&self, path_segments: &[&str]) -> anyhow::Result<(Url, HttpResponse)> {
+        let url = self.url(path_segments);
+        info!(%url, "HTTP GET");
+        let response = self.transport.get(url.clone()).await?;
+        debug!(status = %response.status, "HTTP response");
+        Ok((url, response))
+    }
+
+    pub async fn submit_answer(
+        &self,
+        year: i32,
+        day: u32,
+        part: u8,
+        answer: &str,
+    ) -> anyhow::Result<Verdict> {
+        let url = self.url(&[&year.to_string(), "day", &day.to_string(), "answer"]);
+        info!(%url, "HTTP POST");
+        let response = self
+            .transport
+            .post_form(
+                url.clone(),
+                &[("level", part.to_string().as_str()), ("answer", answer)],
+            )
+            .await?;
+        debug!(status = %response.status, "HTTP response");
+        let html = response.html(&url).await?;
+        Verdict::from_html(&html)
+    }
+}
+
+#[cfg(test)]
+pub mod testing {
+    use super::{HttpResponse, HttpTransport};
+    use bytes::Bytes;
+    use reqwest::{StatusCode, Url};
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    /// Transport answering requests with canned responses by URL path and
+    /// recording the requests, e.g. `"POST /2023/day/1/answer level=1&answer=42"`.
+    #[derive(Debug, Default, Clone)]
+    pub struct MockTransport {
+        responses: HashMap<String, (StatusCode, String)>,
+        requests: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl MockTransport {
+        pub fn with_response(mut self, path: &str, status: StatusCode, body: &str) -> Self {
+            self.responses.insert(path.into(), (status, body.into()));
+            self
+        }
+
+        pub fn requests(&self) -> Arc<Mutex<Vec<String>>> {
+            self.requests.clone()
+        }
+
+        fn respond(&self, request: String, url: &Url) -> anyhow::Result<HttpResponse> {
+            self.requests.lock().unwrap().push(request);
+            let (status, body) = self
+                .responses
+                .get(url.path())
+                .cloned()
+                .unwrap_or((StatusCode::NOT_FOUND, String::new()));
+            Ok(HttpResponse {
+                status,
+                body: Box::pin(tokio_stream::once(Ok(Bytes::from(body)))),
+            })
+        }
+    }
+
+    impl HttpTransport for MockTransport {
+        async fn get(&self, url: Url) -> anyhow::Result<HttpResponse> {
+            self.respond(format!("GET {}", url.path()), &url)
+        }
+
+        async fn post_form(&self, url: Url, form: &[(&str, &str)]) -> anyhow::Result<HttpResponse> {
+            let form = form
+                .iter()
+                .map(|(key, value)| format!("{key}={value}"))
+                .collect::<Vec<_>>()
+                .join("&");
+            self.respond(format!("POST {} {form}", url.path()), &url)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        format_remaining, sanitize_input, testing::MockTransport, unlock_time, AocClient,
+        ClientError, ClientOptions, Verdict,
+    };
+    use chrono::{TimeDelta, Utc};
+    use reqwest::{StatusCode, Url};
+    use rstest::rstest;
+    use secrecy::SecretBox;
+    use std::time::Duration;
+    use tokio_stream::StreamExt;
+
+    fn new_client(options: &ClientOptions) -> anyhow::Result<AocClient> {
+        AocClient::new(
+            Url::parse("https://adventofcode.com/").unwrap(),
+            &SecretBox::new(Box::new("session".into())),
+            options,
+        )
+    }
+
+    #[rstest]
+    #[case("1\n2\n", "1\n2\n")]
+    #[case("1\r\n2\r\n", "1\n2\n")]
+    #[case("\u{feff}1\n", "1\n")]
+    #[case("<a>\n", "<a>\n")]
+    #[case("\u{feff}\r\n\r", "\n\r")]
+    fn test_sanitize_input(#[case] input: &str, #[case] expected: &str) {
+        assert_eq!(sanitize_input(input.into()).unwrap(), expected.as_bytes());
+    }
+
+    #[rstest]
+    #[case("<!DOCTYPE html>\n<html lang=\"en-us\">")]
+    #[case("\n<html><body>500</body></html>")]
+    #[case("<html>")]
+    fn test_sanitize_input_rejects_html(#[case] input: &str) {
+        assert!(sanitize_input(input.into()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_invalid_user_agent() {
+        assert!(new_client(&ClientOptions::default()).is_ok());
+        assert!(new_client(&ClientOptions {
+            user_agent: "aoc\nX-Injected: 1".into(),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_client_options() {
+        assert!(new_client(&ClientOptions {
+            proxy: Some(Url::parse("http://proxy.example:3128").unwrap()),
+            ..Default::default()
+        })
+        .is_ok());
+
+        let error = new_client(&ClientOptions {
+            root_certificates: vec!["/nonexistent/ca.pem".into()],
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(error.to_string().contains("/nonexistent/ca.pem"), "{error}");
+    }
+
+    fn mock_client(transport: MockTransport) -> AocClient<MockTransport> {
+        AocClient::with_transport(Url::parse("https://aoc.test/base").unwrap(), transport).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_get_input() {
+        let client = mock_client(MockTransport::default().with_response(
+            "/base/2023/day/1/input",
+            StatusCode::OK,
+            "1abc2\n",
+        ));
+        let mut body = client.get_input(2023, 1).await.unwrap();
+        assert_eq!(body.next().await.unwrap().unwrap(), "1abc2\n");
+    }
+
+    #[tokio::test]
+    async fn test_get_input_not_found_is_not_released() {
+        let client = mock_client(MockTransport::default());
+        let error = client.get_input(2023, 1).await.err().unwrap();
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(ClientError::PuzzleNotReleased { day: 1, .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(StatusCode::BAD_REQUEST, "")]
+    #[case(
+        StatusCode::OK,
+        r#"<div><a href="/2023/auth/login">[Log In]</a></div><main>...</main>"#
+    )]
+    #[tokio::test]
+    async fn test_detects_invalid_session(#[case] status: StatusCode, #[case] body: &str) {
+        let client = mock_client(
+            MockTransport::default()
+                .with_response("/base/2023/day/1/input", status, body)
+                .with_response("/base/2023/day/1", status, body),
+        );
+        let error = client.get_puzzle_page(2023, 1).await.err().unwrap();
+        assert_eq!(
+            error.downcast_ref::<ClientError>(),
+            Some(&ClientError::SessionInvalid)
+        );
+        if status != StatusCode::OK {
+            let error = client.get_input(2023, 1).await.err().unwrap();
+            assert_eq!(
+                error.downcast_ref::<ClientError>(),
+                Some(&ClientError::SessionInvalid)
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(StatusCode::INTERNAL_SERVER_ERROR)]
+    #[case(StatusCode::SERVICE_UNAVAILABLE)]
+    #[tokio::test]
+    async fn test_server_error_is_not_invalid_session(#[case] status: StatusCode) {
+        let client = mock_client(
+            MockTransport::default()
+                .with_response("/base/2023/day/1/input", status, "")
+                .with_response("/base/2023/day/1", status, ""),
+        );
+        let expected = ClientError::ServerError {
+            status: status.as_u16(),
+        };
+        let error = client.get_puzzle_page(2023, 1).await.err().unwrap();
+        assert_eq!(error.downcast_ref::<ClientError>(), Some(&expected));
+        let error = client.get_input(2023, 1).await.err().unwrap();
+        assert_eq!(error.downcast_ref::<ClientError>(), Some(&expected));
+    }
+
+    #[tokio::test]
+    async fn test_get_puzzle_page_error_status() {
+        let client = mock_client(MockTransport::default().with_response(
+            "/base/2023/day/1",
+            StatusCode::FORBIDDEN,
+            "",
+        ));
+        let error = client.get_puzzle_page(2023, 1).await.err().unwrap();
+        assert!(error.to_string().contains("403"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_whoami() {
+        let client = mock_client(MockTransport::default().with_response(
+            "/base/settings",
+            StatusCode::OK,
+            r#"<header><div class="user">Jan &amp; Co <span class="star-count">450*</span></div></header>"#,
+        ));
+        assert_eq!(client.whoami().await.unwrap(), "Jan & Co");
+    }
+
+    #[tokio::test]
+    async fn test_get_year_progress() {
+        let client = mock_client(MockTransport::default().with_response(
+            "/base/2023",
+            StatusCode::OK,
+            r#"<a href="/2023/day/1" class="calendar-day1 calendar-complete">"#,
+        ));
+        let progress = client.get_year_progress(2023).await.unwrap();
+        assert_eq!(progress.stars[&1], 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_answer() {
+        let transport = MockTransport::default().with_response(
+            "/base/2023/day/1/answer",
+            StatusCode::OK,
+            &page("That's the right answer!"),
+        );
+        let requests = transport.requests();
+        let client = mock_client(transport);
+        assert_eq!(
+            client.submit_answer(2023, 1, 2, "281").await.unwrap(),
+            Verdict::Correct
+        );
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec!["POST /base/2023/day/1/answer level=2&answer=281"]
+        );
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(
+            unlock_time(2023, 19).unwrap().to_rfc3339(),
+            "2023-12-19T00:00:00-05:00"
+        );
+        assert_eq!(unlock_time(2023, 0), None);
+        assert_eq!(unlock_time(2023, 26), None);
+        assert_eq!(unlock_time(2023, 32), None);
+        assert!(unlock_time(2025, 12).is_some());
+        assert_eq!(unlock_time(2025, 13), None);
+    }
+
+    #[rstest]
+    #[case(45, "45s")]
+    #[case(125, "2m 5s")]
+    #[case(3 * 3600 + 12 * 60 + 30, "3h 12m")]
+    #[case(2 * 86400 + 4 * 3600, "2d 4h")]
+    fn test_format_remaining(#[case] seconds: u64, #[case] expected: &str) {
+        assert_eq!(format_remaining(Duration::from_secs(seconds)), expected);
+    }
+
+    #[test]
+    fn test_not_released_message() {
+        let unlocks_at = (Utc::now() + TimeDelta::minutes(3 * 60 + 13)).fixed_offset();
+        let message = ClientError::PuzzleNotReleased {
+            year: 2023,
+            day: 1,
+            unlocks_at,
+        }
+        .to_string();
+        assert!(
+            message.starts_with("puzzle for day 1 of year 2023 unlocks in 3h 12m"),
+            "{message}"
+        );
+    }
+
+    fn page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    #[rstest]
+    #[case(
+        "That's the right answer!  You are one gold star closer to saving Christmas.",
+        Verdict::Correct
+    )]
+    #[case(
+        "That's not the right answer.  If you're stuck, make sure you're using the full input data.",
+        Verdict::Incorrect
+    )]
+    #[case(
+        "That's not the right answer; your answer is too high.  Please wait one minute before trying again.",
+        Verdict::TooHigh
+    )]
+    #[case(
+        "That's not the right answer; your answer is too low.",
+        Verdict::TooLow
+    )]
+    #[case(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 41s left to wait.",
+        Verdict::WaitBeforeRetry(Duration::from_secs(41))
+    )]
+    #[case(
+        "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 2s left to wait.",
+        Verdict::WaitBeforeRetry(Duration::from_secs(242))
+    )]
+    #[case(
+        "You don't seem to be solving the right level.  Did you already complete it?",
+        Verdict::AlreadySolved
+    )]
+    fn test_verdict_from_html(#[case] message: &str, #[case] expected: Verdict) {
+        assert_eq!(Verdict::from_html(&page(message)).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_verdict_from_unexpected_html() {
+        assert!(Verdict::from_html(&page("Something else")).is_err());
     }
 }
@@ -0,0 +1,58 @@
+//! Files emitted by solvers, e.g. graphs of the input for debugging.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use tracing::info;
+
+use crate::solvers::ArtifactSink;
+
+/// Writes artifacts as files into a directory, which is created on the first
+/// write.
+#[derive(Debug, Clone)]
+pub struct ArtifactDirectory {
+    directory: PathBuf,
+}
+
+impl ArtifactDirectory {
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+}
+
+impl ArtifactSink for ArtifactDirectory {
+    fn write(&self, name: &str, content: &[u8]) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            Path::new(name)
+                .file_name()
+                .is_some_and(|file_name| file_name == name),
+            "invalid artifact name {name}"
+        );
+        std::fs::create_dir_all(&self.directory)
+            .with_context(|| format!("creating {}", self.directory.display()))?;
+        let path = self.directory.join(name);
+        std::fs::write(&path, content).with_context(|| format!("writing {}", path.display()))?;
+        info!(path = %path.display(), "wrote artifact");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ArtifactDirectory;
+    use crate::solvers::ArtifactSink;
+
+    #[test]
+    fn test_write() -> anyhow::Result<()> {
+        let root = tempfile::tempdir()?;
+        let sink = ArtifactDirectory::new(root.path().join("2023-20"));
+        sink.write("graph.dot", b"digraph {}")?;
+        assert_eq!(
+            std::fs::read_to_string(root.path().join("2023-20/graph.dot"))?,
+            "digraph {}"
+        );
+        assert!(sink.write("../escape.txt", b"").is_err());
+        assert!(sink.write("", b"").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Summary statistics over repeated timing measurements.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    pub runs: usize,
+    pub median_seconds: f64,
+    pub mean_seconds: f64,
+    /// Sample standard deviation, zero for a single run.
+    pub std_dev_seconds: f64,
+    pub min_seconds: f64,
+    pub max_seconds: f64,
+    /// Number of runs outside of Tukey's fences (1.5 times the interquartile
+    /// range below the first or above the third quartile).
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Computes the statistics of the given samples in seconds. Returns
+    /// `None` if there are no samples.
+    pub fn from_samples(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        let runs = sorted.len();
+        let mean = sorted.iter().sum::<f64>() / runs as f64;
+        let variance = if runs > 1 {
+            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (runs - 1) as f64
+        } else {
+            0.0
+        };
+        let q1 = quantile(&sorted, 0.25);
+        let q3 = quantile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Some(Self {
+            runs,
+            median_seconds: quantile(&sorted, 0.5),
+            mean_seconds: mean,
+            std_dev_seconds: variance.sqrt(),
+            min_seconds: sorted[0],
+            max_seconds: sorted[runs - 1],
+            outliers: sorted.iter().filter(|&&x| x < lower || x > upper).count(),
+        })
+    }
+}
+
+/// Linearly interpolated quantile of sorted, non-empty samples.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    let fraction = position - lower as f64;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * fraction
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stats;
+
+    #[test]
+    fn test_empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_single_sample() {
+        let stats = Stats::from_samples(&[2.0]).unwrap();
+        assert_eq!(stats.median_seconds, 2.0);
+        assert_eq!(stats.std_dev_seconds, 0.0);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::from_samples(&[4.0, 1.0, 3.0, 2.0, 100.0]).unwrap();
+        assert_eq!(stats.runs, 5);
+        assert_eq!(stats.median_seconds, 3.0);
+        assert_eq!(stats.mean_seconds, 22.0);
+        assert!((stats.std_dev_seconds - 43.6176).abs() < 1e-4);
+        assert_eq!(stats.min_seconds, 1.0);
+        assert_eq!(stats.max_seconds, 100.0);
+        assert_eq!(stats.outliers, 1);
+    }
+}
@@ -0,0 +1,293 @@
+//! Reading the AoC session cookie from the cookie stores of local browsers.
+
+use std::path::{Path, PathBuf};
+
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
+use anyhow::{anyhow, Context};
+use clap::ValueEnum;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use secrecy::SecretBox;
+use tempfile::TempDir;
+use tracing::debug;
+
+const COOKIE_HOST: &str = ".adventofcode.com";
+const COOKIE_NAME: &str = "session";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Browser {
+    Firefox,
+    /// Google Chrome or Chromium. Only unencrypted cookies and cookies
+    /// encrypted without a key from the OS keychain are supported.
+    Chrome,
+}
+
+impl Browser {
+    /// Cookie databases of all profiles of the browser.
+    fn cookie_databases(self) -> Vec<PathBuf> {
+        let home = dirs::home_dir().unwrap_or_default();
+        let (roots, file_names): (Vec<PathBuf>, &[&str]) = match self {
+            Self::Firefox => (
+                vec![
+                    home.join(".mozilla/firefox"),
+                    home.join("snap/firefox/common/.mozilla/firefox"),
+                    home.join("Library/Application Support/Firefox/Profiles"),
+                    dirs::data_dir()
+                        .unwrap_or_default()
+                        .join("Mozilla/Firefox/Profiles"),
+                ],
+                &["cookies.sqlite"],
+            ),
+            Self::Chrome => (
+                ["google-chrome", "chromium", "Google/Chrome", "Chromium"]
+                    .into_iter()
+                    .flat_map(|name| {
+                        [dirs::config_dir(), dirs::data_local_dir()]
+                            .into_iter()
+                            .flatten()
+                            .map(move |base| base.join(name))
+                    })
+                    .chain([home.join("Library/Application Support/Google/Chrome")])
+                    .collect(),
+                &["Network/Cookies", "Cookies"],
+            ),
+        };
+        roots
+            .iter()
+            .filter_map(|root| std::fs::read_dir(root).ok())
+            .flatten()
+            .flatten()
+            .flat_map(|profile| file_names.iter().map(move |name| profile.path().join(name)))
+            .filter(|path| path.is_file())
+            .collect()
+    }
+}
+
+/// Finds the AoC session cookie in any profile of the browser.
+pub fn session_cookie(browser: Browser) -> anyhow::Result<SecretBox<String>> {
+    let databases = browser.cookie_databases();
+    if databases.is_empty() {
+        return Err(anyhow!("no cookie store of {browser:?} found"));
+    }
+    let mut cookies = Vec::new();
+    for database in &databases {
+        debug!(database = %database.display(), "reading cookies");
+        if let Some(cookie) = read_cookie(browser, database)
+            .with_context(|| format!("reading cookies from {}", database.display()))?
+        {
+            cookies.push(cookie);
+        }
+    }
+    // Prefer the profile used most recently.
+    cookies
+        .into_iter()
+        .max_by_key(|cookie| cookie.last_access)
+        .map(|cookie| SecretBox::new(Box::new(cookie.value)))
+        .ok_or_else(|| anyhow!("not logged into adventofcode.com in {browser:?}"))
+}
+
+struct Cookie {
+    value: String,
+    /// Time of last access in the unit of the browser.
+    last_access: i64,
+}
+
+fn read_cookie(browser: Browser, database: &Path) -> anyhow::Result<Option<Cookie>> {
+    // Browsers keep their databases locked, so read a copy.
+    let copy = CopiedDatabase::new(database)?;
+    let connection = Connection::open_with_flags(&copy.path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    match browser {
+        Browser::Firefox => Ok(connection
+            .query_row(
+                "SELECT value, lastAccessed FROM moz_cookies WHERE host = ?1 AND name = ?2
+                ORDER BY lastAccessed DESC LIMIT 1",
+                [COOKIE_HOST, COOKIE_NAME],
+                |row| {
+                    Ok(Cookie {
+                        value: row.get(0)?,
+                        last_access: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?),
+        Browser::Chrome => {
+            let Some((value, encrypted_value, last_access)) = connection
+                .query_row(
+                    "SELECT value, encrypted_value, last_access_utc FROM cookies
+                    WHERE host_key = ?1 AND name = ?2 ORDER BY last_access_utc DESC LIMIT 1",
+                    [COOKIE_HOST, COOKIE_NAME],
+                    |row| {
+                        Ok((
+                            row.get::<_, String>(0)?,
+                            row.get::<_, Vec<u8>>(1)?,
+                            row.get(2)?,
+                        ))
+                    },
+                )
+                .optional()?
+            else {
+                return Ok(None);
+            };
+            let value = if encrypted_value.is_empty() {
+                value
+            } else {
+                let version: Option<String> = connection
+                    .query_row("SELECT value FROM meta WHERE key = 'version'", [], |row| {
+                        row.get(0)
+                    })
+                    .optional()?;
+                let version = version
+                    .and_then(|version| version.parse().ok())
+                    .unwrap_or(0);
+                decrypt_chrome_cookie(&encrypted_value, version)?
+            };
+            Ok(Some(Cookie { value, last_access }))
+        }
+    }
+}
+
+/// Decrypts a cookie encrypted by Chrome on Linux without a keyring.
+/// `db_version` is the version of the cookie database.
+fn decrypt_chrome_cookie(encrypted: &[u8], db_version: u32) -> anyhow::Result<String> {
+    let Some(ciphertext) = encrypted.strip_prefix(b"v10") else {
+        return Err(anyhow!(
+            "the cookie is encrypted with a key from the OS keychain, which is not supported; \
+             copy the cookie from the browser's developer tools instead"
+        ));
+    };
+    let mut key = [0; 16];
+    pbkdf2::pbkdf2_hmac::<sha1::Sha1>(b"peanuts", b"saltysalt", 1, &mut key);
+    let plaintext = cbc::Decryptor::<aes::Aes128>::new(&key.into(), &[b' '; 16].into())
+        .decrypt_padded_vec_mut::<Pkcs7>(ciphertext)
+        .map_err(|_| anyhow!("cannot decrypt cookie"))?;
+    // Since database version 24, the value is prefixed with a SHA-256 hash of
+    // the host.
+    let plaintext = if db_version >= 24 {
+        plaintext.get(32..).unwrap_or_default()
+    } else {
+        &plaintext
+    };
+    Ok(String::from_utf8(plaintext.to_vec())?)
+}
+
+/// Copy of a database, including its write-ahead log, that is deleted on drop.
+/// It is placed in a new directory only accessible by the current user, since
+/// it contains session cookies.
+struct CopiedDatabase {
+    _directory: TempDir,
+    path: PathBuf,
+}
+
+impl CopiedDatabase {
+    fn new(database: &Path) -> anyhow::Result<Self> {
+        let mut builder = tempfile::Builder::new();
+        builder.prefix("aoc-cookies-");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(std::fs::Permissions::from_mode(0o700));
+        }
+        let directory = builder
+            .tempdir()
+            .context("creating a temporary directory for the cookie database")?;
+        let path = directory.path().join("cookies.sqlite");
+        std::fs::copy(database, &path)?;
+        let mut wal = database.as_os_str().to_owned();
+        wal.push("-wal");
+        if Path::new(&wal).exists() {
+            std::fs::copy(&wal, directory.path().join("cookies.sqlite-wal"))?;
+        }
+        Ok(Self {
+            _directory: directory,
+            path,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decrypt_chrome_cookie, read_cookie, Browser, CopiedDatabase};
+    use aes::cipher::{block_padding::Pkcs7, BlockEncryptMut, KeyIvInit};
+    use rusqlite::Connection;
+    use sha2::{Digest, Sha256};
+
+    fn encrypt_chrome_cookie(value: &[u8]) -> Vec<u8> {
+        let mut key = [0; 16];
+        pbkdf2::pbkdf2_hmac::<sha1::Sha1>(b"peanuts", b"saltysalt", 1, &mut key);
+        let ciphertext = cbc::Encryptor::<aes::Aes128>::new(&key.into(), &[b' '; 16].into())
+            .encrypt_padded_vec_mut::<Pkcs7>(value);
+        [b"v10".as_slice(), &ciphertext].concat()
+    }
+
+    #[test]
+    fn test_firefox() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("cookies.sqlite");
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE moz_cookies (host TEXT, name TEXT, value TEXT, lastAccessed INTEGER);
+                INSERT INTO moz_cookies VALUES ('.adventofcode.com', 'session', 'old', 1);
+                INSERT INTO moz_cookies VALUES ('.adventofcode.com', 'session', 'new', 2);
+                INSERT INTO moz_cookies VALUES ('.example.com', 'session', 'other', 3);",
+            )
+            .unwrap();
+
+        let cookie = read_cookie(Browser::Firefox, &path).unwrap().unwrap();
+        assert_eq!(cookie.value, "new");
+        assert_eq!(cookie.last_access, 2);
+    }
+
+    #[test]
+    fn test_chrome() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("Cookies");
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE meta (key TEXT, value TEXT);
+                INSERT INTO meta VALUES ('version', '24');
+                CREATE TABLE cookies (
+                    host_key TEXT, name TEXT, value TEXT, encrypted_value BLOB,
+                    last_access_utc INTEGER
+                );",
+            )
+            .unwrap();
+        let plaintext = [Sha256::digest(b".adventofcode.com").as_slice(), b"secret"].concat();
+        connection
+            .execute(
+                "INSERT INTO cookies VALUES ('.adventofcode.com', 'session', '', ?1, 5)",
+                [encrypt_chrome_cookie(&plaintext)],
+            )
+            .unwrap();
+
+        let cookie = read_cookie(Browser::Chrome, &path).unwrap().unwrap();
+        assert_eq!(cookie.value, "secret");
+    }
+
+    #[test]
+    fn test_decrypt_chrome_cookie() {
+        let encrypted = encrypt_chrome_cookie(b"secret");
+        assert_eq!(decrypt_chrome_cookie(&encrypted, 23).unwrap(), "secret");
+        assert!(decrypt_chrome_cookie(b"v11abc", 23).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_copied_database_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let directory = tempfile::tempdir().unwrap();
+        let database = directory.path().join("cookies.sqlite");
+        std::fs::write(&database, "cookies").unwrap();
+
+        let copy = CopiedDatabase::new(&database).unwrap();
+        let copy_directory = copy.path.parent().unwrap().to_path_buf();
+        let mode = std::fs::metadata(&copy_directory)
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o700);
+        assert_eq!(std::fs::read_to_string(&copy.path).unwrap(), "cookies");
+        drop(copy);
+        assert!(!copy_directory.exists());
+    }
+}
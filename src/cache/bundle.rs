@@ -0,0 +1,237 @@
+//! Bundles of cache entries for moving a cache between machines.
+//!
+//! A bundle is a zstd compressed tar archive with a file
+//! `<namespace>/<key>` per entry holding the entry as stored. The checksum of
+//! an entry, if any, is kept in the PAX extended header of its file.
+//!
+//! The answer store is not replaced as a whole on import, but merged answer by
+//! answer (see [`AnswerStore::merge`]).
+
+use std::{
+    io::{Read, Write},
+    path::{Component, Path},
+    sync::Arc,
+    time::UNIX_EPOCH,
+};
+
+use anyhow::{anyhow, Context};
+use tracing::debug;
+
+use aoc::store::{Namespace, Store};
+
+use crate::answers::{self, AnswerKey, AnswerStore};
+
+/// Key of the PAX extended header holding the checksum of an entry.
+const CHECKSUM_PAX_KEY: &str = "AOC.checksum";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub imported: usize,
+    /// Entries that already existed and were kept.
+    pub skipped: usize,
+    /// Imported answers differing from the verified ones.
+    pub conflicts: Vec<AnswerKey>,
+}
+
+/// Writes all entries of the namespaces as a bundle. Returns the number of
+/// exported entries.
+pub fn export<W: Write>(
+    store: &dyn Store,
+    namespaces: &[Namespace],
+    writer: W,
+) -> anyhow::Result<usize> {
+    let mut archive = tar::Builder::new(zstd::Encoder::new(writer, 0)?.auto_finish());
+    let mut count = 0;
+    for &namespace in namespaces {
+        for info in store.list(namespace)? {
+            let Some(entry) = store.get(namespace, &info.key)? else {
+                continue;
+            };
+            let mut header = tar::Header::new_gnu();
+            header.set_size(entry.content.len() as u64);
+            header.set_mode(0o644);
+            header.set_mtime(
+                entry
+                    .modified
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+            );
+            let path = format!("{}/{}", namespace.name(), info.key);
+            if let Some(checksum) = &entry.checksum {
+                archive.append_pax_extensions([(CHECKSUM_PAX_KEY, checksum.as_bytes())])?;
+            }
+            archive
+                .append_data(&mut header, &path, entry.content.as_slice())
+                .with_context(|| format!("adding {path} to bundle"))?;
+            count += 1;
+        }
+    }
+    archive.into_inner()?.flush()?;
+    Ok(count)
+}
+
+/// Stores the entries of a bundle. Existing entries are only replaced if
+/// `overwrite` is set.
+pub fn import<R: Read>(
+    store: &Arc<dyn Store>,
+    reader: R,
+    overwrite: bool,
+) -> anyhow::Result<ImportSummary> {
+    let mut archive = tar::Archive::new(zstd::Decoder::new(reader)?);
+    let mut summary = ImportSummary::default();
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?.into_owned();
+        let (namespace, key) = parse_path(&path)
+            .ok_or_else(|| anyhow!("invalid entry {} in bundle", path.display()))?;
+        if namespace == Namespace::Answers && key == answers::STORE_KEY {
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            let mut answer_store = AnswerStore::load(store.clone())?;
+            summary.conflicts = answer_store.merge(&content, overwrite)?;
+            answer_store.save()?;
+            summary.imported += 1;
+            continue;
+        }
+        if !overwrite && store.contains(namespace, key)? {
+            debug!(namespace = namespace.name(), key, "keeping existing entry");
+            summary.skipped += 1;
+            continue;
+        }
+        let checksum = checksum(&mut entry)?;
+        let mut content = Vec::new();
+        entry.read_to_end(&mut content)?;
+        store.put_with_checksum(namespace, key, &content, checksum.as_deref())?;
+        summary.imported += 1;
+    }
+    Ok(summary)
+}
+
+fn checksum<R: Read>(entry: &mut tar::Entry<R>) -> anyhow::Result<Option<String>> {
+    let Some(extensions) = entry.pax_extensions()? else {
+        return Ok(None);
+    };
+    for extension in extensions {
+        let extension = extension?;
+        if extension.key()? == CHECKSUM_PAX_KEY {
+            return Ok(Some(extension.value()?.into()));
+        }
+    }
+    Ok(None)
+}
+
+/// Splits a path in a bundle into namespace and key, rejecting anything that
+/// could end up outside of the namespace.
+fn parse_path(path: &Path) -> Option<(Namespace, &str)> {
+    let mut components = path.components();
+    let (Some(Component::Normal(namespace)), Some(Component::Normal(key)), None) =
+        (components.next(), components.next(), components.next())
+    else {
+        return None;
+    };
+    let namespace = Namespace::from_name(namespace.to_str()?)?;
+    let key = key.to_str()?;
+    (!key.starts_with('.')).then_some((namespace, key))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{export, import, parse_path, ImportSummary};
+    use crate::answers::{AnswerKey, AnswerStore, Provenance};
+    use aoc::store::{FileStore, Namespace, Store};
+    use rstest::rstest;
+    use std::{path::Path, sync::Arc};
+
+    #[test]
+    fn test_round_trip() {
+        let source_directory = tempfile::tempdir().unwrap();
+        let source = FileStore::new(source_directory.path());
+        source
+            .put_with_checksum(Namespace::Inputs, "2023-01", b"input", Some("abc"))
+            .unwrap();
+        source.put(Namespace::Pages, "2023-01", b"page").unwrap();
+        source
+            .put(Namespace::Leaderboards, "2023-1.json", b"{}")
+            .unwrap();
+
+        let mut bundle = Vec::new();
+        let count = export(&source, &[Namespace::Inputs, Namespace::Pages], &mut bundle).unwrap();
+        assert_eq!(count, 2);
+
+        let target_directory = tempfile::tempdir().unwrap();
+        let target: Arc<dyn Store> = Arc::new(FileStore::new(target_directory.path()));
+        target
+            .put(Namespace::Pages, "2023-01", b"newer page")
+            .unwrap();
+        let summary = import(&target, bundle.as_slice(), false).unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                imported: 1,
+                skipped: 1,
+                conflicts: Vec::new(),
+            }
+        );
+        let input = target.get(Namespace::Inputs, "2023-01").unwrap().unwrap();
+        assert_eq!(input.content, b"input");
+        assert_eq!(input.checksum.as_deref(), Some("abc"));
+        let page = target.get(Namespace::Pages, "2023-01").unwrap().unwrap();
+        assert_eq!(page.content, b"newer page");
+        assert!(!target
+            .contains(Namespace::Leaderboards, "2023-1.json")
+            .unwrap());
+
+        import(&target, bundle.as_slice(), true).unwrap();
+        let page = target.get(Namespace::Pages, "2023-01").unwrap().unwrap();
+        assert_eq!(page.content, b"page");
+        assert_eq!(page.checksum, None);
+    }
+
+    #[test]
+    fn test_import_merges_answers() {
+        const KEY: AnswerKey = AnswerKey {
+            year: 2023,
+            day: 1,
+            part: 1,
+        };
+        let other_key = AnswerKey { part: 2, ..KEY };
+
+        let source_directory = tempfile::tempdir().unwrap();
+        let source: Arc<dyn Store> = Arc::new(FileStore::new(source_directory.path()));
+        let mut answers = AnswerStore::load(source.clone()).unwrap();
+        answers.record(KEY, "1", Provenance::for_input(b"input"));
+        answers.record(other_key, "2", Provenance::for_input(b"input"));
+        answers.save().unwrap();
+        let mut bundle = Vec::new();
+        export(source.as_ref(), &[Namespace::Answers], &mut bundle).unwrap();
+
+        let target_directory = tempfile::tempdir().unwrap();
+        let target: Arc<dyn Store> = Arc::new(FileStore::new(target_directory.path()));
+        let mut answers = AnswerStore::load(target.clone()).unwrap();
+        answers.record(KEY, "3", Provenance::for_input(b"input"));
+        answers.save().unwrap();
+
+        let summary = import(&target, bundle.as_slice(), false).unwrap();
+        assert_eq!(summary.conflicts, vec![KEY]);
+        let answers = AnswerStore::load(target).unwrap();
+        assert_eq!(answers.verified(&KEY).unwrap().answer, "3");
+        assert_eq!(answers.verified(&other_key).unwrap().answer, "2");
+        assert_eq!(answers.conflicts().count(), 1);
+    }
+
+    #[rstest]
+    #[case("inputs/2023-01", Some((Namespace::Inputs, "2023-01")))]
+    #[case("answers/answers.json", Some((Namespace::Answers, "answers.json")))]
+    #[case("unknown/2023-01", None)]
+    #[case("inputs/../2023-01", None)]
+    #[case("/inputs/2023-01", None)]
+    #[case("inputs/.2023-01.tmp", None)]
+    #[case("inputs", None)]
+    fn test_parse_path(#[case] path: &str, #[case] expected: Option<(Namespace, &str)>) {
+        assert_eq!(parse_path(Path::new(path)), expected);
+    }
+}
@@ -0,0 +1,596 @@
+//! Caching of data fetched from the AoC website.
+//!
+//! The entries are kept in a [`Store`] of the library, see [`aoc::store`].
+
+use anyhow::Context;
+use bytes::Bytes;
+use futures_core::{Future, Stream};
+use futures_util::{stream, StreamExt};
+use indicatif::ProgressBar;
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime},
+};
+use tracing::{debug, info, warn};
+
+use crate::progress::DownloadProgress;
+
+pub mod bundle;
+
+use aoc::store::{decode, encode, Compression, EntryInfo, Namespace, Store};
+
+/// Removes all entries in the namespaces that were last written before
+/// `cutoff`, or all entries if there is no cutoff. Returns the removed entries.
+pub fn prune(
+    store: &dyn Store,
+    namespaces: &[Namespace],
+    cutoff: Option<SystemTime>,
+) -> anyhow::Result<Vec<(Namespace, EntryInfo)>> {
+    let mut removed = Vec::new();
+    for &namespace in namespaces {
+        for info in store.list(namespace)? {
+            if cutoff.is_some_and(|cutoff| info.modified >= cutoff) {
+                continue;
+            }
+            debug!(
+                namespace = namespace.name(),
+                key = info.key,
+                "pruning cache entry"
+            );
+            store.remove(namespace, &info.key)?;
+            removed.push((namespace, info));
+        }
+    }
+    Ok(removed)
+}
+
+pub trait Key {
+    type Serialization: AsRef<str>;
+
+    fn serialize(&self) -> Self::Serialization;
+
+    /// Human readable description of the key for messages.
+    fn describe(&self) -> String {
+        self.serialize().as_ref().into()
+    }
+}
+
+/// Returns how long an entry with the given content stays valid, or `None` if
+/// it never expires.
+pub type Expiry = fn(&[u8]) -> Option<Duration>;
+
+/// Prepares fetched content for storing, rejecting content that must not be
+/// cached.
+pub type Sanitizer = fn(Vec<u8>) -> anyhow::Result<Vec<u8>>;
+
+/// Decoded entry kept in memory.
+#[derive(Debug, Clone)]
+struct MemoryEntry {
+    content: Arc<[u8]>,
+    modified: SystemTime,
+}
+
+pub struct FileCache<K, Fetch, FetchReturn, FetchOutput>
+where
+    K: Key + Copy,
+    Fetch: Fn(K) -> FetchReturn,
+    FetchReturn: Future<Output = anyhow::Result<FetchOutput>>,
+    FetchOutput: Stream<Item = anyhow::Result<Bytes>>,
+{
+    key: PhantomData<K>,
+    store: Arc<dyn Store>,
+    /// Entries read or fetched by this process by their serialized key, so
+    /// that repeated accesses neither read nor decode them again.
+    memory: Mutex<HashMap<String, MemoryEntry>>,
+    namespace: Namespace,
+    fetch: Fetch,
+    progress: Option<(DownloadProgress, &'static str)>,
+    expiry: Option<Expiry>,
+    sanitizer: Option<Sanitizer>,
+    compression: Compression,
+}
+
+impl<K, Fetch, FetchReturn, FetchOutput> FileCache<K, Fetch, FetchReturn, FetchOutput>
+where
+    K: Key + Copy + Send + 'static,
+    Fetch: Fn(K) -> FetchReturn,
+    FetchReturn: Future<Output = anyhow::Result<FetchOutput>>,
+    FetchOutput: Stream<Item = anyhow::Result<Bytes>> + std::marker::Unpin,
+{
+    pub fn new(store: Arc<dyn Store>, namespace: Namespace, fetch: Fetch) -> Self {
+        Self {
+            store,
+            namespace,
+            fetch,
+            key: PhantomData,
+            memory: Mutex::default(),
+            progress: None,
+            expiry: None,
+            sanitizer: None,
+            compression: Compression::None,
+        }
+    }
+
+    /// Shows the progress when fetching entries, describing them as `what`,
+    /// e.g. "input".
+    pub fn with_progress(mut self, progress: DownloadProgress, what: &'static str) -> Self {
+        self.progress = Some((progress, what));
+        self
+    }
+
+    /// Refetches entries once they are older than the duration returned by
+    /// `expiry` for their content.
+    pub fn with_expiry(mut self, expiry: Expiry) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// Passes fetched content through `sanitizer` before storing it.
+    pub fn with_sanitizer(mut self, sanitizer: Sanitizer) -> Self {
+        self.sanitizer = Some(sanitizer);
+        self
+    }
+
+    /// Compresses newly written entries. Entries are decompressed on read
+    /// based on their content, so existing entries remain readable when
+    /// changing the compression.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub async fn get(&self, key: &K) -> anyhow::Result<Arc<[u8]>> {
+        let Some(entry) = self.lookup(key).await? else {
+            info!(key = key.serialize().as_ref(), "cache miss, fetching");
+            return self.populate(key).await;
+        };
+
+        if !self.is_expired(entry.modified, &entry.content) {
+            debug!(key = key.serialize().as_ref(), "cache hit");
+            return Ok(entry.content);
+        }
+        info!(
+            key = key.serialize().as_ref(),
+            "cache entry expired, fetching"
+        );
+        match self.populate(key).await {
+            Ok(content) => Ok(content),
+            Err(err) => {
+                warn!(key = key.serialize().as_ref(), error = %err, "cannot refresh cache entry, using expired one");
+                Ok(entry.content)
+            }
+        }
+    }
+
+    /// Returns the entry from memory or, failing that, from the store.
+    async fn lookup(&self, key: &K) -> anyhow::Result<Option<MemoryEntry>> {
+        let serialized = key.serialize().as_ref().to_string();
+        if let Some(entry) = self.memory.lock().unwrap().get(&serialized) {
+            return Ok(Some(entry.clone()));
+        }
+        let decoded = self
+            .with_store(move |store, namespace| {
+                let Some(entry) = store.get(namespace, &serialized)? else {
+                    return Ok(None);
+                };
+                let modified = entry.modified;
+                match decode(entry) {
+                    Ok(content) => Ok(Some((content, modified))),
+                    Err(err) => {
+                        warn!(key = serialized, error = %err, "discarding corrupt cache entry");
+                        store.remove(namespace, &serialized)?;
+                        Ok(None)
+                    }
+                }
+            })
+            .await?;
+        Ok(decoded.map(|(content, modified)| self.remember(key, content.into(), modified)))
+    }
+
+    /// Runs an operation on the store on a thread for blocking work, so that
+    /// concurrent fetches are not held up by slow disks or a locked database.
+    async fn with_store<T: Send + 'static>(
+        &self,
+        operation: impl FnOnce(&dyn Store, Namespace) -> anyhow::Result<T> + Send + 'static,
+    ) -> anyhow::Result<T> {
+        let store = self.store.clone();
+        let namespace = self.namespace;
+        tokio::task::spawn_blocking(move || operation(store.as_ref(), namespace)).await?
+    }
+
+    fn remember(&self, key: &K, content: Arc<[u8]>, modified: SystemTime) -> MemoryEntry {
+        let entry = MemoryEntry { content, modified };
+        self.memory
+            .lock()
+            .unwrap()
+            .insert(key.serialize().as_ref().into(), entry.clone());
+        entry
+    }
+
+    fn is_expired(&self, modified: SystemTime, content: &[u8]) -> bool {
+        self.expiry
+            .and_then(|expiry| expiry(content))
+            .is_some_and(|max_age| modified.elapsed().unwrap_or_default() >= max_age)
+    }
+
+    /// Fetches the entry and stores it, returning its content.
+    pub async fn populate(&self, key: &K) -> anyhow::Result<Arc<[u8]>> {
+        let bar = self.progress.as_ref().map(|(progress, what)| {
+            progress.start(format!("fetching {what} for {}...", key.describe()))
+        });
+        let result = self.fetch_entry(key, bar.as_ref()).await;
+        if let (Some(bar), Some((_, what))) = (bar, &self.progress) {
+            match result {
+                Ok(_) => bar.finish_with_message(format!("fetched {what} for {}", key.describe())),
+                Err(_) => bar.finish_and_clear(),
+            }
+        }
+        let mut content = result?;
+        if let Some(sanitizer) = self.sanitizer {
+            content = sanitizer(content)
+                .with_context(|| format!("invalid content for {}", key.describe()))?;
+        }
+
+        let (encoded, checksum) = encode(&content, self.compression)?;
+        // Only complete entries are stored, so that an interrupted download
+        // never leaves a truncated entry behind.
+        let serialized = key.serialize().as_ref().to_string();
+        self.with_store(move |store, namespace| {
+            store.put_with_checksum(namespace, &serialized, &encoded, Some(&checksum))
+        })
+        .await?;
+        debug!(
+            key = key.serialize().as_ref(),
+            bytes = content.len(),
+            "populated cache entry"
+        );
+        Ok(self
+            .remember(key, content.into(), SystemTime::now())
+            .content)
+    }
+
+    async fn fetch_entry(&self, key: &K, bar: Option<&ProgressBar>) -> anyhow::Result<Vec<u8>> {
+        let mut source = (self.fetch)(*key).await?;
+        let mut content = Vec::new();
+        while let Some(bytes) = source.next().await {
+            let bytes = bytes?;
+            if let Some(bar) = bar {
+                bar.inc(bytes.len() as u64);
+            }
+            content.extend_from_slice(&bytes);
+        }
+        Ok(content)
+    }
+
+    /// Fetches the entries for all keys that are not cached yet, up to
+    /// `max_concurrent` at a time. Failures are only logged; they surface
+    /// again when the entry is accessed with [`FileCache::get`].
+    pub async fn prefetch(&self, keys: impl IntoIterator<Item = K>, max_concurrent: usize) {
+        let keys: Vec<_> = keys
+            .into_iter()
+            .map(|key| (key.serialize().as_ref().to_string(), key))
+            .collect();
+        let missing = self
+            .with_store(move |store, namespace| {
+                Ok(keys
+                    .into_iter()
+                    .filter(|(serialized, _)| {
+                        !store.contains(namespace, serialized).unwrap_or(false)
+                    })
+                    .map(|(_, key)| key)
+                    .collect::<Vec<_>>())
+            })
+            .await;
+        let missing = match missing {
+            Ok(missing) => missing,
+            Err(err) => {
+                debug!(error = %err, "cannot check for cached entries");
+                return;
+            }
+        };
+        if missing.is_empty() {
+            return;
+        }
+        info!(count = missing.len(), "prefetching cache entries");
+        stream::iter(missing)
+            .map(|key| async move {
+                if let Err(err) = self.populate(&key).await {
+                    debug!(key = key.serialize().as_ref(), error = %err, "prefetch failed");
+                }
+            })
+            .buffer_unordered(max_concurrent)
+            .collect::<()>()
+            .await;
+    }
+
+    /// Returns the entry for the key only if it is cached, without fetching.
+    pub async fn get_cached(&self, key: &K) -> Option<Arc<[u8]>> {
+        Some(self.lookup(key).await.ok()??.content)
+    }
+
+    /// Removes the entry for the key, if any, so that it is fetched again on
+    /// the next access.
+    pub async fn invalidate(&self, key: &K) -> anyhow::Result<()> {
+        debug!(key = key.serialize().as_ref(), "invalidating cache entry");
+        self.memory.lock().unwrap().remove(key.serialize().as_ref());
+        let serialized = key.serialize().as_ref().to_string();
+        self.with_store(move |store, namespace| store.remove(namespace, &serialized))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FileCache, Key};
+    use crate::answers::hash_input;
+    use crate::aoc_client::{testing::MockTransport, AocClient};
+    use anyhow::anyhow;
+    use aoc::store::{Compression, FileStore, Namespace, Store, GZIP_MAGIC};
+    use bytes::Bytes;
+    use reqwest::{StatusCode, Url};
+    use std::time::SystemTime;
+    use std::{sync::Arc, time::Duration};
+
+    #[derive(Debug, Clone, Copy)]
+    struct DayKey(u32);
+
+    impl Key for DayKey {
+        type Serialization = String;
+
+        fn serialize(&self) -> Self::Serialization {
+            self.0.to_string()
+        }
+    }
+
+    fn mock_client(transport: MockTransport) -> AocClient<MockTransport> {
+        AocClient::with_transport(Url::parse("https://aoc.test/").unwrap(), transport).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fetches_only_on_cache_miss() {
+        let transport =
+            MockTransport::default().with_response("/2023/day/1/input", StatusCode::OK, "1abc2\n");
+        let requests = transport.requests();
+        let client = mock_client(transport);
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        let cache = FileCache::new(store.clone(), Namespace::Inputs, |key: DayKey| {
+            let client = &client;
+            async move { client.get_input(2023, key.0).await }
+        });
+
+        assert_eq!(&*cache.get(&DayKey(1)).await.unwrap(), b"1abc2\n");
+        assert_eq!(&*cache.get(&DayKey(1)).await.unwrap(), b"1abc2\n");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        assert!(cache.get(&DayKey(2)).await.is_err());
+        assert!(!store.contains(Namespace::Inputs, "2").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_download_leaves_no_entry() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        let cache = FileCache::new(store.clone(), Namespace::Inputs, |_: DayKey| async {
+            anyhow::Ok(tokio_stream::iter(vec![
+                Ok(Bytes::from("partial")),
+                Err(anyhow!("connection reset")),
+            ]))
+        });
+
+        assert!(cache.get(&DayKey(1)).await.is_err());
+        assert!(!store.contains(Namespace::Inputs, "1").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_compression() {
+        let transport =
+            MockTransport::default().with_response("/2023/day/1/input", StatusCode::OK, "1abc2\n");
+        let client = mock_client(transport);
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        store.put(Namespace::Inputs, "2", b"uncompressed").unwrap();
+        let cache = FileCache::new(store.clone(), Namespace::Inputs, |key: DayKey| {
+            let client = &client;
+            async move { client.get_input(2023, key.0).await }
+        })
+        .with_compression(Compression::Gzip);
+
+        assert_eq!(&*cache.get(&DayKey(1)).await.unwrap(), b"1abc2\n");
+        let stored = store.get(Namespace::Inputs, "1").unwrap().unwrap();
+        assert!(stored.content.starts_with(&GZIP_MAGIC));
+        assert_eq!(
+            cache.get_cached(&DayKey(1)).await.as_deref(),
+            Some(&b"1abc2\n"[..])
+        );
+        assert_eq!(&*cache.get(&DayKey(2)).await.unwrap(), b"uncompressed");
+    }
+
+    #[tokio::test]
+    async fn test_expiry() {
+        let transport = MockTransport::default()
+            .with_response("/2023/day/1/input", StatusCode::OK, "stale")
+            .with_response("/2023/day/2/input", StatusCode::OK, "fresh");
+        let requests = transport.requests();
+        let client = mock_client(transport);
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        let cache = FileCache::new(store, Namespace::Inputs, |key: DayKey| {
+            let client = &client;
+            async move { client.get_input(2023, key.0).await }
+        })
+        .with_expiry(|content| (content == b"stale").then_some(Duration::ZERO));
+
+        for _ in 0..2 {
+            cache.get(&DayKey(1)).await.unwrap();
+            cache.get(&DayKey(2)).await.unwrap();
+        }
+        assert_eq!(
+            *requests.lock().unwrap(),
+            vec![
+                "GET /2023/day/1/input",
+                "GET /2023/day/2/input",
+                "GET /2023/day/1/input",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prefetch() {
+        let transport = MockTransport::default()
+            .with_response("/2023/day/1/input", StatusCode::OK, "1")
+            .with_response("/2023/day/2/input", StatusCode::OK, "2");
+        let requests = transport.requests();
+        let client = mock_client(transport);
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        let cache = FileCache::new(store, Namespace::Inputs, |key: DayKey| {
+            let client = &client;
+            async move { client.get_input(2023, key.0).await }
+        });
+
+        cache.get(&DayKey(1)).await.unwrap();
+        cache.prefetch([1, 2, 3].map(DayKey), 2).await;
+        assert_eq!(requests.lock().unwrap().len(), 3);
+        assert_eq!(
+            cache.get_cached(&DayKey(2)).await.as_deref(),
+            Some(&b"2"[..])
+        );
+        assert_eq!(cache.get_cached(&DayKey(3)).await, None);
+    }
+
+    #[tokio::test]
+    async fn test_memory_layer() {
+        let transport =
+            MockTransport::default().with_response("/2023/day/1/input", StatusCode::OK, "fetched");
+        let requests = transport.requests();
+        let client = mock_client(transport);
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        store.put(Namespace::Inputs, "1", b"stored").unwrap();
+        let cache = FileCache::new(store.clone(), Namespace::Inputs, |key: DayKey| {
+            let client = &client;
+            async move { client.get_input(2023, key.0).await }
+        });
+
+        let first = cache.get(&DayKey(1)).await.unwrap();
+        // Later reads are served from memory without touching the store.
+        store.remove(Namespace::Inputs, "1").unwrap();
+        let second = cache.get(&DayKey(1)).await.unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(requests.lock().unwrap().is_empty());
+
+        cache.invalidate(&DayKey(1)).await.unwrap();
+        assert_eq!(&*cache.get(&DayKey(1)).await.unwrap(), b"fetched");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_prune() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = FileStore::new(directory.path());
+        store.put(Namespace::Inputs, "1", b"1").unwrap();
+        store.put(Namespace::Pages, "1", b"1").unwrap();
+        store
+            .put(Namespace::Answers, "answers.json", b"{}")
+            .unwrap();
+
+        let future = SystemTime::now() + Duration::from_secs(60);
+        let past = SystemTime::now() - Duration::from_secs(60);
+        assert!(super::prune(&store, &Namespace::ALL, Some(past))
+            .unwrap()
+            .is_empty());
+
+        let refetchable: Vec<_> = Namespace::ALL
+            .into_iter()
+            .filter(|namespace| namespace.is_refetchable())
+            .collect();
+        let removed = super::prune(&store, &refetchable, Some(future)).unwrap();
+        assert_eq!(
+            removed
+                .iter()
+                .map(|(namespace, info)| (*namespace, info.key.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(Namespace::Inputs, "1"), (Namespace::Pages, "1")]
+        );
+        assert!(store.contains(Namespace::Answers, "answers.json").unwrap());
+
+        super::prune(&store, &[Namespace::Answers], None).unwrap();
+        assert!(!store.contains(Namespace::Answers, "answers.json").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_entry_is_fetched_again() {
+        let transport =
+            MockTransport::default().with_response("/2023/day/1/input", StatusCode::OK, "1abc2\n");
+        let requests = transport.requests();
+        let client = mock_client(transport);
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        let cache = FileCache::new(store.clone(), Namespace::Inputs, |key: DayKey| {
+            let client = &client;
+            async move { client.get_input(2023, key.0).await }
+        });
+        cache.get(&DayKey(1)).await.unwrap();
+        let stored = store.get(Namespace::Inputs, "1").unwrap().unwrap();
+        let corrupted = String::from_utf8(stored.content.clone())
+            .unwrap()
+            .replace("1abc2", "1abc3");
+        store
+            .put_with_checksum(
+                Namespace::Inputs,
+                "1",
+                corrupted.as_bytes(),
+                stored.checksum.as_deref(),
+            )
+            .unwrap();
+
+        let cache = FileCache::new(store.clone(), Namespace::Inputs, |key: DayKey| {
+            let client = &client;
+            async move { client.get_input(2023, key.0).await }
+        });
+        assert_eq!(&*cache.get(&DayKey(1)).await.unwrap(), b"1abc2\n");
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_checksum_is_stored_separately() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        let legacy = format!("sha256:{}\nlegacy", hash_input(b"legacy"));
+        store
+            .put(Namespace::Inputs, "2", legacy.as_bytes())
+            .unwrap();
+        let cache = FileCache::new(store.clone(), Namespace::Inputs, |_: DayKey| async {
+            anyhow::Ok(tokio_stream::iter(vec![Ok(Bytes::from("fetched"))]))
+        });
+
+        assert_eq!(&*cache.get(&DayKey(1)).await.unwrap(), b"fetched");
+        let stored = store.get(Namespace::Inputs, "1").unwrap().unwrap();
+        assert_eq!(stored.content, b"fetched");
+        assert_eq!(stored.checksum, Some(hash_input(b"fetched")));
+        assert_eq!(&*cache.get(&DayKey(2)).await.unwrap(), b"legacy");
+    }
+
+    #[tokio::test]
+    async fn test_sanitizer() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = Arc::new(FileStore::new(directory.path()));
+        let cache = FileCache::new(store.clone(), Namespace::Inputs, |key: DayKey| async move {
+            anyhow::Ok(tokio_stream::iter(vec![Ok(Bytes::from(match key.0 {
+                1 => "a\r\nb\r\n",
+                _ => "rejected",
+            }))]))
+        })
+        .with_sanitizer(|content| match content.as_slice() {
+            b"rejected" => Err(anyhow!("rejected")),
+            _ => Ok(aoc::solvers::unix_line_endings(content)),
+        });
+
+        assert_eq!(&*cache.get(&DayKey(1)).await.unwrap(), b"a\nb\n");
+        assert!(cache.get(&DayKey(2)).await.is_err());
+        assert!(!store.contains(Namespace::Inputs, "2").unwrap());
+    }
+}
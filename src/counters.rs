@@ -0,0 +1,410 @@
+//! Allocation and hardware performance counters for benchmarks.
+//!
+//! Allocations are only counted when the crate is built with the
+//! `count-allocations` feature, which installs a counting global allocator
+//! that also tracks the peak heap usage and enforces heap budgets.
+//! Hardware counters are read via `perf_event_open` on Linux and are only
+//! available if permitted by `kernel.perf_event_paranoid`. They count events
+//! of the calling thread only. Unavailable counters are reported as `None`.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+pub use allocations::HeapBudget;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Counters {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocated_bytes: Option<u64>,
+    /// Peak heap usage above the usage at the start of the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_heap_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_misses: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_misses: Option<u64>,
+}
+
+/// Runs `f` and returns its result together with the counted events.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Counters) {
+    let hardware = perf::HardwareCounters::open();
+    let allocations_before = allocations::snapshot();
+    hardware.enable();
+    let (result, peak_heap_bytes) = measure_peak_heap(f);
+    let (instructions, branch_misses, cache_misses) = hardware.disable_and_read();
+    let allocations_after = allocations::snapshot();
+
+    let (allocations, allocated_bytes) = match (allocations_before, allocations_after) {
+        (Some(before), Some(after)) => (Some(after.0 - before.0), Some(after.1 - before.1)),
+        _ => (None, None),
+    };
+    (
+        result,
+        Counters {
+            allocations,
+            allocated_bytes,
+            peak_heap_bytes,
+            instructions,
+            branch_misses,
+            cache_misses,
+        },
+    )
+}
+
+/// Runs `f` and returns its result together with the peak heap usage during
+/// the run above the usage at its start. Allocations of other threads running
+/// at the same time are included.
+pub fn measure_peak_heap<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+    let baseline = allocations::reset_peak();
+    let result = f();
+    let peak = allocations::peak()
+        .zip(baseline)
+        .map(|(peak, baseline)| peak.saturating_sub(baseline));
+    (result, peak)
+}
+
+/// Whether heap budgets are enforced in this build.
+pub const HEAP_BUDGETS_SUPPORTED: bool = cfg!(feature = "count-allocations");
+
+/// Runs `f` with the heap allocations of the current thread charged to
+/// `budget`. Allocations of other threads, e.g. of a thread pool used by `f`,
+/// are not charged.
+pub fn with_heap_budget<T>(budget: &Arc<HeapBudget>, f: impl FnOnce() -> T) -> T {
+    allocations::set_budget(Some(budget.clone()));
+    let result = f();
+    allocations::set_budget(None);
+    result
+}
+
+/// Formats a count with a metric suffix, e.g. `1.2M`.
+pub fn format_count(count: Option<u64>) -> String {
+    match count {
+        None => "n/a".into(),
+        Some(count) if count < 1_000 => count.to_string(),
+        Some(count) if count < 1_000_000 => format!("{:.1}k", count as f64 / 1e3),
+        Some(count) if count < 1_000_000_000 => format!("{:.1}M", count as f64 / 1e6),
+        Some(count) => format!("{:.1}G", count as f64 / 1e9),
+    }
+}
+
+/// Formats a size in bytes with a binary prefix, e.g. "1.5 KiB".
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "count-allocations")]
+mod allocations {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+        sync::{
+            atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+            Arc,
+        },
+    };
+
+    /// Heap budget of the threads running [`with_heap_budget`](super::with_heap_budget).
+    #[derive(Debug)]
+    pub struct HeapBudget {
+        limit: u64,
+        /// Net bytes allocated by the threads, negative if they freed more
+        /// memory allocated before than they allocated.
+        used: AtomicI64,
+        exceeded: AtomicBool,
+    }
+
+    impl HeapBudget {
+        pub fn new(limit: u64) -> Self {
+            Self {
+                limit,
+                used: AtomicI64::new(0),
+                exceeded: AtomicBool::new(false),
+            }
+        }
+
+        /// Whether the heap usage exceeded the limit at any point.
+        pub fn exceeded(&self) -> bool {
+            self.exceeded.load(Ordering::Relaxed)
+        }
+    }
+
+    struct CountingAllocator;
+
+    thread_local! {
+        /// Budget charged with the allocations of the thread. Only a raw
+        /// pointer, so that accessing it neither allocates nor needs a
+        /// destructor.
+        static BUDGET: Cell<*const HeapBudget> = const { Cell::new(std::ptr::null()) };
+    }
+
+    /// Sets the budget of the current thread, releasing the previous one.
+    pub fn set_budget(budget: Option<Arc<HeapBudget>>) {
+        let new = budget.map_or(std::ptr::null(), Arc::into_raw);
+        let old = BUDGET.with(|budget| budget.replace(new));
+        if !old.is_null() {
+            // SAFETY: `old` was created by `Arc::into_raw` above.
+            drop(unsafe { Arc::from_raw(old) });
+        }
+    }
+
+    /// Adds `delta` bytes to the usage of the budget of the current thread.
+    fn charge(delta: i64) {
+        // The thread-local is gone while the thread is torn down.
+        let _ = BUDGET.try_with(|budget| {
+            // SAFETY: `set_budget` keeps the budget alive while it is set.
+            let Some(budget) = (unsafe { budget.get().as_ref() }) else {
+                return;
+            };
+            let used = budget.used.fetch_add(delta, Ordering::Relaxed) + delta;
+            if u64::try_from(used).is_ok_and(|used| used > budget.limit) {
+                budget.exceeded.store(true, Ordering::Relaxed);
+            }
+        });
+    }
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+    /// Bytes currently allocated on the heap.
+    static HEAP_BYTES: AtomicU64 = AtomicU64::new(0);
+    static PEAK_HEAP_BYTES: AtomicU64 = AtomicU64::new(0);
+
+    fn grow_heap(bytes: usize) {
+        let heap = HEAP_BYTES.fetch_add(bytes as u64, Ordering::Relaxed) + bytes as u64;
+        PEAK_HEAP_BYTES.fetch_max(heap, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+            grow_heap(layout.size());
+            charge(layout.size() as i64);
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            HEAP_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            charge(-(layout.size() as i64));
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+            ALLOCATED_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+            // Both blocks may exist at the same time while the data is copied.
+            grow_heap(new_size);
+            HEAP_BYTES.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            charge(new_size as i64 - layout.size() as i64);
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    pub fn snapshot() -> Option<(u64, u64)> {
+        Some((
+            ALLOCATIONS.load(Ordering::Relaxed),
+            ALLOCATED_BYTES.load(Ordering::Relaxed),
+        ))
+    }
+
+    /// Resets the peak heap usage to the current usage and returns it.
+    pub fn reset_peak() -> Option<u64> {
+        let heap = HEAP_BYTES.load(Ordering::Relaxed);
+        PEAK_HEAP_BYTES.store(heap, Ordering::Relaxed);
+        Some(heap)
+    }
+
+    pub fn peak() -> Option<u64> {
+        Some(PEAK_HEAP_BYTES.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(not(feature = "count-allocations"))]
+mod allocations {
+    use std::sync::Arc;
+
+    /// Heap budget that is never exceeded, as allocations are not counted.
+    #[derive(Debug)]
+    pub struct HeapBudget;
+
+    impl HeapBudget {
+        pub fn new(_limit: u64) -> Self {
+            Self
+        }
+
+        pub fn exceeded(&self) -> bool {
+            false
+        }
+    }
+
+    pub fn set_budget(_budget: Option<Arc<HeapBudget>>) {}
+
+    pub fn snapshot() -> Option<(u64, u64)> {
+        None
+    }
+
+    pub fn reset_peak() -> Option<u64> {
+        None
+    }
+
+    pub fn peak() -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod perf {
+    use std::{
+        fs::File,
+        io::Read,
+        os::fd::{AsRawFd, FromRawFd},
+    };
+
+    use perf_event_open_sys::{bindings, ioctls, perf_event_open};
+
+    struct PerfCounter(File);
+
+    impl PerfCounter {
+        fn open(config: bindings::perf_hw_id) -> Option<Self> {
+            let mut attr = bindings::perf_event_attr {
+                size: std::mem::size_of::<bindings::perf_event_attr>() as u32,
+                type_: bindings::perf_type_id_PERF_TYPE_HARDWARE,
+                config: config as u64,
+                ..Default::default()
+            };
+            attr.set_disabled(1);
+            attr.set_exclude_kernel(1);
+            attr.set_exclude_hv(1);
+            // SAFETY: `attr` is a valid, initialized attribute struct.
+            let fd = unsafe { perf_event_open(&mut attr, 0, -1, -1, 0) };
+            if fd < 0 {
+                return None;
+            }
+            // SAFETY: `fd` is a freshly opened file descriptor owned by nobody
+            // else.
+            Some(Self(unsafe { File::from_raw_fd(fd) }))
+        }
+
+        fn fd(&self) -> i32 {
+            self.0.as_raw_fd()
+        }
+
+        fn enable(&self) {
+            // SAFETY: the ioctls are called on a valid perf event descriptor.
+            unsafe {
+                ioctls::RESET(self.fd(), 0);
+                ioctls::ENABLE(self.fd(), 0);
+            }
+        }
+
+        fn disable_and_read(&mut self) -> Option<u64> {
+            // SAFETY: the ioctl is called on a valid perf event descriptor.
+            unsafe { ioctls::DISABLE(self.fd(), 0) };
+            let mut buffer = [0u8; 8];
+            self.0.read_exact(&mut buffer).ok()?;
+            Some(u64::from_ne_bytes(buffer))
+        }
+    }
+
+    pub struct HardwareCounters([Option<PerfCounter>; 3]);
+
+    impl HardwareCounters {
+        pub fn open() -> Self {
+            Self(
+                [
+                    bindings::perf_hw_id_PERF_COUNT_HW_INSTRUCTIONS,
+                    bindings::perf_hw_id_PERF_COUNT_HW_BRANCH_MISSES,
+                    bindings::perf_hw_id_PERF_COUNT_HW_CACHE_MISSES,
+                ]
+                .map(PerfCounter::open),
+            )
+        }
+
+        pub fn enable(&self) {
+            for counter in self.0.iter().flatten() {
+                counter.enable();
+            }
+        }
+
+        pub fn disable_and_read(mut self) -> (Option<u64>, Option<u64>, Option<u64>) {
+            let [instructions, branch_misses, cache_misses] = self
+                .0
+                .each_mut()
+                .map(|counter| counter.as_mut().and_then(PerfCounter::disable_and_read));
+            (instructions, branch_misses, cache_misses)
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod perf {
+    pub struct HardwareCounters;
+
+    impl HardwareCounters {
+        pub fn open() -> Self {
+            Self
+        }
+
+        pub fn enable(&self) {}
+
+        pub fn disable_and_read(self) -> (Option<u64>, Option<u64>, Option<u64>) {
+            (None, None, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_count, measure, measure_peak_heap};
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(None), "n/a");
+        assert_eq!(format_count(Some(999)), "999");
+        assert_eq!(format_count(Some(1_250)), "1.2k");
+        assert_eq!(format_count(Some(3_400_000)), "3.4M");
+    }
+
+    #[test]
+    fn test_measure_returns_result() {
+        let (result, counters) = measure(|| vec![1u8; 1024].len());
+        assert_eq!(result, 1024);
+        if cfg!(feature = "count-allocations") {
+            assert!(counters.allocations.unwrap() >= 1);
+        } else {
+            assert_eq!(counters.allocations, None);
+        }
+    }
+
+    #[test]
+    fn test_measure_peak_heap() {
+        let (_, peak) = measure_peak_heap(|| {
+            let buffer = vec![1u8; 1 << 20];
+            drop(buffer);
+            vec![1u8; 1024].len()
+        });
+        if cfg!(feature = "count-allocations") {
+            // Tests running in parallel may allocate as well.
+            assert!(peak.unwrap() >= 1 << 20);
+        } else {
+            assert_eq!(peak, None);
+        }
+    }
+}
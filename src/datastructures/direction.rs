@@ -0,0 +1,261 @@
+//! Directions on a grid indexed by `(row, col)`, i.e. with rows growing
+//! downwards.
+
+use anyhow::anyhow;
+
+/// One of the 4 horizontal and vertical directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// All directions in clockwise order, starting upwards.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// Change of `(row, col)` when moving one step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Right => (0, 1),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+        }
+    }
+
+    pub fn turn_cw(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
+
+    pub fn turn_ccw(self) -> Self {
+        match self {
+            Self::Up => Self::Left,
+            Self::Right => Self::Up,
+            Self::Down => Self::Right,
+            Self::Left => Self::Down,
+        }
+    }
+
+    pub fn invert(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Right => Self::Left,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+        }
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+
+    /// Position one step from `pos` in this direction, if it lies within a
+    /// grid of the given `size`.
+    pub fn step(self, pos: (usize, usize), size: (usize, usize)) -> Option<(usize, usize)> {
+        let (d_row, d_col) = self.delta();
+        let row = pos.0.checked_add_signed(d_row)?;
+        let col = pos.1.checked_add_signed(d_col)?;
+        (row < size.0 && col < size.1).then_some((row, col))
+    }
+}
+
+/// Parses the arrows `^`, `>`, `v`, and `<`.
+impl TryFrom<u8> for Direction4 {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            b'^' => Ok(Self::Up),
+            b'>' => Ok(Self::Right),
+            b'v' => Ok(Self::Down),
+            b'<' => Ok(Self::Left),
+            _ => Err(anyhow!("invalid direction '{}'", value.escape_ascii())),
+        }
+    }
+}
+
+/// One of the 8 directions including the diagonal ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions in clockwise order, starting upwards.
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Change of `(row, col)` when moving one step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::UpRight => (-1, 1),
+            Self::Right => (0, 1),
+            Self::DownRight => (1, 1),
+            Self::Down => (1, 0),
+            Self::DownLeft => (1, -1),
+            Self::Left => (0, -1),
+            Self::UpLeft => (-1, -1),
+        }
+    }
+
+    /// Turns clockwise by 45°.
+    pub fn turn_cw(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turns counter-clockwise by 45°.
+    pub fn turn_ccw(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn invert(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    /// Position one step from `pos` in this direction, if it lies within a
+    /// grid of the given `size`.
+    pub fn step(self, pos: (usize, usize), size: (usize, usize)) -> Option<(usize, usize)> {
+        let (d_row, d_col) = self.delta();
+        let row = pos.0.checked_add_signed(d_row)?;
+        let col = pos.1.checked_add_signed(d_col)?;
+        (row < size.0 && col < size.1).then_some((row, col))
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        match direction {
+            Direction4::Up => Self::Up,
+            Direction4::Right => Self::Right,
+            Direction4::Down => Self::Down,
+            Direction4::Left => Self::Left,
+        }
+    }
+}
+
+/// Position on a grid together with the direction it is facing, e.g. the
+/// state of a guard or a reindeer walking through a maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pose {
+    pub pos: (usize, usize),
+    pub dir: Direction4,
+}
+
+impl Pose {
+    pub fn new(pos: (usize, usize), dir: Direction4) -> Self {
+        Self { pos, dir }
+    }
+
+    /// Pose one step forward, if it lies within a grid of the given `size`.
+    pub fn step(self, size: (usize, usize)) -> Option<Self> {
+        Some(Self::new(self.dir.step(self.pos, size)?, self.dir))
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::new(self.pos, self.dir.turn_ccw())
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::new(self.pos, self.dir.turn_cw())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rstest::rstest;
+
+    use super::{Direction4, Direction8, Pose};
+
+    #[test]
+    fn test_direction4_turns() {
+        for dir in Direction4::ALL {
+            assert_eq!(dir.turn_cw().turn_ccw(), dir);
+            assert_eq!(dir.turn_cw().turn_cw(), dir.invert());
+            let (d_row, d_col) = dir.delta();
+            assert_eq!(dir.turn_cw().delta(), (d_col, -d_row));
+            assert_eq!(dir.invert().delta(), (-d_row, -d_col));
+        }
+    }
+
+    #[rstest]
+    #[case(b'^', Direction4::Up)]
+    #[case(b'>', Direction4::Right)]
+    #[case(b'v', Direction4::Down)]
+    #[case(b'<', Direction4::Left)]
+    fn test_direction4_from_arrow(#[case] arrow: u8, #[case] expected: Direction4) {
+        assert_eq!(Direction4::try_from(arrow).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_direction4_from_invalid_arrow() {
+        assert!(Direction4::try_from(b'x').is_err());
+    }
+
+    #[test]
+    fn test_direction4_step() {
+        assert_eq!(Direction4::Up.step((0, 1), (2, 2)), None);
+        assert_eq!(Direction4::Down.step((0, 1), (2, 2)), Some((1, 1)));
+        assert_eq!(Direction4::Right.step((0, 1), (2, 2)), None);
+        assert_eq!(Direction4::Left.step((0, 1), (2, 2)), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_direction8_turns() {
+        for dir in Direction8::ALL {
+            assert_eq!(dir.turn_cw().turn_ccw(), dir);
+            assert_eq!(dir.turn_cw().turn_cw().turn_cw().turn_cw(), dir.invert());
+            let (d_row, d_col) = dir.delta();
+            assert_eq!(dir.invert().delta(), (-d_row, -d_col));
+            assert_eq!(dir.is_diagonal(), d_row != 0 && d_col != 0);
+        }
+        for dir in Direction4::ALL {
+            assert_eq!(Direction8::from(dir).delta(), dir.delta());
+        }
+    }
+
+    #[test]
+    fn test_direction8_step() {
+        assert_eq!(Direction8::UpLeft.step((1, 1), (2, 2)), Some((0, 0)));
+        assert_eq!(Direction8::DownRight.step((1, 1), (2, 2)), None);
+    }
+
+    #[test]
+    fn test_pose() {
+        let pose = Pose::new((0, 1), Direction4::Right);
+        assert_eq!(pose.step((2, 2)), None);
+        assert_eq!(
+            pose.turn_right().step((2, 2)),
+            Some(Pose::new((1, 1), Direction4::Down))
+        );
+        assert_eq!(pose.turn_left(), Pose::new((0, 1), Direction4::Up));
+        assert_eq!(pose.turn_left().turn_right(), pose);
+    }
+}
@@ -1,4 +1,9 @@
-use std::ops::{Deref, DerefMut, Index, IndexMut, Range};
+use std::{
+    fmt::{Display, Write},
+    ops::{Deref, DerefMut, Index, IndexMut, Range},
+};
+
+use crate::solvers::InputError;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GridView<T> {
@@ -9,7 +14,7 @@ pub struct GridView<T> {
 
 impl<T> GridView<Vec<T>> {
     pub fn from_vec(width: usize, separator_width: usize, data: Vec<T>) -> Self {
-        if data.len() % width > 0 && data.len() % width < width - separator_width - 1 {
+        if !data.len().is_multiple_of(width) && data.len() % width < width - separator_width - 1 {
             panic!("width must be a divisor of total data length");
         }
         Self {
@@ -19,6 +24,18 @@ impl<T> GridView<Vec<T>> {
         }
     }
 
+    /// Grid of the given `(height, width)` with every cell set to `value`.
+    pub fn filled(size: (usize, usize), value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width: size.1,
+            pub_size: size,
+            data: vec![value; size.0 * size.1],
+        }
+    }
+
     pub fn from_separated_vec(separator: T, data: Vec<T>) -> Self
     where
         T: Eq,
@@ -31,9 +48,59 @@ impl<T> GridView<Vec<T>> {
     }
 }
 
+impl GridView<Vec<u8>> {
+    /// Parses a grid of decimal digits with one row per line into their
+    /// values 0–9. Any other character is an [`InputError`]; use
+    /// [`parse_digits_or`](GridView::parse_digits_or) for grids with
+    /// placeholders like the `.` in some examples.
+    pub fn parse_digits(input: &[u8]) -> Result<Self, InputError> {
+        let grid = GridView::parse_optional_digits(input, None)?;
+        Ok(grid.map(|digit| digit.expect("placeholders are rejected")))
+    }
+}
+
+impl GridView<Vec<Option<u8>>> {
+    /// Parses a grid of decimal digits with one row per line into their
+    /// values 0–9, mapping the `placeholder` character to `None`.
+    pub fn parse_digits_or(input: &[u8], placeholder: u8) -> Result<Self, InputError> {
+        Self::parse_optional_digits(input, Some(placeholder))
+    }
+
+    fn parse_optional_digits(input: &[u8], placeholder: Option<u8>) -> Result<Self, InputError> {
+        let mut rows = input
+            .strip_suffix(b"\n")
+            .unwrap_or(input)
+            .split(|&c| c == b'\n');
+        let width = rows.next().map_or(0, <[u8]>::len);
+        if let Some(row) = rows.find(|row| row.len() != width) {
+            return Err(InputError::from_bytes(
+                row,
+                format!("a row of {width} characters"),
+            ));
+        }
+        let grid = GridView::from_separated(b'\n', input);
+        if let Some((pos, _)) = grid
+            .indexed_iter()
+            .find(|&(_, c)| !c.is_ascii_digit() && Some(c) != placeholder)
+        {
+            let offset = pos.0 * grid.width + pos.1;
+            return Err(InputError::from_bytes(
+                &input[offset..offset + 1],
+                match placeholder {
+                    Some(placeholder) => {
+                        format!("a digit or '{}'", placeholder.escape_ascii())
+                    }
+                    None => "a digit".into(),
+                },
+            ));
+        }
+        Ok(grid.map(|&c| c.is_ascii_digit().then(|| c - b'0')))
+    }
+}
+
 impl<'a, T> GridView<&'a [T]> {
     pub fn new(width: usize, separator_width: usize, data: &'a [T]) -> Self {
-        if data.len() % width > 0 && data.len() % width < width - separator_width - 1 {
+        if !data.len().is_multiple_of(width) && data.len() % width < width - separator_width - 1 {
             panic!("width must be a divisor of total data length");
         }
         Self {
@@ -96,18 +163,233 @@ impl<T> GridView<T> {
             len: self.height(),
         }
     }
+
+    /// Diagonal running down and to the right with `col - row == k`. The
+    /// main diagonal is `diag(0)`. Diagonals outside of the grid are empty.
+    pub fn diag(&self, k: isize) -> Slice<'_, T> {
+        let (row, col) = if k >= 0 {
+            (0, k.unsigned_abs())
+        } else {
+            (k.unsigned_abs(), 0)
+        };
+        Slice {
+            grid: self,
+            offset: row * self.width + col,
+            stride: self.width + 1,
+            len: self
+                .height()
+                .saturating_sub(row)
+                .min(self.width().saturating_sub(col)),
+        }
+    }
+
+    /// Anti-diagonal running down and to the left with `row + col == k`,
+    /// starting in the top left corner with `anti_diag(0)`. Anti-diagonals
+    /// outside of the grid are empty.
+    pub fn anti_diag(&self, k: usize) -> Slice<'_, T> {
+        let row = (k + 1).saturating_sub(self.width());
+        let col = k.saturating_sub(row);
+        Slice {
+            grid: self,
+            offset: row * self.width + col,
+            stride: self.width.saturating_sub(1),
+            len: (k + 1).min(self.height()).saturating_sub(row),
+        }
+    }
+
+    /// All non-empty diagonals from the bottom left to the top right corner.
+    pub fn diags(&self) -> impl Iterator<Item = Slice<'_, T>> + '_ {
+        (1 - self.height() as isize..self.width() as isize).map(|k| self.diag(k))
+    }
+
+    /// All non-empty anti-diagonals from the top left to the bottom right
+    /// corner.
+    pub fn anti_diags(&self) -> impl Iterator<Item = Slice<'_, T>> + '_ {
+        (0..(self.height() + self.width()).saturating_sub(1)).map(|k| self.anti_diag(k))
+    }
+
+    pub fn row_mut(&mut self, index: usize) -> SliceMut<'_, T> {
+        SliceMut {
+            offset: index * self.width,
+            stride: 1,
+            len: self.width(),
+            grid: self,
+        }
+    }
+
+    pub fn col_mut(&mut self, index: usize) -> SliceMut<'_, T> {
+        SliceMut {
+            offset: index,
+            stride: self.width,
+            len: self.height(),
+            grid: self,
+        }
+    }
 }
 
+/// Value of a cell of a `GridView<T>`.
+type Cell<T> = <GridView<T> as Index<(usize, usize)>>::Output;
+
 impl<T> GridView<T>
 where
     GridView<T>: Index<(usize, usize)>,
-    <GridView<T> as Index<(usize, usize)>>::Output: Copy + Sized,
+    Cell<T>: Copy + Sized,
 {
-    pub fn iter(
-        &self,
-    ) -> impl Iterator<Item = <GridView<T> as Index<(usize, usize)>>::Output> + '_ {
+    pub fn iter(&self) -> impl Iterator<Item = Cell<T>> + '_ {
         GridIterator::new(self)
     }
+
+    /// Iterates over the cells in row-major order together with their
+    /// `(row, col)` index.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), Cell<T>)> + '_ {
+        (0..self.height())
+            .flat_map(move |row| (0..self.width()).map(move |col| ((row, col), self[(row, col)])))
+    }
+}
+
+impl<T, U> GridView<T>
+where
+    T: Deref<Target = [U]>,
+{
+    /// Borrowed window of the given rows and columns of the grid, indexed
+    /// relative to its top left corner.
+    pub fn view(&self, rows: Range<usize>, cols: Range<usize>) -> GridView<&[U]> {
+        if rows.start > rows.end || rows.end > self.height() {
+            panic!("rows exceed view dimensions");
+        }
+        if cols.start > cols.end || cols.end > self.width() {
+            panic!("columns exceed view dimensions");
+        }
+        let (start, len) = match rows.len() {
+            0 => (0, 0),
+            height => (
+                rows.start * self.width + cols.start,
+                (height - 1) * self.width + cols.len(),
+            ),
+        };
+        GridView {
+            width: self.width,
+            pub_size: (rows.len(), cols.len()),
+            data: &self.data[start..start + len],
+        }
+    }
+
+    /// Renders the grid with one line per row and each cell formatted with
+    /// `format_cell`, e.g. to print the state of a simulation for debugging.
+    pub fn render<D: Display>(&self, mut format_cell: impl FnMut(&U) -> D) -> String {
+        let mut rendered = String::new();
+        for row in 0..self.height() {
+            if row > 0 {
+                rendered.push('\n');
+            }
+            for cell in &self.data[row * self.width..][..self.width()] {
+                write!(rendered, "{}", format_cell(cell)).expect("writing to a string");
+            }
+        }
+        rendered
+    }
+
+    /// Grid of the same size with `f` applied to every cell.
+    pub fn map<V>(&self, mut f: impl FnMut(&U) -> V) -> GridView<Vec<V>> {
+        let mut data = Vec::with_capacity(self.height() * self.width());
+        for row in 0..self.height() {
+            data.extend(
+                self.data[row * self.width..][..self.width()]
+                    .iter()
+                    .map(&mut f),
+            );
+        }
+        GridView {
+            width: self.width(),
+            pub_size: self.size(),
+            data,
+        }
+    }
+}
+
+impl<T, U> GridView<T>
+where
+    T: Deref<Target = [U]>,
+    U: Clone,
+{
+    /// Grid with the rows and columns swapped.
+    pub fn transpose(&self) -> GridView<Vec<U>> {
+        self.remap((self.width(), self.height()), |(row, col)| (col, row))
+    }
+
+    /// Grid rotated clockwise by 90°, i.e. the left column becomes the top
+    /// row.
+    pub fn rotate_cw(&self) -> GridView<Vec<U>> {
+        let height = self.height();
+        self.remap((self.width(), height), |(row, col)| (height - 1 - col, row))
+    }
+
+    /// Grid rotated counter-clockwise by 90°, i.e. the right column becomes
+    /// the top row.
+    pub fn rotate_ccw(&self) -> GridView<Vec<U>> {
+        let width = self.width();
+        self.remap((width, self.height()), |(row, col)| (col, width - 1 - row))
+    }
+
+    /// Grid mirrored horizontally, i.e. with the order of the columns reversed.
+    pub fn flip_h(&self) -> GridView<Vec<U>> {
+        let width = self.width();
+        self.remap(self.size(), |(row, col)| (row, width - 1 - col))
+    }
+
+    /// Grid mirrored vertically, i.e. with the order of the rows reversed.
+    pub fn flip_v(&self) -> GridView<Vec<U>> {
+        let height = self.height();
+        self.remap(self.size(), |(row, col)| (height - 1 - row, col))
+    }
+
+    /// Copy of the grid surrounded by a border of `border` cells set to
+    /// `value` on each side, so that neighbors of the original cells can be
+    /// accessed without bounds checks. Cell `(row, col)` of this grid is at
+    /// `(row + border, col + border)` in the padded grid.
+    pub fn padded(&self, border: usize, value: U) -> GridView<Vec<U>> {
+        let mut padded = GridView::filled(
+            (self.height() + 2 * border, self.width() + 2 * border),
+            value,
+        );
+        for row in 0..self.height() {
+            padded.data[(row + border) * padded.width + border..][..self.width()]
+                .clone_from_slice(&self[(row, 0..self.width())]);
+        }
+        padded
+    }
+
+    /// Grid of the given size, taking each cell from the position in this
+    /// grid that `source` maps it to.
+    fn remap(
+        &self,
+        size: (usize, usize),
+        source: impl Fn((usize, usize)) -> (usize, usize),
+    ) -> GridView<Vec<U>> {
+        let mut data = Vec::with_capacity(size.0 * size.1);
+        for row in 0..size.0 {
+            for col in 0..size.1 {
+                data.push(self[source((row, col))].clone());
+            }
+        }
+        GridView::from_vec(size.1, 0, data)
+    }
+}
+
+/// Displays grids of bytes, e.g. parsed from the input, with one line per row.
+impl<T> Display for GridView<T>
+where
+    T: Deref<Target = [u8]>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..self.height() {
+            if row > 0 {
+                f.write_char('\n')?;
+            }
+            f.write_str(&String::from_utf8_lossy(&self[(row, 0..self.width())]))?;
+        }
+        Ok(())
+    }
 }
 
 impl<T> Index<(usize, usize)> for GridView<T>
@@ -179,7 +461,7 @@ where
     type Item = <GridView<T> as Index<(usize, usize)>>::Output;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.row >= self.grid.height() {
+        if self.row >= self.grid.height() || self.grid.width() == 0 {
             return None;
         }
         let current = self.grid[(self.row, self.col)];
@@ -203,6 +485,10 @@ impl<T> Slice<'_, T> {
     pub fn len(&self) -> usize {
         self.len
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 }
 
 impl<T> Slice<'_, T>
@@ -257,13 +543,106 @@ where
     }
 }
 
+/// Row or column of a grid that allows modifying the cells.
+pub struct SliceMut<'a, T> {
+    grid: &'a mut GridView<T>,
+    offset: usize,
+    stride: usize,
+    len: usize,
+}
+
+impl<T> SliceMut<'_, T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T, U> SliceMut<'_, T>
+where
+    T: DerefMut<Target = [U]>,
+{
+    pub fn iter<'s>(&'s self) -> impl Iterator<Item = &'s U> + 's
+    where
+        U: 's,
+    {
+        self.grid
+            .data
+            .iter()
+            .skip(self.offset)
+            .step_by(self.stride)
+            .take(self.len)
+    }
+
+    pub fn iter_mut<'s>(&'s mut self) -> impl Iterator<Item = &'s mut U> + 's
+    where
+        U: 's,
+    {
+        self.grid
+            .data
+            .iter_mut()
+            .skip(self.offset)
+            .step_by(self.stride)
+            .take(self.len)
+    }
+}
+
+impl<T> Index<usize> for SliceMut<'_, T>
+where
+    T: Deref,
+    T::Target: Index<usize>,
+{
+    type Output = <T::Target as Index<usize>>::Output;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        if index >= self.len() {
+            panic!("index exceeds slice length");
+        }
+        &self.grid.data[self.offset + index * self.stride]
+    }
+}
+
+impl<T> IndexMut<usize> for SliceMut<'_, T>
+where
+    T: DerefMut,
+    T::Target: IndexMut<usize>,
+{
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        if index >= self.len() {
+            panic!("index exceeds slice length");
+        }
+        &mut self.grid.data[self.offset + index * self.stride]
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use proptest::prelude::*;
     use rstest::rstest;
     use std::{ops::Range, vec};
 
     use super::GridView;
 
+    /// Grids of distinct cells with separators between the rows and possibly
+    /// a truncated separator after the last row.
+    fn grids() -> impl Strategy<Value = GridView<Vec<u16>>> {
+        (1usize..10, 0usize..3, 1usize..10)
+            .prop_flat_map(|(width, separator_width, height)| {
+                (Just((width, separator_width, height)), 0..=separator_width)
+            })
+            .prop_map(|((width, separator_width, height), truncated)| {
+                let len = height * (width + separator_width) - truncated;
+                GridView::from_vec(
+                    width + separator_width,
+                    separator_width,
+                    (0..len as u16).collect(),
+                )
+            })
+    }
+
     static DATA: [u8; 11] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
 
     #[test]
@@ -328,9 +707,336 @@ mod test {
         assert_eq!(items, vec![0, 1, 2, 5, 6, 7]);
     }
 
+    #[test]
+    fn test_transformations() {
+        let grid = GridView::from_separated(b'\n', b"abc\ndef");
+        let rows = |grid: GridView<Vec<u8>>| {
+            (0..grid.height())
+                .map(|row| String::from_utf8(grid[(row, 0..grid.width())].to_vec()).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(rows(grid.transpose()), ["ad", "be", "cf"]);
+        assert_eq!(rows(grid.rotate_cw()), ["da", "eb", "fc"]);
+        assert_eq!(rows(grid.rotate_ccw()), ["cf", "be", "ad"]);
+        assert_eq!(rows(grid.flip_h()), ["cba", "fed"]);
+        assert_eq!(rows(grid.flip_v()), ["def", "abc"]);
+    }
+
+    #[test]
+    fn test_mutable_slices() {
+        let mut grid = GridView::from_vec(4, 1, (0..12).collect());
+        grid.row_mut(1)[2] = 100;
+        for cell in grid.col_mut(0).iter_mut() {
+            *cell *= 10;
+        }
+        assert_eq!(
+            grid.row_mut(2).iter().copied().collect::<Vec<_>>(),
+            [80, 9, 10]
+        );
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            [0, 1, 2, 40, 5, 100, 80, 9, 10]
+        );
+    }
+
+    #[test]
+    fn test_indexed_iter() {
+        let grid = GridView::new(5, 2, &DATA[0..10]);
+        let items: Vec<_> = grid.indexed_iter().collect();
+        assert_eq!(
+            items,
+            vec![
+                ((0, 0), 0),
+                ((0, 1), 1),
+                ((0, 2), 2),
+                ((1, 0), 5),
+                ((1, 1), 6),
+                ((1, 2), 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let grid = GridView::from_separated(b'\n', b"abc\ndef");
+        let collect = |slice: super::Slice<'_, &[u8]>| String::from_utf8(slice.iter().collect());
+        assert_eq!(
+            grid.diags().map(collect).collect::<Result<Vec<_>, _>>(),
+            Ok(vec!["d".into(), "ae".into(), "bf".into(), "c".into()])
+        );
+        assert_eq!(
+            grid.anti_diags()
+                .map(collect)
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(vec!["a".into(), "bd".into(), "ce".into(), "f".into()])
+        );
+        assert!(grid.diag(3).is_empty());
+        assert!(grid.diag(-2).is_empty());
+        assert!(grid.anti_diag(4).is_empty());
+    }
+
+    #[test]
+    fn test_view() {
+        let grid = GridView::from_separated(b'\n', b"abcd\nefgh\nijkl");
+        let view = grid.view(1..3, 1..3);
+        assert_eq!(view.size(), (2, 2));
+        assert_eq!(view.iter().collect::<Vec<_>>(), b"fgjk");
+        assert_eq!(view[(1, 0..2)], *b"jk");
+        assert_eq!(view.col(1).iter().collect::<Vec<_>>(), b"gk");
+        assert_eq!(view.view(1..2, 0..1)[(0, 0)], b'j');
+        assert_eq!(view.to_owned().iter().collect::<Vec<_>>(), b"fgjk");
+    }
+
+    #[rstest]
+    #[case(0..4, 0..2)]
+    #[case(0..1, 2..5)]
+    #[should_panic]
+    fn test_invalid_view(#[case] rows: Range<usize>, #[case] cols: Range<usize>) {
+        let grid = GridView::from_separated(b'\n', b"abcd\nefgh\nijkl");
+        grid.view(rows, cols);
+    }
+
+    #[test]
+    fn test_filled() {
+        let mut grid = GridView::filled((2, 3), 0);
+        assert_eq!(grid.size(), (2, 3));
+        grid[(1, 2)] = 1;
+        assert_eq!(grid.iter().collect::<Vec<_>>(), [0, 0, 0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = GridView::from_separated(b'\n', b"12\n34");
+        let digits = grid.map(|&c| c - b'0');
+        assert_eq!(digits.size(), (2, 2));
+        assert_eq!(digits.iter().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_parse_digits() -> anyhow::Result<()> {
+        let grid = GridView::parse_digits(b"109\n234\n")?;
+        assert_eq!(grid.size(), (2, 3));
+        assert_eq!(grid.iter().collect::<Vec<_>>(), [1, 0, 9, 2, 3, 4]);
+        assert!(GridView::parse_digits(b"12\n3.").is_err());
+        let grid = GridView::parse_digits_or(b"12\n3.", b'.')?;
+        assert_eq!(grid[(1, 0)], Some(3));
+        assert_eq!(grid[(1, 1)], None);
+        let err = GridView::parse_digits_or(b"12\n3x", b'.').unwrap_err();
+        assert_eq!(err.expected(), "a digit or '.'");
+        assert_eq!(err.snippet(), "x");
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(b"12\n3", "3")]
+    #[case(b"12\n345", "345")]
+    #[case(b"12\n345\n67\n", "345")]
+    fn test_parse_digits_rejects_ragged_rows(#[case] input: &[u8], #[case] snippet: &str) {
+        let mut err = GridView::parse_digits(input).unwrap_err();
+        assert_eq!(err.expected(), "a row of 2 characters");
+        assert_eq!(err.snippet(), snippet);
+        err.locate(input);
+        assert_eq!(err.line(), Some(2));
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = GridView::from_separated(b'\n', b"#.#\n..#\n");
+        assert_eq!(grid.to_string(), "#.#\n..#");
+        assert_eq!(grid.view(0..2, 1..3).to_string(), ".#\n.#");
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = GridView::filled((2, 3), 7u32);
+        assert_eq!(grid.render(|cell| format!("{cell:>2}")), " 7 7 7\n 7 7 7");
+        let grid = GridView::from_separated(b'\n', b"ab\ncd");
+        assert_eq!(grid.render(|&c| c.to_ascii_uppercase() as char), "AB\nCD");
+    }
+
+    #[test]
+    fn test_padded() {
+        let grid = GridView::from_separated(b'\n', b"ab\ncd\n");
+        assert_eq!(grid.padded(1, b'#').to_string(), "####\n#ab#\n#cd#\n####");
+        assert_eq!(grid.padded(0, b'#').to_string(), "ab\ncd");
+    }
+
     #[test]
     fn test_nth_index() {
         let grid = GridView::new(5, 2, &DATA[0..10]);
         assert_eq!(grid.nth_index(5), (1, 2));
     }
+
+    proptest! {
+        #[test]
+        fn test_nth_index_matches_iteration_order(grid in grids()) {
+            for (n, item) in grid.iter().enumerate() {
+                let (row, col) = grid.nth_index(n);
+                prop_assert!(row < grid.height() && col < grid.width());
+                prop_assert_eq!(grid[(row, col)], item);
+            }
+            prop_assert_eq!(grid.iter().count(), grid.width() * grid.height());
+        }
+
+        #[test]
+        fn test_indexed_iter_matches_nth_index(grid in grids()) {
+            let expected = grid.iter().enumerate().map(|(n, item)| (grid.nth_index(n), item));
+            prop_assert!(grid.indexed_iter().eq(expected));
+        }
+
+        #[test]
+        fn test_nth_index_round_trip(
+            (grid, row, col) in grids().prop_flat_map(|grid| {
+                let (height, width) = (grid.height(), grid.width());
+                (Just(grid), 0..height, 0..width)
+            })
+        ) {
+            prop_assert_eq!(grid.nth_index(row * grid.width() + col), (row, col));
+        }
+
+        #[test]
+        fn test_rows_match_indexing(grid in grids()) {
+            for row in 0..grid.height() {
+                let slice = grid.row(row);
+                prop_assert_eq!(slice.len(), grid.width());
+                for col in 0..grid.width() {
+                    prop_assert_eq!(slice[col], grid[(row, col)]);
+                }
+                prop_assert!(slice.iter().eq(grid[(row, 0..grid.width())].iter().copied()));
+            }
+        }
+
+        #[test]
+        fn test_cols_match_indexing(grid in grids()) {
+            for col in 0..grid.width() {
+                let slice = grid.col(col);
+                prop_assert_eq!(slice.len(), grid.height());
+                for row in 0..grid.height() {
+                    prop_assert_eq!(slice[row], grid[(row, col)]);
+                }
+                prop_assert!(slice.iter().eq((0..grid.height()).map(|row| grid[(row, col)])));
+            }
+        }
+
+        #[test]
+        fn test_transformations_are_consistent(grid in grids()) {
+            let cells = |grid: &GridView<Vec<u16>>| (grid.size(), grid.iter().collect::<Vec<_>>());
+            let rotated = grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw();
+            prop_assert_eq!(cells(&rotated), cells(&grid));
+            prop_assert_eq!(cells(&grid.rotate_cw().rotate_ccw()), cells(&grid));
+            prop_assert_eq!(cells(&grid.transpose().transpose()), cells(&grid));
+            prop_assert_eq!(cells(&grid.flip_h().flip_h()), cells(&grid));
+            prop_assert_eq!(cells(&grid.rotate_cw()), cells(&grid.transpose().flip_h()));
+            prop_assert_eq!(cells(&grid.rotate_ccw()), cells(&grid.transpose().flip_v()));
+            prop_assert_eq!(
+                cells(&grid.rotate_cw().rotate_cw()),
+                cells(&grid.flip_h().flip_v())
+            );
+        }
+
+        #[test]
+        fn test_diagonals_match_indexing(grid in grids()) {
+            let mut cells = Vec::new();
+            for (slice, k) in grid.diags().zip(1 - grid.height() as isize..) {
+                for i in 0..slice.len() {
+                    let row = i + (-k).max(0) as usize;
+                    let col = (row as isize + k) as usize;
+                    prop_assert_eq!(slice[i], grid[(row, col)]);
+                    cells.push((row, col));
+                }
+            }
+            for (k, slice) in grid.anti_diags().enumerate() {
+                for i in 0..slice.len() {
+                    let row = i + (k + 1).saturating_sub(grid.width());
+                    prop_assert_eq!(slice[i], grid[(row, k - row)]);
+                    cells.push((row, k - row));
+                }
+            }
+            cells.sort_unstable();
+            let expected: Vec<_> = grid.indexed_iter().map(|(pos, _)| pos).collect();
+            prop_assert_eq!(cells.len(), 2 * expected.len());
+            cells.dedup();
+            prop_assert_eq!(cells, expected);
+        }
+
+        #[test]
+        fn test_view_matches_indexing(
+            (grid, rows, cols) in grids().prop_flat_map(|grid| {
+                let (height, width) = (grid.height(), grid.width());
+                (Just(grid), 0..=height, 0..=width)
+                    .prop_flat_map(move |(grid, row, col)| {
+                        (Just(grid), Just(row), Just(col), row..=height, col..=width)
+                    })
+                    .prop_map(|(grid, row, col, row_end, col_end)| (grid, row..row_end, col..col_end))
+            })
+        ) {
+            let view = grid.view(rows.clone(), cols.clone());
+            prop_assert_eq!(view.size(), (rows.len(), cols.len()));
+            for ((row, col), cell) in view.indexed_iter() {
+                prop_assert_eq!(cell, grid[(rows.start + row, cols.start + col)]);
+            }
+            prop_assert_eq!(view.iter().count(), rows.len() * cols.len());
+        }
+
+        #[test]
+        fn test_padded_matches_indexing(grid in grids(), border in 0usize..3) {
+            let padded = grid.padded(border, u16::MAX);
+            prop_assert_eq!(
+                padded.size(),
+                (grid.height() + 2 * border, grid.width() + 2 * border)
+            );
+            for ((row, col), cell) in padded.indexed_iter() {
+                let inner = (row.checked_sub(border), col.checked_sub(border));
+                match inner {
+                    (Some(row), Some(col)) if row < grid.height() && col < grid.width() => {
+                        prop_assert_eq!(cell, grid[(row, col)]);
+                    }
+                    _ => prop_assert_eq!(cell, u16::MAX),
+                }
+            }
+        }
+
+        #[test]
+        fn test_map_matches_indexing(grid in grids()) {
+            let mapped = grid.map(|&cell| u32::from(cell) * 2);
+            prop_assert_eq!(mapped.size(), grid.size());
+            prop_assert!(mapped.iter().eq(grid.iter().map(|cell| u32::from(cell) * 2)));
+        }
+
+        #[test]
+        fn test_mutable_slices_match_indexing(grid in grids()) {
+            let mut rows = grid.clone();
+            let mut cols = grid.clone();
+            for row in 0..grid.height() {
+                prop_assert!(rows.row_mut(row).iter().copied().eq(grid.row(row).iter()));
+                for cell in rows.row_mut(row).iter_mut() {
+                    *cell += 1;
+                }
+            }
+            for col in 0..grid.width() {
+                let mut slice = cols.col_mut(col);
+                prop_assert_eq!(slice.len(), grid.height());
+                for row in 0..slice.len() {
+                    slice[row] += 1;
+                }
+            }
+            prop_assert!(rows.iter().eq(grid.iter().map(|cell| cell + 1)));
+            prop_assert_eq!(rows, cols);
+        }
+
+        #[test]
+        fn test_range_indexing_matches_indexing(
+            (grid, row, range) in grids().prop_flat_map(|grid| {
+                let (height, width) = (grid.height(), grid.width());
+                (Just(grid), 0..height, 0..=width)
+                    .prop_flat_map(move |(grid, row, start)| (Just(grid), Just(row), Just(start), start..=width))
+                    .prop_map(|(grid, row, start, end)| (grid, row, start..end))
+            })
+        ) {
+            prop_assert!(grid[(row, range.clone())]
+                .iter()
+                .copied()
+                .eq(range.map(|col| grid[(row, col)])));
+        }
+    }
 }
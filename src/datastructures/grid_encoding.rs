@@ -0,0 +1,363 @@
+//! Text and binary encodings of grids.
+//!
+//! The text encoding writes one line per row. The binary encoding stores the
+//! sorted set of distinct cell values (the alphabet) followed by a bitmap
+//! with the minimal number of bits per cell needed to index the alphabet.
+//! Most puzzle grids use only a handful of distinct characters, so this is
+//! considerably more compact than the raw input.
+
+use std::ops::{Deref, Index};
+
+use anyhow::{anyhow, ensure};
+use serde::{
+    de::Error as _,
+    ser::{SerializeSeq, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use super::grid::GridView;
+
+const BINARY_MAGIC: &[u8; 4] = b"GRD1";
+
+/// Upper bound on the number of cells accepted when decoding, protecting
+/// against huge allocations from corrupt headers.
+const MAX_DECODED_CELLS: usize = 1 << 24;
+
+struct Cells<'a, T>(&'a GridView<T>);
+
+impl<T> Serialize for Cells<'_, T>
+where
+    GridView<T>: Index<(usize, usize)>,
+    <GridView<T> as Index<(usize, usize)>>::Output: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let grid = self.0;
+        let mut seq = serializer.serialize_seq(Some(grid.width() * grid.height()))?;
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                seq.serialize_element(&grid[(row, col)])?;
+            }
+        }
+        seq.end()
+    }
+}
+
+impl<T> Serialize for GridView<T>
+where
+    GridView<T>: Index<(usize, usize)>,
+    <GridView<T> as Index<(usize, usize)>>::Output: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("GridView", 3)?;
+        state.serialize_field("height", &self.height())?;
+        state.serialize_field("width", &self.width())?;
+        state.serialize_field("cells", &Cells(self))?;
+        state.end()
+    }
+}
+
+impl<'de, T> Deserialize<'de> for GridView<Vec<T>>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw<T> {
+            height: usize,
+            width: usize,
+            cells: Vec<T>,
+        }
+
+        let Raw {
+            height,
+            width,
+            cells,
+        } = Raw::deserialize(deserializer)?;
+        if width.checked_mul(height) != Some(cells.len()) {
+            return Err(D::Error::custom(format!(
+                "expected {} cells for a {}x{} grid, got {}",
+                width * height,
+                height,
+                width,
+                cells.len()
+            )));
+        }
+        if width == 0 {
+            if height > 0 {
+                return Err(D::Error::custom("grids without columns cannot have rows"));
+            }
+            return Ok(GridView::from_vec(1, 1, cells));
+        }
+        Ok(GridView::from_vec(width, 0, cells))
+    }
+}
+
+impl<T> GridView<T>
+where
+    T: Deref<Target = [u8]>,
+{
+    /// Encodes the grid with one newline-terminated line per row.
+    pub fn encode_text(&self) -> Vec<u8> {
+        let mut text = Vec::with_capacity((self.width() + 1) * self.height());
+        for row in 0..self.height() {
+            text.extend_from_slice(&self[(row, 0..self.width())]);
+            text.push(b'\n');
+        }
+        text
+    }
+
+    pub fn encode_binary(&self) -> Vec<u8> {
+        let mut alphabet = [false; 256];
+        for value in self.iter() {
+            alphabet[value as usize] = true;
+        }
+        let alphabet: Vec<u8> = (0..=255).filter(|&v| alphabet[v as usize]).collect();
+        let mut lookup = [0u8; 256];
+        for (i, &value) in alphabet.iter().enumerate() {
+            lookup[value as usize] = i as u8;
+        }
+        let bits = bits_per_cell(alphabet.len());
+
+        let mut encoded = Vec::with_capacity(
+            BINARY_MAGIC.len()
+                + 10
+                + alphabet.len()
+                + (self.width() * self.height() * bits).div_ceil(8),
+        );
+        encoded.extend_from_slice(BINARY_MAGIC);
+        encoded.extend_from_slice(&(self.height() as u32).to_le_bytes());
+        encoded.extend_from_slice(&(self.width() as u32).to_le_bytes());
+        encoded.extend_from_slice(&(alphabet.len() as u16).to_le_bytes());
+        encoded.extend_from_slice(&alphabet);
+
+        let mut writer = BitWriter::new(encoded);
+        for value in self.iter() {
+            writer.write(lookup[value as usize], bits);
+        }
+        writer.finish()
+    }
+}
+
+impl GridView<Vec<u8>> {
+    /// Decodes a grid from newline-separated rows of equal length. A trailing
+    /// newline is optional.
+    pub fn decode_text(text: &[u8]) -> anyhow::Result<Self> {
+        let text = text.strip_suffix(b"\n").unwrap_or(text);
+        if text.is_empty() {
+            return Ok(GridView::from_vec(1, 1, Vec::new()));
+        }
+        let mut rows = text.split(|&c| c == b'\n');
+        let width = rows.next().map_or(0, <[u8]>::len);
+        ensure!(width > 0, "grid rows must not be empty");
+        if let Some((i, row)) = rows.enumerate().find(|(_, row)| row.len() != width) {
+            return Err(anyhow!(
+                "row {} has length {}, expected {}",
+                i + 1,
+                row.len(),
+                width
+            ));
+        }
+        Ok(GridView::from_vec(width + 1, 1, text.to_vec()))
+    }
+
+    pub fn decode_binary(encoded: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = ByteReader(encoded);
+        ensure!(
+            reader.take(4)? == BINARY_MAGIC,
+            "not a binary grid encoding"
+        );
+        let height = u32::from_le_bytes(reader.take(4)?.try_into()?) as usize;
+        let width = u32::from_le_bytes(reader.take(4)?.try_into()?) as usize;
+        let alphabet_len = u16::from_le_bytes(reader.take(2)?.try_into()?) as usize;
+        ensure!(alphabet_len <= 256, "alphabet too large");
+        let alphabet = reader.take(alphabet_len)?;
+        let num_cells = width
+            .checked_mul(height)
+            .filter(|&num_cells| num_cells <= MAX_DECODED_CELLS)
+            .ok_or_else(|| anyhow!("grid dimensions {height}x{width} too large"))?;
+        ensure!(
+            num_cells == 0 || !alphabet.is_empty(),
+            "non-empty grid without alphabet"
+        );
+        ensure!(
+            width > 0 || height == 0,
+            "grids without columns cannot have rows"
+        );
+
+        let bits = bits_per_cell(alphabet_len);
+        let mut bit_reader = BitReader::new(reader.0);
+        ensure!(
+            reader.0.len() == (num_cells * bits).div_ceil(8),
+            "binary grid encoding has unexpected length"
+        );
+        let mut cells = Vec::with_capacity(num_cells);
+        for _ in 0..num_cells {
+            let index = bit_reader.read(bits)? as usize;
+            cells.push(
+                *alphabet
+                    .get(index)
+                    .ok_or_else(|| anyhow!("cell value {index} not in alphabet"))?,
+            );
+        }
+
+        if width == 0 {
+            return Ok(GridView::from_vec(1, 1, cells));
+        }
+        Ok(GridView::from_vec(width, 0, cells))
+    }
+}
+
+fn bits_per_cell(alphabet_len: usize) -> usize {
+    if alphabet_len <= 1 {
+        0
+    } else {
+        (usize::BITS - (alphabet_len - 1).leading_zeros()) as usize
+    }
+}
+
+struct ByteReader<'a>(&'a [u8]);
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, n: usize) -> anyhow::Result<&'a [u8]> {
+        ensure!(self.0.len() >= n, "unexpected end of binary grid encoding");
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+}
+
+struct BitWriter {
+    buffer: Vec<u8>,
+    used_bits: usize,
+}
+
+impl BitWriter {
+    fn new(buffer: Vec<u8>) -> Self {
+        Self {
+            buffer,
+            used_bits: 0,
+        }
+    }
+
+    fn write(&mut self, value: u8, bits: usize) {
+        for bit in 0..bits {
+            if self.used_bits.is_multiple_of(8) {
+                self.buffer.push(0);
+            }
+            if value & (1 << bit) != 0 {
+                *self.buffer.last_mut().unwrap() |= 1 << (self.used_bits % 8);
+            }
+            self.used_bits += 1;
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
+struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn read(&mut self, bits: usize) -> anyhow::Result<u8> {
+        let mut value = 0;
+        for bit in 0..bits {
+            let byte = self
+                .data
+                .get(self.position / 8)
+                .ok_or_else(|| anyhow!("unexpected end of binary grid encoding"))?;
+            if byte & (1 << (self.position % 8)) != 0 {
+                value |= 1 << bit;
+            }
+            self.position += 1;
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::GridView;
+
+    fn grids(
+        alphabet: impl Strategy<Value = u8> + Clone,
+    ) -> impl Strategy<Value = GridView<Vec<u8>>> {
+        (1usize..16, 0usize..16).prop_flat_map(move |(width, height)| {
+            vec(alphabet.clone(), width * height)
+                .prop_map(move |cells| GridView::from_vec(width, 0, cells))
+        })
+    }
+
+    #[test]
+    fn test_encode_text() {
+        let grid = GridView::from_separated(b'\n', b"#.#\n..#");
+        assert_eq!(grid.encode_text(), b"#.#\n..#\n");
+    }
+
+    #[test]
+    fn test_decode_text_rejects_ragged_rows() {
+        assert!(GridView::decode_text(b"#.#\n.#\n").is_err());
+    }
+
+    #[test]
+    fn test_binary_encoding_is_compact() {
+        let grid = GridView::from_separated(b'\n', b"#..#\n.##.\n#..#\n.##.");
+        let encoded = grid.encode_binary();
+        // header, 2 letter alphabet, 16 cells with 1 bit each
+        assert_eq!(encoded.len(), 14 + 2 + 2);
+        let decoded = GridView::decode_binary(&encoded).unwrap();
+        assert_eq!(decoded.size(), grid.size());
+        assert!(decoded.iter().eq(grid.iter()));
+    }
+
+    #[test]
+    fn test_decode_binary_rejects_truncated_input() {
+        let grid = GridView::from_separated(b'\n', b"#..#\n.##.\n#..#\n.##.");
+        let encoded = grid.encode_binary();
+        assert!(GridView::decode_binary(&encoded[..encoded.len() - 1]).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn test_binary_round_trip(grid in grids(any::<u8>())) {
+            prop_assert_eq!(GridView::decode_binary(&grid.encode_binary()).unwrap(), grid);
+        }
+
+        #[test]
+        fn test_binary_round_trip_small_alphabet(grid in grids(prop::sample::select(b"#.O".to_vec()))) {
+            prop_assert_eq!(GridView::decode_binary(&grid.encode_binary()).unwrap(), grid);
+        }
+
+        #[test]
+        fn test_text_round_trip(grid in grids(b' '..=b'~')) {
+            // the width of a grid without rows cannot be represented as text
+            prop_assume!(grid.height() > 0);
+            let decoded = GridView::decode_text(&grid.encode_text()).unwrap();
+            prop_assert_eq!(decoded.size(), grid.size());
+            prop_assert!(decoded.iter().eq(grid.iter()));
+        }
+
+        #[test]
+        fn test_serde_round_trip(grid in grids(any::<u8>())) {
+            let json = serde_json::to_string(&grid).unwrap();
+            let decoded: GridView<Vec<u8>> = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(decoded, grid);
+        }
+
+        #[test]
+        fn test_decode_binary_does_not_panic(data in vec(any::<u8>(), 0..64)) {
+            let mut encoded = b"GRD1".to_vec();
+            encoded.extend(data);
+            let _ = GridView::decode_binary(&encoded);
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Hash maps and sets for hot paths.
+//!
+//! By default, these use the Fx hasher, which is considerably faster than
+//! the standard library's SipHash for the small integer and byte slice keys
+//! typical for puzzle solvers, but offers no protection against hash
+//! flooding. Enable the `std-hash` feature to fall back to the standard
+//! hasher.
+
+use std::collections::{HashMap, HashSet};
+
+#[cfg(not(feature = "std-hash"))]
+pub type FastBuildHasher = rustc_hash::FxBuildHasher;
+#[cfg(feature = "std-hash")]
+pub type FastBuildHasher = std::collections::hash_map::RandomState;
+
+pub type FastMap<K, V> = HashMap<K, V, FastBuildHasher>;
+pub type FastSet<T> = HashSet<T, FastBuildHasher>;
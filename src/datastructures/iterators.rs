@@ -1,8 +1,28 @@
+use std::ops::Range;
+
 const TOP: u8 = 0b1000;
 const RIGHT: u8 = 0b0100;
 const BOTTOM: u8 = 0b0010;
 const LEFT: u8 = 0b0001;
 
+/// Offsets of the 4 neighbors in the order of [`NeighborIterator2d`].
+pub const NEIGHBOR_OFFSETS: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+
+/// Offsets of the 8 neighbors including the diagonal ones in the order of
+/// [`SurroundIterator2d`].
+pub const SURROUND_OFFSETS: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Iterates over the 8 neighbors of `center` including the diagonal ones
+/// within a grid of the given `size`.
 pub struct SurroundIterator2d {
     center: (usize, usize),
     sides: u8,
@@ -58,6 +78,8 @@ impl Iterator for SurroundIterator2d {
     }
 }
 
+/// Iterates over the 4 horizontal and vertical neighbors of `center` within a
+/// grid of the given `size`.
 pub struct NeighborIterator2d {
     center: (usize, usize),
     size: (usize, usize),
@@ -97,9 +119,101 @@ impl Iterator for NeighborIterator2d {
     }
 }
 
+/// Iterates over the positions at the given offsets from `center` that lie
+/// within a grid of the given `size`, e.g. the moves of a knight.
+pub struct OffsetIterator2d<'a> {
+    center: (usize, usize),
+    size: (usize, usize),
+    offsets: std::slice::Iter<'a, (isize, isize)>,
+}
+
+impl<'a> OffsetIterator2d<'a> {
+    pub fn new(
+        center: (usize, usize),
+        size: (usize, usize),
+        offsets: &'a [(isize, isize)],
+    ) -> Self {
+        Self {
+            center,
+            size,
+            offsets: offsets.iter(),
+        }
+    }
+}
+
+impl Iterator for OffsetIterator2d<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offsets.find_map(|&(d_row, d_col)| {
+            let row = self.center.0.checked_add_signed(d_row)?;
+            let col = self.center.1.checked_add_signed(d_col)?;
+            (row < self.size.0 && col < self.size.1).then_some((row, col))
+        })
+    }
+}
+
+/// Iterates in row-major order over all positions within the Manhattan
+/// distance `radius` of `center` (including `center` itself) that lie within a
+/// grid of the given `size`. Each position is yielded together with its
+/// distance to `center`.
+pub struct ManhattanIterator2d {
+    center: (usize, usize),
+    size: (usize, usize),
+    radius: usize,
+    row: usize,
+    row_end: usize,
+    col: usize,
+}
+
+impl ManhattanIterator2d {
+    pub fn new(center: (usize, usize), size: (usize, usize), radius: usize) -> Self {
+        let mut iter = Self {
+            center,
+            size,
+            radius,
+            row: center.0.saturating_sub(radius),
+            row_end: (center.0 + radius + 1).min(size.0),
+            col: 0,
+        };
+        iter.col = iter.col_range(iter.row).start;
+        iter
+    }
+
+    fn col_range(&self, row: usize) -> Range<usize> {
+        let remaining = self.radius - row.abs_diff(self.center.0);
+        self.center.1.saturating_sub(remaining)..(self.center.1 + remaining + 1).min(self.size.1)
+    }
+}
+
+impl Iterator for ManhattanIterator2d {
+    type Item = ((usize, usize), usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.row < self.row_end {
+            if self.col < self.col_range(self.row).end {
+                let pos = (self.row, self.col);
+                self.col += 1;
+                let distance = pos.0.abs_diff(self.center.0) + pos.1.abs_diff(self.center.1);
+                return Some((pos, distance));
+            }
+            self.row += 1;
+            if self.row < self.row_end {
+                self.col = self.col_range(self.row).start;
+            }
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{NeighborIterator2d, SurroundIterator2d};
+    use rstest::rstest;
+
+    use super::{
+        ManhattanIterator2d, NeighborIterator2d, OffsetIterator2d, SurroundIterator2d,
+        NEIGHBOR_OFFSETS, SURROUND_OFFSETS,
+    };
 
     #[test]
     fn test_surround_iterator_2d_upper_left() {
@@ -148,4 +262,71 @@ mod test {
         let indices: Vec<_> = NeighborIterator2d::new((1, 1), (3, 3)).collect();
         assert_eq!(indices, vec![(0, 1), (1, 0), (2, 1), (1, 2),]);
     }
+
+    #[rstest]
+    #[case((0, 0))]
+    #[case((1, 1))]
+    #[case((2, 1))]
+    #[case((0, 2))]
+    fn test_offset_iterator_2d_matches_fixed_neighborhoods(#[case] center: (usize, usize)) {
+        assert!(OffsetIterator2d::new(center, (3, 3), &SURROUND_OFFSETS)
+            .eq(SurroundIterator2d::new(center, (3, 3))));
+        assert!(OffsetIterator2d::new(center, (3, 3), &NEIGHBOR_OFFSETS)
+            .eq(NeighborIterator2d::new(center, (3, 3))));
+    }
+
+    #[test]
+    fn test_offset_iterator_2d_custom_offsets() {
+        let knight_moves = [
+            (-2, 1),
+            (-1, 2),
+            (1, 2),
+            (2, 1),
+            (2, -1),
+            (1, -2),
+            (-1, -2),
+            (-2, -1),
+        ];
+        let indices: Vec<_> = OffsetIterator2d::new((0, 1), (3, 4), &knight_moves).collect();
+        assert_eq!(indices, vec![(1, 3), (2, 2), (2, 0)]);
+    }
+
+    #[test]
+    fn test_manhattan_iterator_2d() {
+        let positions: Vec<_> = ManhattanIterator2d::new((1, 2), (4, 4), 2).collect();
+        assert_eq!(
+            positions,
+            vec![
+                ((0, 1), 2),
+                ((0, 2), 1),
+                ((0, 3), 2),
+                ((1, 0), 2),
+                ((1, 1), 1),
+                ((1, 2), 0),
+                ((1, 3), 1),
+                ((2, 1), 2),
+                ((2, 2), 1),
+                ((2, 3), 2),
+                ((3, 2), 2),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case((0, 0), 0)]
+    #[case((2, 3), 1)]
+    #[case((4, 1), 3)]
+    #[case((3, 3), 10)]
+    fn test_manhattan_iterator_2d_matches_brute_force(
+        #[case] center: (usize, usize),
+        #[case] radius: usize,
+    ) {
+        let size: (usize, usize) = (5, 4);
+        let expected: Vec<_> = (0..size.0)
+            .flat_map(|row| (0..size.1).map(move |col| (row, col)))
+            .map(|pos| (pos, pos.0.abs_diff(center.0) + pos.1.abs_diff(center.1)))
+            .filter(|&(_, distance)| distance <= radius)
+            .collect();
+        assert!(ManhattanIterator2d::new(center, size, radius).eq(expected));
+    }
 }
@@ -1,2 +1,9 @@
+pub mod direction;
 pub mod grid;
+pub mod grid_encoding;
+pub mod hash;
 pub mod iterators;
+pub mod point;
+pub mod ranges;
+pub mod regions;
+pub mod search;
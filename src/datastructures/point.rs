@@ -0,0 +1,280 @@
+//! Small 2D and 3D vectors for positions, velocities, and offsets.
+//!
+//! The components have no fixed meaning. Points on a
+//! [`GridView`](super::grid::GridView) use `(row, col)` like its indices, so
+//! that they convert to and from index tuples, whereas puzzles giving
+//! coordinates as `x,y` keep that order.
+
+use std::{
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+use num::{Integer, Signed};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct P2<T>(pub T, pub T);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct P3<T>(pub T, pub T, pub T);
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a < b {
+        b - a
+    } else {
+        a - b
+    }
+}
+
+impl<T> P2<T> {
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> P2<U> {
+        P2(f(self.0), f(self.1))
+    }
+
+    /// Converts the components, if they are representable in `U`, e.g.
+    /// between signed offsets and unsigned grid indices.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<P2<U>> {
+        Some(P2(self.0.try_into().ok()?, self.1.try_into().ok()?))
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> T
+    where
+        T: PartialOrd + Add<Output = T> + Sub<Output = T>,
+    {
+        abs_diff(self.0, other.0) + abs_diff(self.1, other.1)
+    }
+}
+
+impl<T: Signed> P2<T> {
+    /// Manhattan norm, i.e. the distance to the origin.
+    pub fn manhattan(&self) -> T {
+        self.0.abs() + self.1.abs()
+    }
+}
+
+impl<T: Integer> P2<T> {
+    /// Component-wise remainder of the floored division, which wraps
+    /// positions into `0..modulus` for positive moduli.
+    pub fn mod_floor(&self, modulus: &Self) -> Self {
+        P2(self.0.mod_floor(&modulus.0), self.1.mod_floor(&modulus.1))
+    }
+}
+
+impl P2<usize> {
+    pub fn checked_add_signed(self, rhs: P2<isize>) -> Option<Self> {
+        Some(P2(
+            self.0.checked_add_signed(rhs.0)?,
+            self.1.checked_add_signed(rhs.1)?,
+        ))
+    }
+
+    /// Adds an offset, wrapping around on underflow. Wrapped indices are out
+    /// of bounds of any grid, which saves a check if the grid is indexed
+    /// anyway.
+    pub fn wrapping_add_signed(self, rhs: P2<isize>) -> Self {
+        P2(
+            self.0.wrapping_add_signed(rhs.0),
+            self.1.wrapping_add_signed(rhs.1),
+        )
+    }
+}
+
+impl<T> P3<T> {
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> P3<U> {
+        P3(f(self.0), f(self.1), f(self.2))
+    }
+
+    /// Converts the components, if they are representable in `U`.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<P3<U>> {
+        Some(P3(
+            self.0.try_into().ok()?,
+            self.1.try_into().ok()?,
+            self.2.try_into().ok()?,
+        ))
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> T
+    where
+        T: PartialOrd + Add<Output = T> + Sub<Output = T>,
+    {
+        abs_diff(self.0, other.0) + abs_diff(self.1, other.1) + abs_diff(self.2, other.2)
+    }
+}
+
+impl<T: Signed> P3<T> {
+    /// Manhattan norm, i.e. the distance to the origin.
+    pub fn manhattan(&self) -> T {
+        self.0.abs() + self.1.abs() + self.2.abs()
+    }
+}
+
+/// Component type `T`, repeated once per component in [`impl_point`].
+macro_rules! component_type {
+    ($component:tt) => {
+        T
+    };
+}
+
+/// Implements the conversions from and to tuples and the component-wise
+/// arithmetic for a point type with the given components.
+macro_rules! impl_point {
+    ($point:ident, $($component:tt),+) => {
+        impl<T> From<($(component_type!($component),)+)> for $point<T> {
+            fn from(tuple: ($(component_type!($component),)+)) -> Self {
+                $point($(tuple.$component),+)
+            }
+        }
+
+        impl<T> From<$point<T>> for ($(component_type!($component),)+) {
+            fn from(p: $point<T>) -> Self {
+                ($(p.$component,)+)
+            }
+        }
+
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $point($(self.$component + rhs.$component),+)
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $point($(self.$component - rhs.$component),+)
+            }
+        }
+
+        impl<T: Mul<Output = T> + Copy> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                $point($(self.$component * rhs),+)
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $point($(-self.$component),+)
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$component += rhs.$component;)+
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$component -= rhs.$component;)+
+            }
+        }
+    };
+}
+
+impl_point!(P2, 0, 1);
+impl_point!(P3, 0, 1, 2);
+
+fn parse_components<T, const N: usize>(s: &str) -> anyhow::Result<[T; N]>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let components = s
+        .split(',')
+        .map(|c| c.trim().parse::<T>())
+        .collect::<Result<Vec<_>, _>>()?;
+    components
+        .try_into()
+        .map_err(|components: Vec<_>| anyhow!("expected {N} components, got {}", components.len()))
+}
+
+/// Parses comma-separated components like `3, -4`.
+impl<T> FromStr for P2<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [a, b] = parse_components(s)?;
+        Ok(P2(a, b))
+    }
+}
+
+/// Parses comma-separated components like `19, 13, 30`.
+impl<T> FromStr for P3<T>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [a, b, c] = parse_components(s)?;
+        Ok(P3(a, b, c))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::{P2, P3};
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(P2(1, 2) + P2(3, -4), P2(4, -2));
+        assert_eq!(P2(1, 2) - P2(3, -4), P2(-2, 6));
+        assert_eq!(P2(1, -2) * 3, P2(3, -6));
+        assert_eq!(-P2(1, -2), P2(-1, 2));
+        assert_eq!(P3(1., 2., 3.) + P3(1., 1., 1.) * 0.5, P3(1.5, 2.5, 3.5));
+
+        let mut p = P3(1, 2, 3);
+        p += P3(1, 1, 1);
+        p -= P3(0, 2, 0);
+        assert_eq!(p, P3(2, 1, 4));
+    }
+
+    #[test]
+    fn test_manhattan() {
+        assert_eq!(P2(3, -4).manhattan(), 7);
+        assert_eq!(P3(-1, 2, -3).manhattan(), 6);
+        assert_eq!(P2(1usize, 5).manhattan_distance(P2(4, 2)), 6);
+    }
+
+    #[test]
+    fn test_signed_conversions() {
+        assert_eq!(P2(1usize, 2).checked_add_signed(P2(-1, 1)), Some(P2(0, 3)));
+        assert_eq!(P2(0usize, 2).checked_add_signed(P2(-1, 1)), None);
+        assert_eq!(P2(0usize, 2).wrapping_add_signed(P2(-1, 1)).0, usize::MAX);
+        assert_eq!(P2(-1isize, 2).try_cast::<usize>(), None);
+        assert_eq!(P2(1isize, 2).try_cast::<usize>(), Some(P2(1, 2)));
+        assert_eq!(P2(-7, 12).mod_floor(&P2(5, 5)), P2(3, 2));
+    }
+
+    #[test]
+    fn test_parse() -> anyhow::Result<()> {
+        assert_eq!("3,-4".parse::<P2<i64>>()?, P2(3, -4));
+        assert_eq!("19, 13, 30".parse::<P3<f64>>()?, P3(19., 13., 30.));
+        assert!("1, 2".parse::<P3<i32>>().is_err());
+        assert!("1, x".parse::<P2<i32>>().is_err());
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_add_sub_round_trip(a in any::<(i32, i32)>(), b in any::<(i32, i32)>()) {
+            let (a, b) = (P2::from(a).map(i64::from), P2::from(b).map(i64::from));
+            prop_assert_eq!(a + b - b, a);
+            prop_assert_eq!((a - b).manhattan(), a.manhattan_distance(b));
+        }
+    }
+}
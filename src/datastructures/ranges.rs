@@ -0,0 +1,409 @@
+//! Half-open intervals of integers, e.g. the seed ranges mapped through the
+//! almanac of 2023 day 5 or the ranges of fresh ingredient ids of 2025 day 5.
+
+use std::{
+    cmp::Ordering,
+    collections::BTreeMap,
+    iter::Sum,
+    ops::{Range, Sub},
+};
+
+use anyhow::bail;
+
+/// Intersection of two ranges, if they overlap.
+pub fn intersect<T: Copy + Ord>(a: &Range<T>, b: &Range<T>) -> Option<Range<T>> {
+    let intersection = a.start.max(b.start)..a.end.min(b.end);
+    (!intersection.is_empty()).then_some(intersection)
+}
+
+/// Non-empty parts of `minuend` not covered by `subtrahend`, in ascending
+/// order.
+pub fn subtract<T: Copy + Ord>(minuend: &Range<T>, subtrahend: &Range<T>) -> Vec<Range<T>> {
+    if subtrahend.is_empty() {
+        return if minuend.is_empty() {
+            vec![]
+        } else {
+            vec![minuend.clone()]
+        };
+    }
+    [
+        minuend.start..minuend.end.min(subtrahend.start),
+        minuend.start.max(subtrahend.end)..minuend.end,
+    ]
+    .into_iter()
+    .filter(|range| !range.is_empty())
+    .collect()
+}
+
+/// Key of a [`RangeMap`] that compares equal to every overlapping range, so
+/// that a lookup with a single-element range finds the range containing it.
+#[derive(Debug, Clone)]
+struct RangeKey(Range<u64>);
+
+impl PartialEq<Self> for RangeKey {
+    fn eq(&self, other: &Self) -> bool {
+        !(self.0.end <= other.0.start || other.0.end <= self.0.start)
+    }
+}
+
+impl Eq for RangeKey {}
+
+impl PartialOrd for RangeKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RangeKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            return Ordering::Equal;
+        }
+        self.0.start.cmp(&other.0.start)
+    }
+}
+
+/// Mapping of disjoint source ranges to destination ranges of the same
+/// length. Values outside all source ranges map to themselves.
+#[derive(Debug, Clone, Default)]
+pub struct RangeMap(BTreeMap<RangeKey, u64>);
+
+impl RangeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps `source` to the range of the same length starting at
+    /// `dest_start`.
+    ///
+    /// # Panics
+    ///
+    /// If `source` overlaps a previously inserted source range. Use
+    /// [`try_insert`](Self::try_insert) for ranges from puzzle inputs.
+    pub fn insert(&mut self, source: Range<u64>, dest_start: u64) {
+        if let Err(err) = self.try_insert(source, dest_start) {
+            panic!("{err}");
+        }
+    }
+
+    /// Maps `source` to the range of the same length starting at
+    /// `dest_start`, unless it overlaps a previously inserted source range or
+    /// the destination range exceeds `u64::MAX`.
+    pub fn try_insert(&mut self, source: Range<u64>, dest_start: u64) -> anyhow::Result<()> {
+        if source.is_empty() {
+            return Ok(());
+        }
+        if dest_start.checked_add(source.end - source.start).is_none() {
+            bail!("destination of source range {source:?} exceeds u64::MAX");
+        }
+        let key = RangeKey(source);
+        if self.0.contains_key(&key) {
+            bail!("source range {:?} overlaps an existing mapping", key.0);
+        }
+        self.0.insert(key, dest_start);
+        Ok(())
+    }
+
+    pub fn map(&self, value: u64) -> u64 {
+        match self.0.get_key_value(&RangeKey(value..value + 1)) {
+            Some((source, dest_start)) => dest_start + (value - source.0.start),
+            None => value,
+        }
+    }
+
+    /// Maps all values of `range`, splitting it where it crosses the
+    /// boundaries of source ranges. The resulting ranges are not sorted and
+    /// may overlap if several source ranges map to the same destination.
+    pub fn map_range(&self, range: &Range<u64>) -> Vec<Range<u64>> {
+        let mapped = self.0.iter().filter_map(|(source, dest_start)| {
+            intersect(range, &source.0).map(|intersection| {
+                dest_start + (intersection.start - source.0.start)
+                    ..dest_start + (intersection.end - source.0.start)
+            })
+        });
+        let unmapped = self.0.keys().fold(
+            if range.is_empty() {
+                vec![]
+            } else {
+                vec![range.clone()]
+            },
+            |remainder, source| {
+                remainder
+                    .iter()
+                    .flat_map(|part| subtract(part, &source.0))
+                    .collect()
+            },
+        );
+        mapped.chain(unmapped).collect()
+    }
+}
+
+/// Set of values stored as sorted, disjoint ranges. Overlapping and adjacent
+/// ranges are merged on insertion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSet<T = u64>(BTreeMap<T, T>);
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self(BTreeMap::new())
+    }
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let Range { mut start, mut end } = range;
+        while let Some((&other_start, &other_end)) = self.0.range(..=end).next_back() {
+            if other_end < start {
+                break;
+            }
+            start = start.min(other_start);
+            end = end.max(other_end);
+            self.0.remove(&other_start);
+        }
+        self.0.insert(start, end);
+    }
+
+    pub fn remove(&mut self, range: &Range<T>) {
+        let overlapping: Vec<_> = self.overlapping(range).collect();
+        for other in overlapping {
+            self.0.remove(&other.start);
+            for part in subtract(&other, range) {
+                self.0.insert(part.start, part.end);
+            }
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.0
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value < end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn total_len(&self) -> T
+    where
+        T: Sub<Output = T> + Sum<T>,
+    {
+        self.iter().map(|range| range.end - range.start).sum()
+    }
+
+    /// The disjoint ranges of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.0.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// Ranges of the set overlapping `range` in descending order.
+    fn overlapping<'a>(&'a self, range: &'a Range<T>) -> impl Iterator<Item = Range<T>> + 'a {
+        self.0
+            .range(..range.end)
+            .rev()
+            .take_while(|(_, &end)| end > range.start)
+            .map(|(&start, &end)| start..end)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.iter());
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.iter()
+            .flat_map(|range| {
+                other
+                    .overlapping(&range)
+                    .filter_map(|other| intersect(&range, &other))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in other.iter() {
+            difference.remove(&range);
+        }
+        difference
+    }
+
+    /// Values within `bounds` that are not in the set.
+    pub fn complement(&self, bounds: Range<T>) -> Self {
+        Self::from_iter([bounds]).difference(self)
+    }
+}
+
+impl<T: Copy + Ord> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::{collection::btree_set, prelude::*};
+    use std::{collections::BTreeSet, ops::Range};
+
+    use super::{intersect, subtract, RangeMap, RangeSet};
+
+    fn ranges() -> impl Strategy<Value = Range<u64>> {
+        (0u64..64, 1u64..32).prop_map(|(start, len)| start..start + len)
+    }
+
+    /// Range maps with disjoint source ranges.
+    fn range_maps() -> impl Strategy<Value = RangeMap> {
+        btree_set(0u64..100, 0..10)
+            .prop_flat_map(|bounds| {
+                let bounds: Vec<_> = bounds.into_iter().collect();
+                let count = bounds.len() / 2;
+                (Just(bounds), prop::collection::vec(0u64..200, count))
+            })
+            .prop_map(|(bounds, destinations)| {
+                let mut map = RangeMap::new();
+                for (bounds, destination) in bounds.chunks_exact(2).zip(destinations) {
+                    map.insert(bounds[0]..bounds[1], destination);
+                }
+                map
+            })
+    }
+
+    fn elements(ranges: &[Range<u64>]) -> BTreeSet<u64> {
+        ranges.iter().flat_map(Range::clone).collect()
+    }
+
+    #[test]
+    fn test_range_map() {
+        let mut map = RangeMap::new();
+        map.insert(98..100, 50);
+        map.insert(50..98, 52);
+        assert_eq!(map.map(49), 49);
+        assert_eq!(map.map(50), 52);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(100), 100);
+        let mut mapped = map.map_range(&(90..102));
+        mapped.sort_by_key(|range| range.start);
+        assert_eq!(mapped, [50..52, 92..100, 100..102]);
+    }
+
+    #[test]
+    fn test_range_map_rejects_overlapping_sources() {
+        let mut map = RangeMap::new();
+        map.insert(10..20, 0);
+        assert!(map.try_insert(15..25, 100).is_err());
+        assert!(map.try_insert(5..11, 100).is_err());
+        assert!(map.try_insert(0..10, u64::MAX - 5).is_err());
+        assert!(map.try_insert(20..25, 100).is_ok());
+        assert_eq!(map.map(22), 102);
+    }
+
+    fn range_sets() -> impl Strategy<Value = RangeSet> {
+        prop::collection::vec(ranges(), 0..6).prop_map(RangeSet::from_iter)
+    }
+
+    #[test]
+    fn test_range_set() {
+        let mut set = RangeSet::from_iter([3..5, 10..14, 16..20, 12..16]);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3..5, 10..20]);
+        assert_eq!(set.total_len(), 12);
+        assert!(set.contains(3));
+        assert!(!set.contains(5));
+        set.insert(5..6);
+        set.remove(&(12..14));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3..6, 10..12, 14..20]);
+        assert_eq!(
+            set.complement(0..16).iter().collect::<Vec<_>>(),
+            [0..3, 6..10, 12..14]
+        );
+    }
+
+    #[test]
+    fn test_range_set_operations() {
+        let a = RangeSet::from_iter([0..10, 20..30]);
+        let mut b = RangeSet::new();
+        b.insert(5..25);
+        let union = a.union(&b);
+        assert_eq!(union.iter().next(), Some(0..30));
+        assert_eq!(union.total_len(), 30);
+        assert_eq!(
+            a.intersection(&b).iter().collect::<Vec<_>>(),
+            [5..10, 20..25]
+        );
+        assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), [0..5, 25..30]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_intersect(a in ranges(), b in ranges()) {
+            let expected: BTreeSet<_> = a.clone().filter(|x| b.contains(x)).collect();
+            let intersection = intersect(&a, &b);
+            prop_assert_eq!(intersection.is_none(), expected.is_empty());
+            prop_assert_eq!(elements(&intersection.into_iter().collect::<Vec<_>>()), expected);
+        }
+
+        #[test]
+        fn test_subtract(minuend in ranges(), subtrahend in ranges()) {
+            let difference = subtract(&minuend, &subtrahend);
+            let expected: BTreeSet<_> = minuend.clone().filter(|x| !subtrahend.contains(x)).collect();
+            prop_assert!(difference.iter().all(|range| !range.is_empty()));
+            prop_assert_eq!(
+                difference.iter().map(|range| range.end - range.start).sum::<u64>(),
+                expected.len() as u64
+            );
+            prop_assert_eq!(elements(&difference), expected);
+        }
+
+        #[test]
+        fn test_map_range_maps_each_element_once(map in range_maps(), range in ranges()) {
+            let mapped = map.map_range(&range);
+            prop_assert!(mapped.iter().all(|range| !range.is_empty()));
+            prop_assert_eq!(
+                mapped.iter().map(|range| range.end - range.start).sum::<u64>(),
+                range.end - range.start
+            );
+            let mut expected: Vec<_> = range.map(|x| map.map(x)).collect();
+            expected.sort_unstable();
+            let mut actual: Vec<_> = mapped.iter().flat_map(Range::clone).collect();
+            actual.sort_unstable();
+            prop_assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn test_range_set_matches_element_sets(a in range_sets(), b in range_sets()) {
+            let elements_a = elements(&a.iter().collect::<Vec<_>>());
+            let elements_b = elements(&b.iter().collect::<Vec<_>>());
+            prop_assert_eq!(a.total_len(), elements_a.len() as u64);
+            prop_assert!(a.iter().zip(a.iter().skip(1)).all(|(x, y)| x.end < y.start));
+            prop_assert!((0..100).all(|x| a.contains(x) == elements_a.contains(&x)));
+
+            let union = elements(&a.union(&b).iter().collect::<Vec<_>>());
+            prop_assert_eq!(union, &elements_a | &elements_b);
+            let intersection = elements(&a.intersection(&b).iter().collect::<Vec<_>>());
+            prop_assert_eq!(intersection, &elements_a & &elements_b);
+            let difference = elements(&a.difference(&b).iter().collect::<Vec<_>>());
+            prop_assert_eq!(difference, &elements_a - &elements_b);
+            let complement = elements(&a.complement(0..100).iter().collect::<Vec<_>>());
+            prop_assert_eq!(complement, (0..100).filter(|x| !elements_a.contains(x)).collect());
+        }
+    }
+}
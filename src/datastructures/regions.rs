@@ -0,0 +1,157 @@
+//! Connected regions of grid cells, e.g. the garden plots of one plant.
+
+use std::ops::{Deref, Index};
+
+use super::{grid::GridView, iterators::NeighborIterator2d};
+
+/// Labeling of the cells of a grid with the ids of the regions they belong
+/// to. Regions are numbered in the row-major order of their first cell.
+pub struct Regions {
+    labels: GridView<Vec<Option<usize>>>,
+    cells: Vec<Vec<(usize, usize)>>,
+}
+
+impl Regions {
+    /// Labels the regions of horizontally or vertically adjacent cells with
+    /// equal values.
+    pub fn of_equal<T, U>(grid: &GridView<T>) -> Self
+    where
+        T: Deref<Target = [U]>,
+        U: PartialEq,
+    {
+        Self::find(grid.size(), |_| true, |a, b| grid[a] == grid[b])
+    }
+
+    /// Labels the regions of horizontally or vertically adjacent cells that
+    /// match the predicate. Other cells do not belong to any region.
+    pub fn of_matching(
+        size: (usize, usize),
+        is_member: impl FnMut((usize, usize)) -> bool,
+    ) -> Self {
+        Self::find(size, is_member, |_, _| true)
+    }
+
+    fn find(
+        size: (usize, usize),
+        mut is_member: impl FnMut((usize, usize)) -> bool,
+        mut connects: impl FnMut((usize, usize), (usize, usize)) -> bool,
+    ) -> Self {
+        let mut labels = GridView::filled(size, None);
+        let mut cells = vec![];
+        for start in (0..size.0).flat_map(|row| (0..size.1).map(move |col| (row, col))) {
+            if labels[start].is_some() || !is_member(start) {
+                continue;
+            }
+            let id = cells.len();
+            let mut region = vec![start];
+            labels[start] = Some(id);
+            let mut next = 0;
+            while let Some(&pos) = region.get(next) {
+                next += 1;
+                for neighbor in NeighborIterator2d::new(pos, size) {
+                    if labels[neighbor].is_none() && is_member(neighbor) && connects(pos, neighbor)
+                    {
+                        labels[neighbor] = Some(id);
+                        region.push(neighbor);
+                    }
+                }
+            }
+            cells.push(region);
+        }
+        Self { labels, cells }
+    }
+
+    /// Id of the region the cell at `pos` belongs to.
+    pub fn label(&self, pos: (usize, usize)) -> Option<usize> {
+        self.labels[pos]
+    }
+
+    /// Number of regions.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn area(&self, id: usize) -> usize {
+        self.cells[id].len()
+    }
+
+    /// Cells of the region in the order they were reached from its first cell.
+    pub fn cells(&self, id: usize) -> &[(usize, usize)] {
+        &self.cells[id]
+    }
+
+    /// Cells of all regions in the order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = &[(usize, usize)]> + '_ {
+        self.cells.iter().map(Vec::as_slice)
+    }
+}
+
+impl Index<usize> for Regions {
+    type Output = [(usize, usize)];
+
+    fn index(&self, id: usize) -> &Self::Output {
+        self.cells(id)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::Regions;
+    use crate::datastructures::{grid::GridView, iterators::NeighborIterator2d};
+
+    #[test]
+    fn test_regions_of_equal() {
+        let grid = GridView::from_separated(b'\n', b"AAB\nCAB\nCCA");
+        let regions = Regions::of_equal(&grid);
+        assert_eq!(regions.len(), 4);
+        assert_eq!(regions.label((1, 1)), Some(0));
+        assert_eq!(regions.label((2, 2)), Some(3));
+        let areas: Vec<_> = (0..regions.len()).map(|id| regions.area(id)).collect();
+        assert_eq!(areas, [3, 2, 3, 1]);
+        let mut cells = regions[2].to_vec();
+        cells.sort_unstable();
+        assert_eq!(cells, [(1, 0), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn test_regions_of_matching() {
+        let grid = GridView::from_separated(b'\n', b"#.#\n#..\n.##");
+        let regions = Regions::of_matching(grid.size(), |pos| grid[pos] == b'.');
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions.label((0, 0)), None);
+        assert_eq!(regions.iter().map(<[_]>::len).collect::<Vec<_>>(), [3, 1]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_regions_partition_grid(
+            data in proptest::collection::vec(0u8..3, 1..=36),
+            width in 1usize..=6,
+        ) {
+            let height = data.len() / width;
+            prop_assume!(height > 0);
+            let grid = GridView::from_vec(width, 0, data[..height * width].to_vec());
+            let regions = Regions::of_equal(&grid);
+            prop_assert_eq!(
+                regions.iter().map(<[_]>::len).sum::<usize>(),
+                height * width
+            );
+            for (pos, value) in grid.indexed_iter() {
+                let label = regions.label(pos).unwrap();
+                prop_assert!(regions[label].contains(&pos));
+                for neighbor in NeighborIterator2d::new(pos, grid.size()) {
+                    prop_assert_eq!(
+                        regions.label(neighbor) == Some(label),
+                        grid[neighbor] == value
+                    );
+                }
+            }
+        }
+    }
+}
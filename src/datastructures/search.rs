@@ -0,0 +1,392 @@
+//! Shortest path searches on graphs given implicitly by a successor function,
+//! which maps a node to its neighbors and the costs of the edges to them.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use super::{grid::GridView, hash::FastMap, iterators::NeighborIterator2d};
+
+struct Node<N, C> {
+    node: N,
+    cost: C,
+    /// Predecessors on paths of minimal cost, only the first one unless all
+    /// optimal paths are tracked.
+    parents: Vec<usize>,
+}
+
+/// Nodes reached by a search, indexed in the order of discovery.
+struct Search<N, C> {
+    nodes: Vec<Node<N, C>>,
+    indices: FastMap<N, usize>,
+}
+
+impl<N, C> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+{
+    /// Runs A* and returns the indices of the goal nodes reached with minimal
+    /// cost: only the first one, or all of them if `all_optimal` is set. The
+    /// latter also records all predecessors on optimal paths.
+    fn run<I>(
+        start: N,
+        mut successors: impl FnMut(&N) -> I,
+        mut heuristic: impl FnMut(&N) -> C,
+        mut is_goal: impl FnMut(&N) -> bool,
+        all_optimal: bool,
+    ) -> (Self, Vec<usize>)
+    where
+        I: IntoIterator<Item = (N, C)>,
+    {
+        let mut search = Self {
+            nodes: vec![Node {
+                node: start.clone(),
+                cost: C::default(),
+                parents: vec![],
+            }],
+            indices: FastMap::default(),
+        };
+        let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+        search.indices.insert(start, 0);
+        let mut goals: Vec<usize> = vec![];
+        while let Some(Reverse((estimate, cost, index))) = queue.pop() {
+            if cost > search.nodes[index].cost {
+                continue;
+            }
+            if let Some(&goal) = goals.first() {
+                // Nodes with a higher estimate cannot be on an optimal path.
+                if estimate > search.nodes[goal].cost {
+                    break;
+                }
+            }
+            if is_goal(&search.nodes[index].node) {
+                goals.push(index);
+                if !all_optimal {
+                    break;
+                }
+                continue;
+            }
+
+            for (successor, edge_cost) in successors(&search.nodes[index].node) {
+                let successor_cost = cost + edge_cost;
+                let successor_index = match search.indices.entry(successor) {
+                    Entry::Occupied(entry) => {
+                        let node = &mut search.nodes[*entry.get()];
+                        if successor_cost > node.cost
+                            || (successor_cost == node.cost && !all_optimal)
+                        {
+                            continue;
+                        }
+                        if successor_cost == node.cost {
+                            node.parents.push(index);
+                            continue;
+                        }
+                        node.cost = successor_cost;
+                        node.parents = vec![index];
+                        *entry.get()
+                    }
+                    Entry::Vacant(entry) => {
+                        search.nodes.push(Node {
+                            node: entry.key().clone(),
+                            cost: successor_cost,
+                            parents: vec![index],
+                        });
+                        *entry.insert(search.nodes.len() - 1)
+                    }
+                };
+                let estimate = successor_cost + heuristic(&search.nodes[successor_index].node);
+                queue.push(Reverse((estimate, successor_cost, successor_index)));
+            }
+        }
+        (search, goals)
+    }
+
+    fn path_to(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[index].node.clone()];
+        while let Some(&parent) = self.nodes[index].parents.first() {
+            path.push(self.nodes[parent].node.clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+
+    /// All nodes on optimal paths to the given goals, in no particular order.
+    fn optimal_nodes(&self, goals: Vec<usize>) -> Vec<N> {
+        let mut on_path = vec![false; self.nodes.len()];
+        let mut to_visit = goals;
+        while let Some(index) = to_visit.pop() {
+            if !std::mem::replace(&mut on_path[index], true) {
+                to_visit.extend(&self.nodes[index].parents);
+            }
+        }
+        on_path
+            .iter()
+            .zip(&self.nodes)
+            .filter(|(&on_path, _)| on_path)
+            .map(|(_, node)| node.node.clone())
+            .collect()
+    }
+}
+
+/// Finds the minimal cost to reach a goal node from `start` with Dijkstra's
+/// algorithm. Edge costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but also returns a path of minimal cost from `start` to
+/// the goal, including both.
+pub fn dijkstra_with_path<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (search, goals) = Search::run(start, successors, |_| C::default(), is_goal, false);
+    let &goal = goals.first()?;
+    Some((search.nodes[goal].cost, search.path_to(goal)))
+}
+
+/// Finds the minimal cost to reach a goal node from `start` with A*. The
+/// `heuristic` estimates the remaining cost to a goal. It must be consistent,
+/// i.e. never decrease by more than the cost of an edge and never
+/// overestimate, for the result to be optimal.
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (search, goals) = Search::run(start, successors, heuristic, is_goal, false);
+    goals.first().map(|&goal| search.nodes[goal].cost)
+}
+
+/// Like [`astar`], but returns all nodes that lie on any path of minimal cost
+/// from `start` to any goal, in no particular order.
+pub fn astar_all_optimal<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (search, goals) = Search::run(start, successors, heuristic, is_goal, true);
+    let cost = search.nodes[*goals.first()?].cost;
+    Some((cost, search.optimal_nodes(goals)))
+}
+
+/// Distances of the cells of a grid to the nearest source of a breadth-first
+/// search, with the predecessors on the shortest paths.
+pub struct DistanceMap {
+    distances: GridView<Vec<Option<usize>>>,
+    predecessors: GridView<Vec<Option<(usize, usize)>>>,
+}
+
+impl DistanceMap {
+    /// Number of steps from the nearest source, if `pos` was reached.
+    pub fn distance(&self, pos: (usize, usize)) -> Option<usize> {
+        self.distances[pos]
+    }
+
+    /// Previous cell on a shortest path from a source to `pos`, if `pos` was
+    /// reached and is not a source.
+    pub fn predecessor(&self, pos: (usize, usize)) -> Option<(usize, usize)> {
+        self.predecessors[pos]
+    }
+
+    /// Reached cells with their distances in row-major order.
+    pub fn reached(&self) -> impl Iterator<Item = ((usize, usize), usize)> + '_ {
+        self.distances
+            .indexed_iter()
+            .filter_map(|(pos, distance)| Some((pos, distance?)))
+    }
+}
+
+/// Breadth-first search over the 4-neighborhoods of a grid of the given
+/// `size`, starting from all `sources` at once and only entering cells that
+/// are passable.
+pub fn bfs_grid(
+    size: (usize, usize),
+    sources: impl IntoIterator<Item = (usize, usize)>,
+    mut is_passable: impl FnMut((usize, usize)) -> bool,
+) -> DistanceMap {
+    let mut distances = GridView::filled(size, None);
+    let mut predecessors = GridView::filled(size, None);
+    let mut queue = VecDeque::new();
+    for source in sources {
+        if distances[source].is_none() {
+            distances[source] = Some(0);
+            queue.push_back(source);
+        }
+    }
+    while let Some(pos) = queue.pop_front() {
+        let next_distance = distances[pos].map(|distance| distance + 1);
+        for neighbor in NeighborIterator2d::new(pos, size) {
+            if distances[neighbor].is_none() && is_passable(neighbor) {
+                distances[neighbor] = next_distance;
+                predecessors[neighbor] = Some(pos);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    DistanceMap {
+        distances,
+        predecessors,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::{astar, astar_all_optimal, bfs_grid, dijkstra, dijkstra_with_path};
+    use crate::datastructures::grid::GridView;
+
+    /// Edges of a small directed graph with a cheaper detour `0 → 1 → 2 → 3`
+    /// around the direct edge `0 → 3`.
+    fn successors(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (3, 10), (4, 1)],
+            1 => vec![(2, 2)],
+            2 => vec![(3, 3), (0, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(dijkstra(0, successors, |&node| node == 3), Some(6));
+        assert_eq!(dijkstra(0, successors, |&node| node == 0), Some(0));
+        assert_eq!(dijkstra(1, successors, |&node| node == 4), Some(4));
+        assert_eq!(dijkstra(3, successors, |&node| node == 0), None);
+    }
+
+    #[test]
+    fn test_dijkstra_with_path() {
+        assert_eq!(
+            dijkstra_with_path(0, successors, |&node| node == 3),
+            Some((6, vec![0, 1, 2, 3]))
+        );
+        assert_eq!(
+            dijkstra_with_path(0, successors, |&node| node == 0),
+            Some((0, vec![0]))
+        );
+    }
+
+    #[test]
+    fn test_astar_all_optimal() {
+        // Two optimal paths 0 → 1 → 3 → 4 and 0 → 2 → 3 → 4, and a costlier
+        // one through 5.
+        let successors = |node: &u32| match node {
+            0 => vec![(1, 1), (2, 1), (5, 1)],
+            1 | 2 => vec![(3, 1)],
+            3 => vec![(4, 1)],
+            5 => vec![(4, 5)],
+            _ => vec![],
+        };
+        let (cost, mut nodes) = astar_all_optimal(
+            0,
+            successors,
+            |&node| u32::from(node != 4),
+            |&node| node == 4,
+        )
+        .unwrap();
+        nodes.sort_unstable();
+        assert_eq!(cost, 3);
+        assert_eq!(nodes, [0, 1, 2, 3, 4]);
+        assert_eq!(astar(0, successors, |_| 0, |&node| node == 4), Some(3));
+        assert_eq!(
+            astar_all_optimal(4, successors, |_| 0, |&node| node == 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bfs_grid() {
+        let grid = GridView::from_separated(b'\n', b"..#.\n#...\n..#.");
+        let distances = bfs_grid(grid.size(), [(0, 0), (2, 3)], |pos| grid[pos] != b'#');
+        let reached: Vec<_> = distances.reached().collect();
+        assert_eq!(
+            reached,
+            [
+                ((0, 0), 0),
+                ((0, 1), 1),
+                ((0, 3), 2),
+                ((1, 1), 2),
+                ((1, 2), 2),
+                ((1, 3), 1),
+                ((2, 0), 4),
+                ((2, 1), 3),
+                ((2, 3), 0),
+            ]
+        );
+        assert_eq!(distances.distance((0, 2)), None);
+        assert_eq!(distances.predecessor((0, 3)), Some((1, 3)));
+        assert_eq!(distances.predecessor((2, 3)), None);
+    }
+
+    proptest! {
+        #[test]
+        fn test_dijkstra_on_line_matches_distance(start in 0i32..20, goal in 0i32..20) {
+            let successors = |&node: &i32| {
+                [(node - 1, 1u32), (node + 1, 1)]
+                    .into_iter()
+                    .filter(|&(next, _)| (0..20).contains(&next))
+            };
+            let (cost, path) = dijkstra_with_path(start, successors, |&node| node == goal).unwrap();
+            prop_assert_eq!(cost, start.abs_diff(goal));
+            prop_assert_eq!(path.len() as u32, cost + 1);
+        }
+        #[test]
+        fn test_astar_on_grid_matches_dijkstra(
+            walls in proptest::collection::vec(any::<bool>(), 36),
+            goal in (0i32..6, 0i32..6),
+        ) {
+            let successors = |&(row, col): &(i32, i32)| {
+                [(row - 1, col), (row + 1, col), (row, col - 1), (row, col + 1)]
+                    .into_iter()
+                    .filter(|&(row, col)| {
+                        (0..6).contains(&row) && (0..6).contains(&col) && !walls[(row * 6 + col) as usize]
+                    })
+                    .map(|next| (next, 1u32))
+            };
+            let heuristic = |&(row, col): &(i32, i32)| row.abs_diff(goal.0) + col.abs_diff(goal.1);
+            let is_goal = |&node: &(i32, i32)| node == goal;
+            prop_assert_eq!(
+                astar((0, 0), successors, heuristic, is_goal),
+                dijkstra((0, 0), successors, is_goal)
+            );
+            if let Some((cost, nodes)) = astar_all_optimal((0, 0), successors, heuristic, is_goal) {
+                prop_assert!(nodes.contains(&(0, 0)) && nodes.contains(&goal));
+                prop_assert!(nodes.len() as u32 > cost);
+            }
+        }
+    }
+}
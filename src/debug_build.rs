@@ -0,0 +1,72 @@
+//! Detection of debug builds, in which brute-force solvers are 10–50 times
+//! slower and appear to hang.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use ansi_term::Color::Yellow;
+use anyhow::Context;
+
+/// Warns if this is a debug build. If `reexec` is set and a release build of
+/// the binary exists, it is run with the same arguments instead and the
+/// process exits with its exit code.
+pub fn check(reexec: bool) -> anyhow::Result<()> {
+    if !cfg!(debug_assertions) {
+        return Ok(());
+    }
+    let release = std::env::current_exe()
+        .ok()
+        .and_then(|exe| release_binary(&exe))
+        .filter(|release| release.is_file());
+    match release {
+        Some(release) if reexec => {
+            eprintln!(
+                "{}",
+                Yellow.paint(format!("Running the release build {}", release.display()))
+            );
+            let status = Command::new(&release)
+                .args(std::env::args_os().skip(1))
+                .status()
+                .with_context(|| format!("running {}", release.display()))?;
+            std::process::exit(status.code().unwrap_or(1));
+        }
+        _ => {
+            eprintln!(
+                "{} {}",
+                Yellow.bold().paint("Warning:"),
+                Yellow.paint(
+                    "this is a debug build, in which solvers are much slower. Build with \
+                     `--release` or pass `--reexec-release` to use an existing release build."
+                )
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Path of the release build next to the debug build `exe` in Cargo's target
+/// directory, e.g. `target/release/aoc` for `target/debug/aoc`.
+fn release_binary(exe: &Path) -> Option<PathBuf> {
+    let profile_dir = exe.parent()?;
+    if profile_dir.file_name()? != "debug" {
+        return None;
+    }
+    Some(profile_dir.parent()?.join("release").join(exe.file_name()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::release_binary;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_release_binary() {
+        assert_eq!(
+            release_binary(Path::new("/src/aoc/target/debug/aoc")),
+            Some(PathBuf::from("/src/aoc/target/release/aoc"))
+        );
+        assert_eq!(release_binary(Path::new("/usr/bin/aoc")), None);
+    }
+}
@@ -0,0 +1,35 @@
+//! Cooperative handling of Ctrl-C.
+//!
+//! The first interrupt only sets a flag that long-running loops check between
+//! parts, so that they can stop, report what has been computed so far, and
+//! persist their state. They return normally and `main` exits with
+//! [`EXIT_CODE`] once pending writes have completed. A second interrupt exits
+//! immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ansi_term::Color::Yellow;
+
+/// Exit code conventionally used for termination by SIGINT.
+pub const EXIT_CODE: u8 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+pub fn install_handler() -> anyhow::Result<()> {
+    ctrlc::set_handler(|| {
+        if INTERRUPTED.swap(true, Ordering::SeqCst) {
+            std::process::exit(EXIT_CODE.into());
+        }
+        eprintln!(
+            "\n{}",
+            Yellow.paint(
+                "Interrupted, stopping after the current part. Press Ctrl-C again to abort immediately."
+            )
+        );
+    })?;
+    Ok(())
+}
+
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
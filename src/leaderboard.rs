@@ -0,0 +1,181 @@
+//! Private leaderboards as provided by the AoC JSON API.
+
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use aoc::store::{Namespace, Store};
+
+use crate::aoc_client::{AocClient, HttpTransport};
+
+/// AoC asks to not poll the leaderboard API more often than this.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub owner_id: u64,
+    pub event: String,
+    /// Members by their ID.
+    pub members: BTreeMap<String, Member>,
+}
+
+impl Leaderboard {
+    /// Members ordered by descending local score. Ties are broken by who
+    /// got their last star first.
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<_> = self.members.values().collect();
+        members.sort_by_key(|member| (std::cmp::Reverse(member.local_score), member.last_star_ts));
+        members
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Anonymous users do not have a name.
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    #[serde(default)]
+    pub global_score: u64,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub last_star_ts: DateTime<Utc>,
+    /// Completed parts by day.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u8, StarCompletion>>,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StarCompletion {
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub get_star_ts: DateTime<Utc>,
+    pub star_index: u64,
+}
+
+/// Caches leaderboards for the [`POLL_INTERVAL`] so that repeated
+/// invocations do not exceed the recommended request rate.
+pub struct LeaderboardCache {
+    store: Arc<dyn Store>,
+}
+
+impl LeaderboardCache {
+    pub fn new(store: Arc<dyn Store>) -> Self {
+        Self { store }
+    }
+
+    pub async fn get<T: HttpTransport>(
+        &self,
+        client: &AocClient<T>,
+        year: i32,
+        owner_id: u64,
+    ) -> anyhow::Result<Leaderboard> {
+        let key = format!("{year}-{owner_id}.json");
+        if let Some(leaderboard) = self.read_fresh(&key)? {
+            debug!(key, "leaderboard cache hit");
+            return Ok(leaderboard);
+        }
+
+        info!(year, owner_id, "leaderboard cache miss, fetching");
+        let leaderboard = client.get_private_leaderboard(year, owner_id).await?;
+        self.store.put(
+            Namespace::Leaderboards,
+            &key,
+            serde_json::to_string(&leaderboard)?.as_bytes(),
+        )?;
+        Ok(leaderboard)
+    }
+
+    fn read_fresh(&self, key: &str) -> anyhow::Result<Option<Leaderboard>> {
+        let Some(entry) = self.store.get(Namespace::Leaderboards, key)? else {
+            return Ok(None);
+        };
+        if entry.modified.elapsed().unwrap_or_default() >= POLL_INTERVAL {
+            return Ok(None);
+        }
+        // A corrupt entry is simply fetched again.
+        Ok(serde_json::from_slice(&entry.content).ok())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Leaderboard, LeaderboardCache};
+    use crate::aoc_client::{testing::MockTransport, AocClient};
+    use aoc::store::FileStore;
+    use reqwest::{StatusCode, Url};
+    use std::sync::Arc;
+
+    const LEADERBOARD: &str = r#"{
+        "owner_id": 1,
+        "event": "2023",
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 10,
+                "global_score": 0, "last_star_ts": 1701500000,
+                "completion_day_level": {
+                    "1": {
+                        "1": {"get_star_ts": 1701410000, "star_index": 5},
+                        "2": {"get_star_ts": 1701420000, "star_index": 9}
+                    },
+                    "2": {"1": {"get_star_ts": 1701500000, "star_index": 20}}
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 3, "local_score": 10,
+                "global_score": 0, "last_star_ts": 1701400000,
+                "completion_day_level": {}
+            },
+            "3": {
+                "id": 3, "name": "carol", "stars": 0, "local_score": 0,
+                "global_score": 0, "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_and_rank() {
+        let leaderboard: Leaderboard = serde_json::from_str(LEADERBOARD).unwrap();
+        let alice = &leaderboard.members["1"];
+        assert_eq!(alice.completion_day_level[&1].len(), 2);
+        assert_eq!(
+            alice.completion_day_level[&2][&1].get_star_ts.to_rfc3339(),
+            "2023-12-02T06:53:20+00:00"
+        );
+
+        let ranking: Vec<_> = leaderboard
+            .ranking()
+            .into_iter()
+            .map(|member| member.display_name())
+            .collect();
+        assert_eq!(ranking, vec!["(anonymous user #2)", "alice", "carol"]);
+    }
+
+    #[tokio::test]
+    async fn test_cache_respects_poll_interval() {
+        let transport = MockTransport::default().with_response(
+            "/2023/leaderboard/private/view/1.json",
+            StatusCode::OK,
+            LEADERBOARD,
+        );
+        let requests = transport.requests();
+        let client =
+            AocClient::with_transport(Url::parse("https://aoc.test/").unwrap(), transport).unwrap();
+        let directory = tempfile::tempdir().unwrap();
+        let cache = LeaderboardCache::new(Arc::new(FileStore::new(directory.path())));
+
+        let fetched = cache.get(&client, 2023, 1).await.unwrap();
+        let cached = cache.get(&client, 2023, 1).await.unwrap();
+        assert_eq!(fetched, cached);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,18 @@
+//! Solvers of the Advent of Code puzzles. The `aoc` command line tool fetches
+//! inputs and runs them, and with the `web` feature they can be compiled to
+//! WebAssembly for the browser front-end in `web/`.
+
+#[cfg(feature = "year2023")]
+#[macro_use]
+extern crate lazy_static;
+
+#[macro_use]
+extern crate solver_dispatch;
+
+pub mod datastructures;
+pub mod solvers;
+#[cfg(feature = "cli")]
+pub mod store;
+pub mod visualization;
+#[cfg(feature = "web")]
+pub mod web;
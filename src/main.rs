@@ -1,29 +1,65 @@
 #[macro_use]
 extern crate lazy_static;
 
-#[macro_use]
-extern crate solver_dispatch;
-
+mod answers;
 mod aoc_client;
+mod artifacts;
+mod bench;
+#[cfg(feature = "browser-cookies")]
+mod browser_cookies;
 mod cache;
-mod datastructures;
+mod counters;
+mod debug_build;
+mod interrupt;
+mod leaderboard;
+mod notification;
+mod output;
+mod progress;
+mod puzzle;
 mod session_id_store;
-mod solvers;
+mod watchdog;
+
+use aoc::{solvers, store, visualization};
 
-use ansi_term::Color::Yellow;
+use ansi_term::Color::{Red, Yellow};
 use ansi_term::Style;
+use answers::{AnswerKey, AnswerStore, Check, GoldenAnswers, Provenance, Resolution};
 use anyhow::Context;
-use aoc_client::AocClient;
+use aoc::store::{Compression, Namespace};
+use aoc_client::{AocClient, ClientOptions, Verdict};
+use artifacts::ArtifactDirectory;
+use bench::Stats;
 use cache::FileCache;
 use chrono::{Datelike, FixedOffset, NaiveDate, Utc};
-use clap::{Args, Parser, Subcommand};
-use dirs::cache_dir;
+use clap::{ArgAction, Args, Parser, Subcommand};
+use counters::format_size;
+use inquire::Select;
+use itertools::Itertools;
 use lazy_init::Lazy;
+use leaderboard::{Leaderboard, LeaderboardCache};
+use output::{
+    BenchReport, BenchResult, CacheEntry, CacheListReport, CacheStatsReport, DayReport, ListReport,
+    NamespaceStats, OutputFormat, PartReport, Report, SolveReport, SolverEntry, VerifyOutcome,
+    VerifyReport, VerifyResult,
+};
+use progress::{DownloadProgress, ProgressBarReporter};
+use puzzle::{PuzzlePage, YearProgress};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use reqwest::Url;
-use session_id_store::SessionIdStore;
-use solvers::Solver;
+use session_id_store::{SessionBackend, SessionIdStore};
+use solvers::{
+    Artifacts, InputError, MaybeSolution, Progress, Solution, Solver, SolverParams, Tag,
+};
+use std::io::BufWriter;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::try_join;
+use tracing::{debug, info, info_span, level_filters::LevelFilter};
+use tracing_flame::{FlameLayer, FlushGuard};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
+use watchdog::Limits;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -32,18 +68,202 @@ struct MainArgs {
     #[command(subcommand)]
     command: Option<Command>,
 
+    /// Output format.
+    #[arg(long = "format", value_enum, global = true, default_value_t)]
+    format: OutputFormat,
+
+    /// Increase logging verbosity (-v for info, -vv for debug, -vvv for
+    /// trace). Logs are written to stderr.
+    #[arg(short = 'v', long = "verbose", action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// Write the time spent in the spans of the runner and the solvers to a
+    /// file in the folded stack format, e.g. to render a flame graph with
+    /// `inferno-flamegraph`.
+    #[arg(long = "trace", value_name = "FILE", global = true)]
+    trace: Option<PathBuf>,
+
+    /// Where to store cached inputs, pages, and answers.
+    #[arg(
+        long = "cache-backend",
+        value_enum,
+        env = store::CACHE_BACKEND_ENV_VAR,
+        global = true,
+        default_value_t
+    )]
+    cache_backend: store::Backend,
+
+    /// Directory of the cache. Defaults to `aoc` in the user's cache
+    /// directory.
+    #[arg(
+        long = "cache-dir",
+        value_name = "PATH",
+        env = store::CACHE_DIR_ENV_VAR,
+        global = true
+    )]
+    cache_dir: Option<PathBuf>,
+
+    /// Named profile with its own session ID and cache, e.g. for a second
+    /// AoC account.
+    #[arg(long = "profile", env = store::PROFILE_ENV_VAR, global = true, value_parser = session_id_store::parse_profile)]
+    profile: Option<String>,
+
+    /// Where to store the session ID.
+    #[arg(long = "session-store", value_enum, global = true, default_value_t)]
+    session_store: SessionBackend,
+
+    /// User-Agent to send to the AoC website. Please include contact
+    /// information as requested by AoC.
+    #[arg(long = "user-agent", global = true, default_value = aoc_client::DEFAULT_USER_AGENT)]
+    user_agent: String,
+
+    /// Proxy for requests to the AoC website. Defaults to the HTTPS_PROXY
+    /// environment variable.
+    #[arg(long = "proxy", value_name = "URL", global = true)]
+    proxy: Option<Url>,
+
+    /// PEM file with additional root certificates to trust. Can be given
+    /// multiple times.
+    #[arg(long = "ca-cert", value_name = "PATH", global = true)]
+    ca_certs: Vec<PathBuf>,
+
+    /// In a debug build, run the release build of this binary instead if it
+    /// exists. The release build is not rebuilt.
+    #[arg(long = "reexec-release", env = "AOC_REEXEC_RELEASE", global = true)]
+    reexec_release: bool,
+
     #[command(flatten)]
-    solve_args: SolveArgs,
+    solve_args: SolveCommandArgs,
 }
 
 #[derive(Subcommand, Debug)]
 enum Command {
     /// Set the session ID for interacting with the AoC API.
-    SetSessionId,
+    SetSessionId(SetSessionIdArgs),
+    /// Show the user the stored session ID belongs to.
+    Whoami,
     /// Solve puzzles.
-    Solve(SolveArgs),
+    Solve(SolveCommandArgs),
+    /// List the implemented solvers with their titles and tags.
+    List(ListArgs),
     /// Create module for a day from template.
     Create(SolveArgs),
+    /// Manage the store of verified answers.
+    #[command(subcommand)]
+    Answers(AnswersCommand),
+    /// Check solvers against the verified answers on the puzzle inputs.
+    Verify(VerifyArgs),
+    /// Measure solving times over repeated runs.
+    Bench(BenchArgs),
+    /// Submit an answer to the AoC website.
+    Submit(SubmitArgs),
+    /// Show a private leaderboard.
+    Leaderboard(LeaderboardArgs),
+    /// Show the stars collected on each day of a year.
+    Calendar(CalendarArgs),
+    /// Inspect and clean up the cache.
+    #[command(subcommand)]
+    Cache(CacheCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// Show the number of entries and their total size per namespace.
+    Stats,
+    /// List the cached entries.
+    List(CacheListArgs),
+    /// Delete entries that were not updated for some time.
+    Prune(PruneArgs),
+    /// Delete all entries.
+    Clear(ClearArgs),
+    /// Write entries to a zstd compressed tar bundle, e.g. to move them to
+    /// another machine.
+    Export(ExportArgs),
+    /// Add the entries of a bundle created with `cache export`.
+    Import(ImportArgs),
+}
+
+#[derive(Args, Clone, Debug)]
+struct CacheListArgs {
+    /// Only list entries of this namespace.
+    #[arg(short = 'n', long = "namespace", value_enum)]
+    namespace: Option<Namespace>,
+}
+
+#[derive(Args, Clone, Debug)]
+struct PruneArgs {
+    /// Delete entries older than this, e.g. "30d" or "1y".
+    #[arg(long = "older-than", value_parser = humantime::parse_duration)]
+    older_than: Duration,
+
+    /// Namespaces to prune. Defaults to all but the verified answers, which
+    /// cannot be fetched again.
+    #[arg(short = 'n', long = "namespace", value_enum)]
+    namespaces: Vec<Namespace>,
+}
+
+#[derive(Args, Clone, Debug)]
+struct ExportArgs {
+    /// Path of the bundle to write, e.g. "bundle.tar.zst".
+    path: PathBuf,
+
+    /// Namespaces to export. Defaults to inputs, puzzle pages, and answers.
+    #[arg(short = 'n', long = "namespace", value_enum)]
+    namespaces: Vec<Namespace>,
+}
+
+#[derive(Args, Clone, Debug)]
+struct ImportArgs {
+    /// Path of the bundle to import.
+    path: PathBuf,
+
+    /// Replace existing entries instead of keeping them.
+    #[arg(long = "overwrite")]
+    overwrite: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+struct ClearArgs {
+    /// Namespaces to clear. Defaults to all but the verified answers, which
+    /// cannot be fetched again.
+    #[arg(short = 'n', long = "namespace", value_enum)]
+    namespaces: Vec<Namespace>,
+}
+
+#[derive(Subcommand, Debug)]
+enum AnswersCommand {
+    /// Resolve conflicts between verified answers and newly computed ones.
+    Resolve(ResolveArgs),
+    /// Manually record a known correct answer, e.g. one accepted on the
+    /// website.
+    Record(RecordArgs),
+}
+
+#[derive(Args, Clone, Debug)]
+struct SetSessionIdArgs {
+    /// Read the session ID from stdin instead of asking for it.
+    #[arg(long = "from-stdin")]
+    from_stdin: bool,
+
+    /// Session ID to store instead of asking for it. Note that it is visible
+    /// to other users in the process list; prefer --from-stdin.
+    #[arg(long = "value", value_name = "COOKIE", conflicts_with = "from_stdin")]
+    value: Option<String>,
+
+    /// Read the session cookie from the cookie store of a browser where you
+    /// are logged into adventofcode.com.
+    #[cfg(feature = "browser-cookies")]
+    #[arg(long = "from-browser", value_enum, conflicts_with_all = ["from_stdin", "value"])]
+    from_browser: Option<browser_cookies::Browser>,
+
+    /// Check that the session ID is valid before storing it.
+    #[arg(long = "validate")]
+    validate: bool,
+
+    /// Store the session ID in a file encrypted with a passphrase instead of
+    /// the credential store.
+    #[arg(long = "encrypt")]
+    encrypt: bool,
 }
 
 #[derive(Args, Clone, Debug)]
@@ -60,6 +280,220 @@ struct SolveArgs {
     year: Option<i32>,
 }
 
+#[derive(Args, Clone, Debug)]
+struct SolveCommandArgs {
+    #[command(flatten)]
+    solve_args: SolveArgs,
+
+    /// Record the computed answers as verified for all parts that do not
+    /// have a verified answer yet.
+    #[arg(long = "record", conflicts_with = "example")]
+    record: bool,
+
+    /// Solve the embedded example with the given label (e.g. "1" for
+    /// `day<DD>-1.example`) instead of the puzzle input.
+    #[arg(short = 'e', long = "example")]
+    example: Option<String>,
+
+    /// Send a desktop notification when solving a part takes longer than the
+    /// given number of seconds.
+    #[arg(long = "notify-after", value_name = "SECONDS")]
+    notify_after: Option<f64>,
+
+    /// Skip parts that already have an accepted answer on the website.
+    #[arg(long = "skip-solved", conflicts_with = "example")]
+    skip_solved: bool,
+
+    /// Override a constant the solver takes from the puzzle text, e.g.
+    /// "steps=6". Can be given multiple times. The answers are not checked
+    /// against the verified answers.
+    #[arg(
+        long = "solver-arg",
+        value_name = "KEY=VALUE",
+        value_parser = solvers::parse_solver_arg,
+        conflicts_with = "record"
+    )]
+    solver_args: Vec<(String, String)>,
+
+    /// Show visualizations of solvers supporting them after solving a day.
+    /// If a directory is given, they are written to it as text, PNG, and
+    /// animated GIF files instead.
+    #[arg(
+        long = "visualize",
+        value_name = "DIR",
+        num_args = 0..=1,
+        require_equals = true
+    )]
+    visualize: Option<Option<PathBuf>>,
+
+    /// Directory to write files emitted by solvers to, e.g. graphs of the
+    /// input, in a subdirectory per day.
+    #[arg(long = "artifact-dir", value_name = "DIR")]
+    artifact_dir: Option<PathBuf>,
+
+    /// Use an alternative algorithm registered for the day instead of the
+    /// default solver. `aoc list` shows the available algorithms.
+    #[arg(long = "algo", value_name = "NAME")]
+    algo: Option<String>,
+
+    /// Seed for solvers making random or heuristic choices. Runs with the
+    /// same seed are reproducible.
+    #[arg(long = "seed", value_name = "N", default_value_t = solvers::DEFAULT_SEED)]
+    seed: u64,
+}
+
+#[derive(Args, Clone, Debug)]
+struct ResolveArgs {
+    /// Accept all new answers without prompting.
+    #[arg(long = "accept", conflicts_with = "keep")]
+    accept: bool,
+
+    /// Keep all verified answers without prompting.
+    #[arg(long = "keep")]
+    keep: bool,
+}
+
+#[derive(Args, Clone, Debug)]
+struct RecordArgs {
+    /// Year of the puzzle.
+    #[arg(short = 'y', long = "year")]
+    year: i32,
+
+    /// Day of the puzzle.
+    #[arg(short = 'd', long = "day")]
+    day: u32,
+
+    /// Part of the puzzle.
+    #[arg(short = 'p', long = "part", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// The correct answer.
+    answer: String,
+}
+
+#[derive(Args, Clone, Debug)]
+struct SubmitArgs {
+    /// Year of the puzzle.
+    #[arg(short = 'y', long = "year")]
+    year: i32,
+
+    /// Day of the puzzle.
+    #[arg(short = 'd', long = "day")]
+    day: u32,
+
+    /// Part of the puzzle. Defaults to the first part without an accepted
+    /// answer.
+    #[arg(short = 'p', long = "part", value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Answer to submit. Defaults to the answer computed by the solver.
+    answer: Option<String>,
+}
+
+#[derive(Args, Clone, Debug)]
+struct LeaderboardArgs {
+    /// ID of the leaderboard owner, as shown in the leaderboard URL.
+    owner_id: u64,
+
+    /// Year of the leaderboard. Defaults to the current year.
+    #[arg(short = 'y', long = "year")]
+    year: Option<i32>,
+}
+
+#[derive(Args, Clone, Debug)]
+struct ListArgs {
+    /// Only list solvers of this year.
+    #[arg(short = 'y', long = "year")]
+    year: Option<i32>,
+
+    /// Only list solvers with this tag. Can be given multiple times to
+    /// require all of the tags.
+    #[arg(short = 't', long = "tag", value_enum)]
+    tags: Vec<Tag>,
+}
+
+#[derive(Args, Clone, Debug)]
+struct CalendarArgs {
+    /// Year of the calendar. Defaults to the current year.
+    #[arg(short = 'y', long = "year")]
+    year: Option<i32>,
+}
+
+#[derive(Args, Clone, Debug)]
+struct VerifyArgs {
+    /// Year to verify. Defaults to the current year.
+    #[arg(short = 'y', long = "year", conflicts_with = "all_years")]
+    year: Option<i32>,
+
+    /// Days to verify. Defaults to all days with a solver.
+    #[arg(short = 'd', long = "days", conflicts_with = "all_years")]
+    days: Option<Vec<u32>>,
+
+    /// Verify all days of all years with a solver.
+    #[arg(long = "all-years")]
+    all_years: bool,
+
+    /// Write a JUnit XML report to the given file.
+    #[arg(long = "report", value_name = "FILE")]
+    report: Option<PathBuf>,
+
+    /// Fail a part if it takes longer than the given number of seconds. The
+    /// solver is not stopped, but keeps running in the background until it
+    /// finishes or verification ends.
+    #[arg(long = "timeout", value_name = "SECONDS")]
+    timeout: Option<f64>,
+
+    /// Reject inputs larger than the given number of bytes.
+    #[arg(long = "max-input-bytes", value_name = "BYTES")]
+    max_input_bytes: Option<usize>,
+
+    /// Fail a part if its heap usage exceeds the given number of bytes. Like
+    /// with --timeout, the solver is not stopped. Requires the
+    /// `count-allocations` feature.
+    #[arg(long = "max-memory-bytes", value_name = "BYTES")]
+    max_memory_bytes: Option<u64>,
+
+    /// Number of parts to verify in parallel. Defaults to the number of CPUs.
+    /// Timings of parts verified in parallel include contention between them.
+    #[arg(short = 'j', long = "threads", value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+
+    /// Check against the answers in `DIR/year<YYYY>/answers.toml` instead of
+    /// the recorded answers, e.g. `--golden src/solvers`.
+    #[arg(long = "golden", value_name = "DIR")]
+    golden: Option<PathBuf>,
+}
+
+#[derive(Args, Clone, Debug)]
+struct BenchArgs {
+    #[command(flatten)]
+    solve_args: SolveArgs,
+
+    /// Number of unmeasured runs per part before measuring.
+    #[arg(long = "warmup", value_name = "N", default_value_t = 3)]
+    warmup: usize,
+
+    /// Number of measured runs per part.
+    #[arg(long = "runs", value_name = "N", default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+    runs: u64,
+
+    /// Additionally report allocations and the peak heap usage (requires the
+    /// `count-allocations` feature), instructions, branch misses, and cache
+    /// misses of one extra run per part. Hardware counters are only available on Linux.
+    #[arg(long = "counters")]
+    counters: bool,
+
+    /// Use an alternative algorithm registered for the day instead of the
+    /// default solver. `aoc list` shows the available algorithms.
+    #[arg(long = "algo", value_name = "NAME")]
+    algo: Option<String>,
+
+    /// Seed for solvers making random or heuristic choices. Runs with the
+    /// same seed are reproducible.
+    #[arg(long = "seed", value_name = "N", default_value_t = solvers::DEFAULT_SEED)]
+    seed: u64,
+}
+
 struct RequestedDays {
     pub year: i32,
     pub days: Vec<u32>,
@@ -96,10 +530,46 @@ impl cache::Key for InputKey {
     type Serialization = String;
 
     fn serialize(&self) -> Self::Serialization {
-        format!("{:04}-{:02}", self.year, self.day)
+        store::input_key(self.year, self.day)
+    }
+
+    fn describe(&self) -> String {
+        format!("{} day {}", self.year, self.day)
     }
 }
 
+/// Sets up logging and, if a trace file is given, recording spans to it.
+/// The returned guard must be kept until the end of `main` to write the
+/// trace.
+fn init_logging(
+    verbosity: u8,
+    trace: Option<&Path>,
+) -> anyhow::Result<Option<FlushGuard<BufWriter<std::fs::File>>>> {
+    let level = match verbosity {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+    let (flame_layer, guard) = match trace {
+        Some(path) => {
+            let (layer, guard) = FlameLayer::with_file(path)
+                .with_context(|| format!("creating trace file {}", path.display()))?;
+            (Some(layer.with_threads_collapsed(true)), Some(guard))
+        }
+        None => (None, None),
+    };
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_filter(level),
+        )
+        .with(flame_layer)
+        .init();
+    Ok(guard)
+}
+
 fn get_current_aoc_date() -> NaiveDate {
     Utc::now()
         .with_timezone(&FixedOffset::west_opt(5 * 60 * 60).unwrap())
@@ -132,9 +602,7 @@ async fn add_module_declaration(path: impl AsRef<Path>, days_to_add: &[u32]) ->
                 days_to_add
                     .iter()
                     .map(|day| format!("    pub mod day{day};"))
-                    .chain(std::iter::once(format!(
-                        "    {MODULE_DECLARATION_MARKER}"
-                    )))
+                    .chain(std::iter::once(format!("    {MODULE_DECLARATION_MARKER}")))
                     .collect::<Vec<_>>()
                     .join("\n")
             } else {
@@ -147,57 +615,492 @@ async fn add_module_declaration(path: impl AsRef<Path>, days_to_add: &[u32]) ->
     Ok(())
 }
 
+fn print_conflict_warning(answer: &str, verified: &answers::RecordedAnswer, input_hash: &str) {
+    eprintln!(
+        "{} {}",
+        Yellow.bold().paint("Warning:"),
+        Yellow.paint(format!(
+            "answer {} differs from verified answer {} ({})",
+            answer, verified.answer, verified.provenance
+        ))
+    );
+    if verified.provenance.input_hash != input_hash {
+        eprintln!(
+            "{}",
+            Yellow.paint("         The input changed since the answer was verified.")
+        );
+    }
+    eprintln!(
+        "{}",
+        Yellow.paint("         Run `aoc answers resolve` to accept or discard the new answer.")
+    );
+}
+
+/// Solves a part, passing the answer to part 1 on to part 2 if available.
+fn solve_part(
+    solver: &dyn Solver,
+    part: u8,
+    part_1: Option<&Solution>,
+) -> anyhow::Result<MaybeSolution> {
+    let _span = match part {
+        1 => info_span!("solve_part_1"),
+        _ => info_span!("solve_part_2"),
+    }
+    .entered();
+    match (part, part_1) {
+        (1, _) => solver.solve_part_1().map(Some),
+        (2, Some(part_1)) => solver.solve_part_2_with(part_1),
+        (2, None) => solver.solve_part_2(),
+        _ => Err(anyhow::anyhow!("invalid part {part}")),
+    }
+}
+
+/// Creates the solver for a day, or the alternative algorithm `algo` for it. If
+/// the input is malformed, the offending line is printed to stderr.
+fn create_solver<'input>(
+    year: i32,
+    day: u32,
+    algo: Option<&str>,
+    input: &'input [u8],
+) -> anyhow::Result<Box<dyn Solver<'input> + 'input>> {
+    let _span = info_span!("new", year, day, algo).entered();
+    solvers::new_algo_solver(year, day, algo, input).inspect_err(|err| {
+        if let Some(err) = err.downcast_ref::<InputError>() {
+            eprint!(
+                "{}",
+                output::text::input_error(err, &String::from_utf8_lossy(input))
+            );
+        }
+    })
+}
+
+fn verify_part(
+    limits: &Limits,
+    input: &anyhow::Result<Arc<[u8]>>,
+    year: i32,
+    day: u32,
+    part: u8,
+    expected: &str,
+) -> VerifyOutcome {
+    let input = match input {
+        Ok(input) => solvers::normalize_input(year, day, input.to_vec()),
+        Err(err) => {
+            return VerifyOutcome::Error {
+                message: format!("{err:#}"),
+            }
+        }
+    };
+    let answer = watchdog::run(limits, input, move |input| {
+        let mut solver = solvers::new_solver_from_bytes(year, day, input)?;
+        solver.precompute()?;
+        Ok(solve_part(solver.as_ref(), part, None)?
+            .ok_or_else(|| anyhow::anyhow!("part {part} has no solution"))?
+            .answer_str()
+            .to_string())
+    });
+    match answer {
+        Ok(answer) if answer == expected => VerifyOutcome::Pass { answer },
+        Ok(answer) => VerifyOutcome::Fail {
+            expected: expected.into(),
+            actual: answer,
+        },
+        Err(err) => VerifyOutcome::Error {
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Selected namespaces, or all that can be fetched again if none are.
+fn namespaces_or_refetchable(namespaces: Vec<Namespace>) -> Vec<Namespace> {
+    if !namespaces.is_empty() {
+        return namespaces;
+    }
+    Namespace::ALL
+        .into_iter()
+        .filter(|namespace| namespace.is_refetchable())
+        .collect()
+}
+
+fn print_removed(removed: &[(Namespace, store::EntryInfo)]) {
+    let bytes = removed.iter().map(|(_, info)| info.size).sum();
+    println!(
+        "🧹 Removed {} entries ({})",
+        removed.len(),
+        format_size(bytes)
+    );
+}
+
+fn print_year_progress(progress: &YearProgress) {
+    println!(
+        "📆 {} {}⭐",
+        Style::new()
+            .underline()
+            .paint(format!("Calendar {}", progress.year)),
+        progress.total_stars()
+    );
+    for (day, &stars) in &progress.stars {
+        println!("{day:>4}) {}", "⭐".repeat(stars.into()));
+    }
+}
+
+fn print_leaderboard(leaderboard: &Leaderboard) {
+    println!(
+        "🏆 {}",
+        Style::new().underline().paint(format!(
+            "Private leaderboard {} of #{}",
+            leaderboard.event, leaderboard.owner_id
+        ))
+    );
+    for (rank, member) in (1..).zip(leaderboard.ranking()) {
+        println!(
+            "{rank:>4}) {:>5} {:>3}⭐ {}",
+            member.local_score,
+            member.stars,
+            member.display_name()
+        );
+    }
+}
+
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
     let args = MainArgs::parse();
-    let session_id_store = SessionIdStore::new()?;
+    if matches!(
+        args.command,
+        None | Some(Command::Solve(_) | Command::Verify(_) | Command::Bench(_))
+    ) {
+        debug_build::check(args.reexec_release)?;
+    }
+    let _trace_guard = init_logging(args.verbose, args.trace.as_deref())?;
+    interrupt::install_handler()?;
+    let session_id_store = SessionIdStore::new(args.session_store, args.profile.as_deref())?;
+
+    let client_options = ClientOptions {
+        user_agent: args.user_agent,
+        proxy: args.proxy,
+        root_certificates: args.ca_certs,
+    };
+    let client: Lazy<AocClient> = Lazy::new();
+    let create_client = || {
+        debug!("creating AoC client");
+        AocClient::new(
+            Url::parse(aoc_client::BASE_URL)
+                .context("client base URL")
+                .expect("cannot create HTTP client"),
+            &session_id_store.session_id().expect("missing session ID"),
+            &client_options,
+        )
+        .expect("cannot create AoC client")
+    };
+    // Only commands that need them open the store and set up the caches.
+    let client = &client;
+    let open_store = || {
+        args.cache_backend.open(&store::root(
+            args.cache_dir.clone(),
+            args.profile.as_deref(),
+        ))
+    };
+    let download_progress = DownloadProgress::default();
+    let new_input_cache = |store| {
+        FileCache::new(store, Namespace::Inputs, move |key: InputKey| {
+            let client = client.get_or_create(create_client);
+            async move { client.get_input(key.year, key.day).await }
+        })
+        .with_progress(download_progress.clone(), "input")
+        .with_sanitizer(aoc_client::sanitize_input)
+    };
+    let new_page_cache = |store| {
+        FileCache::new(store, Namespace::Pages, move |key: InputKey| {
+            let client = client.get_or_create(create_client);
+            async move { client.get_puzzle_page(key.year, key.day).await }
+        })
+        .with_progress(download_progress.clone(), "puzzle page")
+        .with_compression(Compression::Gzip)
+        // Pages change when a star is earned, possibly without using this tool.
+        .with_expiry(|html| {
+            (!PuzzlePage::from_html(&String::from_utf8_lossy(html)).is_solved(2))
+                .then_some(Duration::from_secs(24 * 60 * 60))
+        })
+    };
 
     let command = args.command.unwrap_or(Command::Solve(args.solve_args));
     match command {
-        Command::SetSessionId => {
-            session_id_store.prompt()?;
+        Command::SetSessionId(SetSessionIdArgs {
+            from_stdin,
+            value,
+            #[cfg(feature = "browser-cookies")]
+            from_browser,
+            validate,
+            encrypt,
+        }) => {
+            #[cfg(feature = "browser-cookies")]
+            let value = match from_browser {
+                Some(browser) => Some(
+                    secrecy::ExposeSecret::expose_secret(&browser_cookies::session_cookie(
+                        browser,
+                    )?)
+                    .clone(),
+                ),
+                None => value,
+            };
+            let session_id = match value {
+                Some(value) => SessionIdStore::read_from(value.as_bytes())?,
+                None if from_stdin => SessionIdStore::read_from(std::io::stdin().lock())?,
+                None => SessionIdStore::read_from_prompt()?,
+            };
+            if validate {
+                let client = AocClient::new(
+                    Url::parse(aoc_client::BASE_URL)?,
+                    &session_id,
+                    &client_options,
+                )?;
+                println!("✅ Logged in as {}", client.whoami().await?);
+            }
+            if encrypt {
+                SessionIdStore::new(args.session_store, args.profile.as_deref())?
+                    .with_passphrase(SessionIdStore::read_passphrase_from_prompt()?)
+                    .set(&session_id)?;
+            } else {
+                session_id_store.set(&session_id)?;
+            }
+            if session_id_store::session_id_from_env().is_some() {
+                println!(
+                    "{}",
+                    Yellow.paint(format!(
+                        "{} is set and takes precedence over the stored session ID",
+                        session_id_store::SESSION_ENV_VAR
+                    ))
+                );
+            }
         }
-        Command::Solve(solve_args) => {
+        Command::Whoami => {
+            let username = client.get_or_create(create_client).whoami().await?;
+            println!("Logged in as {username}");
+        }
+        Command::Solve(SolveCommandArgs {
+            solve_args,
+            record,
+            example,
+            notify_after,
+            skip_solved,
+            solver_args,
+            visualize,
+            artifact_dir,
+            algo,
+            seed,
+        }) => {
+            let store = open_store()?;
+            let input_cache = new_input_cache(store.clone());
+            let page_cache = new_page_cache(store.clone());
             let RequestedDays { year, days } = solve_args.into();
+            debug!(year, ?days, "solving requested days");
+            let mut answer_store = AnswerStore::load(store.clone())?;
 
-            let client: Lazy<AocClient> = Lazy::new();
-            let create_client = || {
-                AocClient::new(
-                    Url::parse("https://adventofcode.com/")
-                        .context("client base URL")
-                        .expect("cannot create HTTP client"),
-                    session_id_store.session_id().expect("missing session ID"),
-                )
-                .expect("cannot create AoC client")
-            };
-            let cache_path = cache_dir().map_or_else(
-                || {
-                    eprintln!("Warning: couldn't locate cache directory, using ./aoc-cache");
-                    "./aoc-cache".into()
-                },
-                |cache_base| cache_base.join("aoc"),
-            );
-            let input_cache = FileCache::new(cache_path, |key: InputKey| {
-                let client = client.get_or_create(create_client);
-                async move { client.get_input(key.year, key.day).await }
-            })
-            .await?;
+            if example.is_none() {
+                input_cache
+                    .prefetch(
+                        days.iter().map(|&day| InputKey::from_yd(year, day)),
+                        aoc_client::MAX_CONCURRENT_REQUESTS,
+                    )
+                    .await;
+            }
 
+            let mut report = SolveReport::default();
             for &day in days.iter() {
-                println!();
-                println!(
-                    "📆 {}",
-                    Style::new().underline().paint(format!(
-                        "{}, day {}",
-                        year,
-                        Style::new().bold().paint(day.to_string())
-                    ))
-                );
+                if interrupt::interrupted() {
+                    break;
+                }
+                let page_key = InputKey::from_yd(year, day);
+                let info = solvers::solver_info(year, day);
+                let solver_title = info.and_then(|info| info.title);
+                // The page is only needed to find solved parts and for titles
+                // the solver does not know.
+                let puzzle_page = if skip_solved {
+                    Some(page_cache.get(&page_key).await?)
+                } else if solver_title.is_some() {
+                    None
+                } else if example.is_some() {
+                    // Examples are solved offline, so only use a page fetched
+                    // earlier.
+                    page_cache.get_cached(&page_key).await
+                } else {
+                    match page_cache.get(&page_key).await {
+                        Ok(html) => Some(html),
+                        Err(err) => {
+                            eprintln!(
+                                "{} {}",
+                                Yellow.bold().paint("Warning:"),
+                                Yellow.paint(format!("cannot fetch puzzle title: {err:#}"))
+                            );
+                            None
+                        }
+                    }
+                }
+                .map(|html| PuzzlePage::from_html(&String::from_utf8_lossy(&html)));
+                let title = solver_title
+                    .map(String::from)
+                    .or_else(|| puzzle_page.as_ref().and_then(|page| page.title.clone()));
+
+                if args.format == OutputFormat::Text {
+                    println!();
+                    print!("{}", output::text::day_header(year, day, title.as_deref()));
+                }
+
+                let embedded_example = example
+                    .as_ref()
+                    .map(|label| {
+                        solvers::find_example(year, day, label).ok_or_else(|| {
+                            anyhow::anyhow!("no example '{label}' for day {day} of year {year}")
+                        })
+                    })
+                    .transpose()?;
+                let expected = embedded_example
+                    .map(|example| example.expected_answers())
+                    .transpose()?
+                    .flatten();
+                let raw_input = match embedded_example {
+                    Some(example) => example.input.as_bytes().to_vec(),
+                    None => input_cache
+                        .get(&InputKey::from_yd(year, day))
+                        .await?
+                        .to_vec(),
+                };
+                let input = solvers::normalize_input(year, day, raw_input.clone());
+                info!(year, day, input_len = input.len(), "creating solver");
+                let mut solver = create_solver(year, day, algo.as_deref(), &input)?;
+                solver.set_seed(seed);
+                if args.format == OutputFormat::Text {
+                    solver.set_progress(Progress::new(Arc::new(ProgressBarReporter::default())));
+                }
+                if let Some(artifact_dir) = &artifact_dir {
+                    let directory = artifact_dir.join(format!("{year}-{day:02}"));
+                    solver
+                        .set_artifacts(Artifacts::new(Arc::new(ArtifactDirectory::new(directory))));
+                }
+                // Solver arguments given on the command line take precedence over
+                // the ones needed by the example.
+                let params: SolverParams = expected
+                    .iter()
+                    .flat_map(|expected| &expected.params)
+                    .map(|(key, value)| (key.clone(), value.to_string()))
+                    .chain(solver_args.iter().cloned())
+                    .collect();
+                solver.set_params(&params)?;
+                params
+                    .check_all_used()
+                    .with_context(|| format!("solving day {day} of year {year}"))?;
+                let mut day_report = DayReport {
+                    year,
+                    day,
+                    title,
+                    tags: info.map_or_else(Vec::new, |info| info.tags.to_vec()),
+                    parts: Vec::with_capacity(2),
+                };
+                let mut precomputed = false;
+                let mut part_1 = None;
+                for part in [1, 2] {
+                    if interrupt::interrupted() {
+                        break;
+                    }
+                    if skip_solved
+                        && puzzle_page
+                            .as_ref()
+                            .is_some_and(|page| page.is_solved(part))
+                    {
+                        if args.format == OutputFormat::Text {
+                            print!("{}", output::text::part_skipped(part));
+                        }
+                        continue;
+                    }
+                    // Shared work is only done if a part is solved and counts
+                    // towards the time of the first solved part.
+                    let start = Instant::now();
+                    if !precomputed {
+                        info_span!("precompute").in_scope(|| solver.precompute())?;
+                        precomputed = true;
+                    }
+                    let (solution, peak_heap_bytes) = counters::measure_peak_heap(|| {
+                        solve_part(solver.as_ref(), part, part_1.as_ref())
+                    });
+                    let solution = solution?;
+                    let elapsed = start.elapsed();
+                    let Some(solution) = solution else {
+                        if args.format == OutputFormat::Text {
+                            print!("{}", output::text::part_not_available(part));
+                        }
+                        continue;
+                    };
+                    info!(year, day, part, ?elapsed, ?peak_heap_bytes, "solved part");
+                    let answer = solution.answer_str();
+                    if notify_after.is_some_and(|threshold| elapsed.as_secs_f64() > threshold) {
+                        notification::notify_solved(year, day, part, &answer, elapsed);
+                    }
+                    if args.format == OutputFormat::Text {
+                        print!(
+                            "{}",
+                            output::text::part_solution(
+                                &solution,
+                                expected.as_ref().and_then(|expected| expected.part(part))
+                            )
+                        );
+                    }
+
+                    // The answer store tracks answers to puzzle inputs with
+                    // the constants from the puzzle text only.
+                    if example.is_none() && params.is_empty() {
+                        let key = AnswerKey { year, day, part };
+                        let provenance = Provenance::for_input(&raw_input);
+                        let input_hash = provenance.input_hash.clone();
+                        match answer_store.check(key, &answer, provenance.clone()) {
+                            Check::Conflict { verified } => {
+                                print_conflict_warning(&answer, verified, &input_hash)
+                            }
+                            Check::Unverified if record => {
+                                answer_store.record(key, &answer, provenance);
+                            }
+                            Check::Unverified | Check::Correct => {}
+                        }
+                    }
+
+                    day_report.parts.push(PartReport {
+                        part,
+                        description: solution.description().into(),
+                        answer: answer.into_owned(),
+                        elapsed_seconds: elapsed.as_secs_f64(),
+                        peak_heap_bytes,
+                    });
+                    if part == 1 {
+                        part_1 = Some(solution);
+                    }
+                }
+                if let Some(directory) = &visualize {
+                    let frames = solver.visualize()?;
+                    match directory {
+                        Some(directory) => {
+                            let name = format!("{year}-{day:02}");
+                            for path in visualization::write_frames(directory, &name, &frames)? {
+                                if args.format == OutputFormat::Text {
+                                    println!("🖼️  Wrote visualization to {}", path.display());
+                                }
+                            }
+                        }
+                        None if args.format == OutputFormat::Text => {
+                            visualization::print_frames(&frames)
+                        }
+                        None => {}
+                    }
+                }
+                report.days.push(day_report);
+            }
+
+            answer_store.save()?;
 
-                let input = input_cache.get(&InputKey::from_yd(year, day)).await?;
-                let solver: Box<dyn Solver> = solver_dispatch!(input, year, day)?;
-                println!("⭐ {}", solver.solve_part_1()?);
-                println!("⭐ {}", solver.solve_part_2()?);
+            match args.format {
+                OutputFormat::Text if report.days.len() > 1 || interrupt::interrupted() => {
+                    println!();
+                    print!("{}", output::timing::render(&report.days));
+                }
+                OutputFormat::Text => {}
+                OutputFormat::Json => output::print_json(Report::Solve(report))?,
             }
         }
         Command::Create(solve_args) => {
@@ -212,17 +1115,565 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             for day in &days {
                 let day_path = base_path.join(format!("day{day}.rs"));
                 let example_path = base_path.join(format!("day{day}-1.example"));
+                let expected_path = example_path.with_extension("expected");
                 let source_content = TEMPLATE.replace("{{day}}", &day.to_string());
                 try_join!(
                     write_if_non_existent(day_path, &source_content),
                     write_if_non_existent(example_path, ""),
+                    write_if_non_existent(expected_path, "part1 = \"TODO\"\npart2 = \"TODO\"\n"),
                 )?;
             }
             add_module_declaration("src/solvers/mod.rs", &days).await?;
         }
+        Command::Verify(VerifyArgs {
+            year,
+            days,
+            all_years,
+            report: report_path,
+            timeout,
+            max_input_bytes,
+            max_memory_bytes,
+            threads,
+            golden,
+        }) => {
+            let store = open_store()?;
+            let input_cache = new_input_cache(store.clone());
+            if max_memory_bytes.is_some() && !counters::HEAP_BUDGETS_SUPPORTED {
+                return Err(anyhow::anyhow!(
+                    "--max-memory-bytes requires the count-allocations feature"
+                )
+                .into());
+            }
+            let limits = Limits {
+                max_input_bytes,
+                time_budget: timeout.map(Duration::from_secs_f64),
+                max_memory_bytes,
+            };
+            let answer_store = AnswerStore::load(store.clone())?;
+            let year = year.unwrap_or_else(|| get_current_aoc_date().year());
+            let days_to_verify: Vec<_> = solvers::solver_days()
+                .into_iter()
+                .filter(|&(y, d)| {
+                    all_years || (y == year && days.as_ref().is_none_or(|days| days.contains(&d)))
+                })
+                .collect();
+            let golden = golden
+                .map(|dir| -> anyhow::Result<_> {
+                    let mut golden = GoldenAnswers::default();
+                    for year in days_to_verify.iter().map(|&(year, _)| year).dedup() {
+                        golden.extend(GoldenAnswers::load(&dir, year)?);
+                    }
+                    Ok(golden)
+                })
+                .transpose()?;
+            let expected_answer = |key: AnswerKey| match &golden {
+                Some(golden) => golden.get(&key).map(String::from),
+                None => answer_store
+                    .verified(&key)
+                    .map(|verified| verified.answer.clone()),
+            };
+            input_cache
+                .prefetch(
+                    days_to_verify
+                        .iter()
+                        .filter(|&&(year, day)| {
+                            [1, 2].into_iter().any(|part| {
+                                expected_answer(AnswerKey { year, day, part }).is_some()
+                            })
+                        })
+                        .map(|&(year, day)| InputKey::from_yd(year, day)),
+                    aoc_client::MAX_CONCURRENT_REQUESTS,
+                )
+                .await;
+
+            let mut jobs = Vec::new();
+            for (year, day) in days_to_verify {
+                if interrupt::interrupted() {
+                    break;
+                }
+                let expected_answers =
+                    [1, 2].map(|part| expected_answer(AnswerKey { year, day, part }));
+                // Avoid fetching inputs that cannot be checked against anything.
+                let input = Arc::new(if expected_answers.iter().any(Option::is_some) {
+                    input_cache.get(&InputKey::from_yd(year, day)).await
+                } else {
+                    Err(anyhow::anyhow!("no verified answers"))
+                });
+                for (part, expected) in (1..).zip(expected_answers) {
+                    jobs.push((year, day, part, input.clone(), expected));
+                }
+            }
+
+            // Parts are verified on a thread pool and printed as they finish,
+            // but reported in order.
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads.map_or(0, usize::from))
+                .build()?;
+            let concurrency = pool.current_num_threads().min(jobs.len());
+            let results = pool.install(|| {
+                jobs.into_par_iter()
+                    .filter_map(|(year, day, part, input, expected)| {
+                        if interrupt::interrupted() {
+                            return None;
+                        }
+                        debug!(year, day, part, "verifying");
+                        let start = Instant::now();
+                        let outcome = match expected {
+                            Some(expected) => {
+                                verify_part(&limits, &input, year, day, part, &expected)
+                            }
+                            None => VerifyOutcome::Skipped,
+                        };
+                        let result = VerifyResult {
+                            year,
+                            day,
+                            part,
+                            elapsed_seconds: start.elapsed().as_secs_f64(),
+                            outcome,
+                        };
+                        if args.format == OutputFormat::Text {
+                            print!("{}", output::text::verify_result(&result));
+                        }
+                        Some(result)
+                    })
+                    .collect()
+            });
+            let report = VerifyReport {
+                results,
+                concurrency,
+            };
+
+            if let Some(report_path) = report_path {
+                tokio::fs::write(&report_path, output::junit::render(&report))
+                    .await
+                    .with_context(|| format!("writing report {}", report_path.display()))?;
+            }
+            let num_failed = report.num_failed();
+            match args.format {
+                OutputFormat::Text => {
+                    println!("\n{} checked, {} failed", report.results.len(), num_failed);
+                    if report.concurrency > 1 {
+                        println!(
+                            "{}",
+                            Yellow.paint(format!(
+                                "Timings were measured with {} parts verified in parallel and \
+                                 include contention, use --threads 1 to time parts in isolation",
+                                report.concurrency
+                            ))
+                        );
+                    }
+                }
+                OutputFormat::Json => output::print_json(Report::Verify(report))?,
+            }
+            if num_failed > 0 && !interrupt::interrupted() {
+                return Err(anyhow::anyhow!("{num_failed} parts failed verification").into());
+            }
+        }
+        Command::Bench(BenchArgs {
+            solve_args,
+            warmup,
+            runs,
+            counters,
+            algo,
+            seed,
+        }) => {
+            let input_cache = new_input_cache(open_store()?);
+            let RequestedDays { year, days } = solve_args.into();
+            input_cache
+                .prefetch(
+                    days.iter().map(|&day| InputKey::from_yd(year, day)),
+                    aoc_client::MAX_CONCURRENT_REQUESTS,
+                )
+                .await;
+            let mut report = BenchReport::default();
+            'days: for day in days {
+                let input = solvers::normalize_input(
+                    year,
+                    day,
+                    input_cache
+                        .get(&InputKey::from_yd(year, day))
+                        .await?
+                        .to_vec(),
+                );
+                let mut solver = create_solver(year, day, algo.as_deref(), &input)?;
+                solver.set_seed(seed);
+                info_span!("precompute").in_scope(|| solver.precompute())?;
+                let mut part_1 = None;
+                'parts: for part in [1, 2] {
+                    if interrupt::interrupted() {
+                        break 'days;
+                    }
+                    debug!(year, day, part, warmup, runs, "benchmarking");
+                    for _ in 0..warmup {
+                        solve_part(solver.as_ref(), part, part_1.as_ref())?;
+                    }
+                    let mut samples = Vec::with_capacity(runs as usize);
+                    let mut solution = None;
+                    for _ in 0..runs {
+                        let start = Instant::now();
+                        solution = solve_part(solver.as_ref(), part, part_1.as_ref())?;
+                        samples.push(start.elapsed().as_secs_f64());
+                        if solution.is_none() {
+                            continue 'parts;
+                        }
+                    }
+                    let counters = if counters {
+                        let (solution, counters) = counters::measure(|| {
+                            solve_part(solver.as_ref(), part, part_1.as_ref())
+                        });
+                        solution?;
+                        Some(counters)
+                    } else {
+                        None
+                    };
+                    let result = BenchResult {
+                        year,
+                        day,
+                        part,
+                        algo: algo.clone(),
+                        warmup,
+                        stats: Stats::from_samples(&samples).expect("at least one run"),
+                        counters,
+                    };
+                    if args.format == OutputFormat::Text {
+                        print!("{}", output::text::bench_result(&result));
+                    }
+                    report.results.push(result);
+                    if part == 1 {
+                        part_1 = solution;
+                    }
+                }
+            }
+            if args.format == OutputFormat::Json {
+                output::print_json(Report::Bench(report))?;
+            }
+        }
+        Command::Submit(SubmitArgs {
+            year,
+            day,
+            part,
+            answer,
+        }) => {
+            let store = open_store()?;
+            let page_cache = new_page_cache(store.clone());
+            let input_cache = new_input_cache(store.clone());
+            let page_key = InputKey::from_yd(year, day);
+            let part = match part {
+                Some(part) => part,
+                None => {
+                    let html = page_cache.get(&page_key).await?;
+                    let page = PuzzlePage::from_html(&String::from_utf8_lossy(&html));
+                    match page.next_unsolved_part() {
+                        Some(part) => part,
+                        None => {
+                            println!("Both parts of {year}, day {day} are already solved.");
+                            return Ok(ExitCode::SUCCESS);
+                        }
+                    }
+                }
+            };
+            let input = input_cache.get(&InputKey::from_yd(year, day)).await?;
+            let (answer, commit) = match answer {
+                Some(answer) => (answer, None),
+                None => {
+                    let normalized = solvers::normalize_input(year, day, input.to_vec());
+                    let mut solver = create_solver(year, day, None, &normalized)?;
+                    solver.precompute()?;
+                    let solution = solve_part(solver.as_ref(), part, None)?.ok_or_else(|| {
+                        anyhow::anyhow!("{year}, day {day} has no part {part} to solve")
+                    })?;
+                    (
+                        solution.answer_str().into_owned(),
+                        Provenance::for_input(&input).commit,
+                    )
+                }
+            };
+            let verdict = client
+                .get_or_create(create_client)
+                .submit_answer(year, day, part, &answer)
+                .await?;
+            match verdict {
+                Verdict::Correct => {
+                    println!("⭐ {}: {}", Style::new().bold().paint(&answer), verdict)
+                }
+                Verdict::AlreadySolved => println!("{}: {}", answer, verdict),
+                _ => println!("❌ {}: {}", answer, Red.paint(verdict.to_string())),
+            }
+            if verdict == Verdict::Correct {
+                let mut answer_store = AnswerStore::load(store.clone())?;
+                let provenance = Provenance {
+                    commit,
+                    ..Provenance::for_input(&input)
+                };
+                answer_store.record(AnswerKey { year, day, part }, &answer, provenance);
+                answer_store.save()?;
+            }
+            if matches!(verdict, Verdict::Correct | Verdict::AlreadySolved) {
+                // The page now shows the accepted answer.
+                page_cache.invalidate(&page_key).await?;
+            }
+        }
+        Command::Leaderboard(LeaderboardArgs { owner_id, year }) => {
+            let store = open_store()?;
+            let year = year.unwrap_or_else(|| get_current_aoc_date().year());
+            let leaderboard = LeaderboardCache::new(store.clone())
+                .get(client.get_or_create(create_client), year, owner_id)
+                .await?;
+            match args.format {
+                OutputFormat::Text => print_leaderboard(&leaderboard),
+                OutputFormat::Json => output::print_json(Report::Leaderboard(leaderboard))?,
+            }
+        }
+        Command::List(ListArgs { year, tags }) => {
+            let report = ListReport {
+                solvers: solvers::solver_days()
+                    .into_iter()
+                    .filter(|&(solver_year, _)| year.is_none_or(|year| year == solver_year))
+                    .filter_map(|(year, day)| solvers::solver_info(year, day))
+                    .filter(|info| tags.iter().all(|tag| info.tags.contains(tag)))
+                    .map(|info| SolverEntry {
+                        year: info.year,
+                        day: info.day,
+                        title: info.title.map(String::from),
+                        tags: info.tags.to_vec(),
+                        algorithms: solvers::algorithms(info.year, info.day)
+                            .into_iter()
+                            .map(String::from)
+                            .collect(),
+                    })
+                    .collect(),
+            };
+            match args.format {
+                OutputFormat::Text => {
+                    for solver in &report.solvers {
+                        let mut tags = solver
+                            .tags
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        if !solver.algorithms.is_empty() {
+                            tags.push_str(&format!("  [--algo {}]", solver.algorithms.join(", ")));
+                        }
+                        println!(
+                            "{} day {:>2}  {:<40} {}",
+                            solver.year,
+                            solver.day,
+                            solver.title.as_deref().unwrap_or_default(),
+                            Style::new().dimmed().paint(tags)
+                        );
+                    }
+                }
+                OutputFormat::Json => output::print_json(Report::List(report))?,
+            }
+        }
+        Command::Calendar(CalendarArgs { year }) => {
+            let year = year.unwrap_or_else(|| get_current_aoc_date().year());
+            let progress = client
+                .get_or_create(create_client)
+                .get_year_progress(year)
+                .await?;
+            match args.format {
+                OutputFormat::Text => print_year_progress(&progress),
+                OutputFormat::Json => output::print_json(Report::Calendar(progress))?,
+            }
+        }
+        Command::Cache(CacheCommand::Stats) => {
+            let store = open_store()?;
+            let mut report = CacheStatsReport::default();
+            for namespace in Namespace::ALL {
+                let infos = store.list(namespace)?;
+                report.namespaces.push(NamespaceStats {
+                    namespace: namespace.name().into(),
+                    entries: infos.len(),
+                    bytes: infos.iter().map(|info| info.size).sum(),
+                });
+            }
+            match args.format {
+                OutputFormat::Text => {
+                    for stats in &report.namespaces {
+                        println!(
+                            "{:<12} {:>5} entries {:>10}",
+                            stats.namespace,
+                            stats.entries,
+                            format_size(stats.bytes)
+                        );
+                    }
+                    println!(
+                        "{:<12} {:>5} entries {:>10}",
+                        "total",
+                        report.namespaces.iter().map(|s| s.entries).sum::<usize>(),
+                        format_size(report.namespaces.iter().map(|s| s.bytes).sum())
+                    );
+                }
+                OutputFormat::Json => output::print_json(Report::CacheStats(report))?,
+            }
+        }
+        Command::Cache(CacheCommand::List(CacheListArgs { namespace })) => {
+            let store = open_store()?;
+            let namespaces = namespace.map_or_else(|| Namespace::ALL.to_vec(), |n| vec![n]);
+            let mut report = CacheListReport::default();
+            for namespace in namespaces {
+                for info in store.list(namespace)? {
+                    report.entries.push(CacheEntry {
+                        namespace: namespace.name().into(),
+                        key: info.key,
+                        bytes: info.size,
+                        modified: info.modified.into(),
+                    });
+                }
+            }
+            match args.format {
+                OutputFormat::Text => {
+                    for entry in &report.entries {
+                        println!(
+                            "{:<12} {:<20} {:>10}  {}",
+                            entry.namespace,
+                            entry.key,
+                            format_size(entry.bytes),
+                            entry.modified.format("%Y-%m-%d %H:%M")
+                        );
+                    }
+                }
+                OutputFormat::Json => output::print_json(Report::CacheList(report))?,
+            }
+        }
+        Command::Cache(CacheCommand::Prune(PruneArgs {
+            older_than,
+            namespaces,
+        })) => {
+            let store = open_store()?;
+            let cutoff = std::time::SystemTime::now()
+                .checked_sub(older_than)
+                .unwrap_or(std::time::UNIX_EPOCH);
+            let removed = cache::prune(
+                store.as_ref(),
+                &namespaces_or_refetchable(namespaces),
+                Some(cutoff),
+            )?;
+            print_removed(&removed);
+        }
+        Command::Cache(CacheCommand::Clear(ClearArgs { namespaces })) => {
+            let store = open_store()?;
+            let removed =
+                cache::prune(store.as_ref(), &namespaces_or_refetchable(namespaces), None)?;
+            print_removed(&removed);
+        }
+        Command::Cache(CacheCommand::Export(ExportArgs { path, namespaces })) => {
+            let store = open_store()?;
+            let namespaces = if namespaces.is_empty() {
+                vec![Namespace::Inputs, Namespace::Pages, Namespace::Answers]
+            } else {
+                namespaces
+            };
+            let file = std::fs::File::create(&path)
+                .with_context(|| format!("creating bundle {}", path.display()))?;
+            let count = cache::bundle::export(store.as_ref(), &namespaces, file)?;
+            println!("📦 Exported {count} entries to {}", path.display());
+        }
+        Command::Cache(CacheCommand::Import(ImportArgs { path, overwrite })) => {
+            let store = open_store()?;
+            let file = std::fs::File::open(&path)
+                .with_context(|| format!("opening bundle {}", path.display()))?;
+            let summary = cache::bundle::import(&store, file, overwrite)?;
+            println!("📦 Imported {} entries", summary.imported);
+            for key in &summary.conflicts {
+                let action = if overwrite {
+                    "replaced by the imported one"
+                } else {
+                    "kept, resolve with `aoc answers resolve`"
+                };
+                println!(
+                    "{}",
+                    Yellow.paint(format!(
+                        "Imported answer for {key} differs from the verified answer, {action}"
+                    ))
+                );
+            }
+            if summary.skipped > 0 {
+                println!(
+                    "{}",
+                    Yellow.paint(format!(
+                        "Kept {} existing entries, use --overwrite to replace them",
+                        summary.skipped
+                    ))
+                );
+            }
+        }
+        Command::Answers(AnswersCommand::Record(RecordArgs {
+            year,
+            day,
+            part,
+            answer,
+        })) => {
+            let store = open_store()?;
+            let input_cache = new_input_cache(store.clone());
+            let mut answer_store = AnswerStore::load(store.clone())?;
+            let input = input_cache.get(&InputKey::from_yd(year, day)).await?;
+            let key = AnswerKey { year, day, part };
+            // The answer was not computed by a solver, so there is no commit.
+            let provenance = Provenance {
+                commit: None,
+                ..Provenance::for_input(&input)
+            };
+            if !answer_store.record(key, &answer, provenance.clone()) {
+                let input_hash = provenance.input_hash.clone();
+                if let Check::Conflict { verified } = answer_store.check(key, &answer, provenance) {
+                    print_conflict_warning(&answer, verified, &input_hash);
+                }
+            }
+            answer_store.save()?;
+        }
+        Command::Answers(AnswersCommand::Resolve(ResolveArgs { accept, keep })) => {
+            let store = open_store()?;
+            let mut answer_store = AnswerStore::load(store.clone())?;
+            let conflicts: Vec<_> = answer_store
+                .conflicts()
+                .map(|(key, verified, conflict)| (key, verified.clone(), conflict.clone()))
+                .collect();
+            if conflicts.is_empty() {
+                println!("No conflicting answers.");
+            }
+
+            for (key, verified, conflict) in conflicts {
+                let resolution = if accept {
+                    Resolution::AcceptNew
+                } else if keep {
+                    Resolution::KeepOld
+                } else {
+                    let accept_option = format!(
+                        "Accept new answer {} ({})",
+                        conflict.answer, conflict.provenance
+                    );
+                    let keep_option = format!(
+                        "Keep verified answer {} ({})",
+                        verified.answer, verified.provenance
+                    );
+                    let choice = Select::new(
+                        &format!("Conflicting answers for {key}:"),
+                        vec![keep_option.clone(), accept_option],
+                    )
+                    .prompt()
+                    .context("conflict resolution input")?;
+                    if choice == keep_option {
+                        Resolution::KeepOld
+                    } else {
+                        Resolution::AcceptNew
+                    }
+                };
+                answer_store.resolve(key, resolution)?;
+            }
+
+            answer_store.save()?;
+        }
     }
 
-    Ok(())
+    // Commands stop early when interrupted but return normally, so that their
+    // stores are closed and pending writes complete before exiting.
+    if interrupt::interrupted() {
+        return Ok(ExitCode::from(interrupt::EXIT_CODE));
+    }
+    Ok(ExitCode::SUCCESS)
 }
 
 #[cfg(test)]
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use notify_rust::Notification;
+
+/// Sends a desktop notification about a solved part. Failing to deliver the
+/// notification is not fatal and only results in a warning.
+pub fn notify_solved(year: i32, day: u32, part: u8, answer: &str, elapsed: Duration) {
+    let result = Notification::new()
+        .appname("aoc")
+        .summary(&format!("{year}, day {day}, part {part} solved"))
+        .body(&format!(
+            "Answer: {answer}\nElapsed: {:.1}s",
+            elapsed.as_secs_f64()
+        ))
+        .show();
+    if let Err(err) = result {
+        eprintln!("Warning: couldn't send desktop notification: {err}");
+    }
+}
@@ -0,0 +1,177 @@
+//! JUnit-style XML reports for consumption by CI systems.
+
+use std::{collections::BTreeMap, fmt::Write};
+
+use super::{VerifyOutcome, VerifyReport, VerifyResult};
+
+pub fn render(report: &VerifyReport) -> String {
+    let all_results: Vec<&VerifyResult> = report.results.iter().collect();
+    let mut by_year: BTreeMap<i32, Vec<&VerifyResult>> = BTreeMap::new();
+    for result in &report.results {
+        by_year.entry(result.year).or_default().push(result);
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        xml,
+        "<testsuites name=\"aoc\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+        all_results.len(),
+        count(&all_results, |o| matches!(o, VerifyOutcome::Fail { .. })),
+        count(&all_results, |o| matches!(o, VerifyOutcome::Error { .. })),
+        count(&all_results, |o| matches!(o, VerifyOutcome::Skipped)),
+        total_time(&all_results),
+    )
+    .unwrap();
+
+    for (year, results) in by_year {
+        writeln!(
+            xml,
+            "  <testsuite name=\"aoc.{year}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+            results.len(),
+            count(&results, |o| matches!(o, VerifyOutcome::Fail { .. })),
+            count(&results, |o| matches!(o, VerifyOutcome::Error { .. })),
+            count(&results, |o| matches!(o, VerifyOutcome::Skipped)),
+            total_time(&results),
+        )
+        .unwrap();
+        if report.concurrency > 1 {
+            writeln!(
+                xml,
+                "    <properties>\n      <property name=\"concurrency\" value=\"{}\"/>\n    </properties>",
+                report.concurrency
+            )
+            .unwrap();
+        }
+        for result in results {
+            write_testcase(&mut xml, result);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn write_testcase(xml: &mut String, result: &VerifyResult) {
+    write!(
+        xml,
+        "    <testcase classname=\"aoc.{}\" name=\"day{:02}.part{}\" time=\"{:.3}\"",
+        result.year, result.day, result.part, result.elapsed_seconds
+    )
+    .unwrap();
+    match &result.outcome {
+        VerifyOutcome::Pass { .. } => xml.push_str("/>\n"),
+        VerifyOutcome::Fail { expected, actual } => {
+            writeln!(
+                xml,
+                ">\n      <failure message=\"expected {}, got {}\"/>\n    </testcase>",
+                escape(expected),
+                escape(actual)
+            )
+            .unwrap();
+        }
+        VerifyOutcome::Error { message } => {
+            writeln!(
+                xml,
+                ">\n      <error message=\"{}\"/>\n    </testcase>",
+                escape(message)
+            )
+            .unwrap();
+        }
+        VerifyOutcome::Skipped => {
+            xml.push_str(">\n      <skipped message=\"no verified answer\"/>\n    </testcase>\n");
+        }
+    }
+}
+
+fn count<F: Fn(&VerifyOutcome) -> bool>(results: &[&VerifyResult], predicate: F) -> usize {
+    results
+        .iter()
+        .filter(|result| predicate(&result.outcome))
+        .count()
+}
+
+fn total_time(results: &[&VerifyResult]) -> f64 {
+    results.iter().map(|result| result.elapsed_seconds).sum()
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::render;
+    use crate::output::{VerifyOutcome, VerifyReport, VerifyResult};
+
+    fn result(year: i32, day: u32, part: u8, outcome: VerifyOutcome) -> VerifyResult {
+        VerifyResult {
+            year,
+            day,
+            part,
+            elapsed_seconds: 0.5,
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let report = VerifyReport {
+            results: vec![
+                result(2023, 2, 1, VerifyOutcome::Pass { answer: "8".into() }),
+                result(
+                    2023,
+                    2,
+                    2,
+                    VerifyOutcome::Fail {
+                        expected: "2286".into(),
+                        actual: "<none>".into(),
+                    },
+                ),
+                result(2024, 1, 1, VerifyOutcome::Skipped),
+            ],
+            concurrency: 1,
+        };
+        assert_eq!(
+            render(&report),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="aoc" tests="3" failures="1" errors="0" skipped="1" time="1.500">
+  <testsuite name="aoc.2023" tests="2" failures="1" errors="0" skipped="0" time="1.000">
+    <testcase classname="aoc.2023" name="day02.part1" time="0.500"/>
+    <testcase classname="aoc.2023" name="day02.part2" time="0.500">
+      <failure message="expected 2286, got &lt;none&gt;"/>
+    </testcase>
+  </testsuite>
+  <testsuite name="aoc.2024" tests="1" failures="0" errors="0" skipped="1" time="0.500">
+    <testcase classname="aoc.2024" name="day01.part1" time="0.500">
+      <skipped message="no verified answer"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn test_render_records_concurrency() {
+        let report = VerifyReport {
+            results: vec![result(2023, 2, 1, VerifyOutcome::Skipped)],
+            concurrency: 4,
+        };
+        assert!(render(&report).contains(
+            "    <properties>\n      <property name=\"concurrency\" value=\"4\"/>\n    </properties>\n"
+        ));
+    }
+}
@@ -0,0 +1,315 @@
+//! Machine-readable output of the CLI.
+//!
+//! Every JSON document emitted by a command is wrapped in a [`Document`]
+//! carrying a `schema_version` and a `kind` tag. Adding fields is a
+//! compatible change and does not bump the version, so consumers must ignore
+//! fields they do not know. Removing, renaming, or changing the type of a
+//! field requires bumping [`SCHEMA_VERSION`].
+
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bench::Stats, counters::Counters, leaderboard::Leaderboard, puzzle::YearProgress, solvers::Tag,
+};
+
+pub mod junit;
+pub mod text;
+pub mod timing;
+
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable, colored output.
+    #[default]
+    Text,
+    /// JSON document following the versioned output schema.
+    Json,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Document {
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub report: Report,
+}
+
+impl Document {
+    pub fn new(report: Report) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            report,
+        }
+    }
+
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        #[derive(Deserialize)]
+        struct Versioned {
+            schema_version: u32,
+        }
+
+        let Versioned { schema_version } = serde_json::from_str(json)?;
+        if schema_version > SCHEMA_VERSION {
+            return Err(anyhow!(
+                "unsupported schema version {schema_version} (supported up to {SCHEMA_VERSION})"
+            ));
+        }
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Report {
+    Solve(SolveReport),
+    Verify(VerifyReport),
+    Bench(BenchReport),
+    Leaderboard(Leaderboard),
+    Calendar(YearProgress),
+    CacheStats(CacheStatsReport),
+    CacheList(CacheListReport),
+    List(ListReport),
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ListReport {
+    pub solvers: Vec<SolverEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolverEntry {
+    pub year: i32,
+    pub day: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    /// Alternative algorithms selectable with `--algo`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub algorithms: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheStatsReport {
+    pub namespaces: Vec<NamespaceStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NamespaceStats {
+    pub namespace: String,
+    pub entries: usize,
+    /// Total size of the stored entries in bytes.
+    pub bytes: u64,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheListReport {
+    pub entries: Vec<CacheEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub namespace: String,
+    pub key: String,
+    pub bytes: u64,
+    pub modified: DateTime<Utc>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolveReport {
+    pub days: Vec<DayReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayReport {
+    pub year: i32,
+    pub day: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+    pub parts: Vec<PartReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub part: u8,
+    pub description: String,
+    pub answer: String,
+    #[serde(default)]
+    pub elapsed_seconds: f64,
+    /// Peak heap usage while solving the part, if allocations are counted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_heap_bytes: Option<u64>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifyReport {
+    pub results: Vec<VerifyResult>,
+    /// Number of parts verified in parallel. With more than one, the elapsed
+    /// times include contention between the parts and are not comparable to
+    /// timings of parts solved in isolation.
+    #[serde(default)]
+    pub concurrency: usize,
+}
+
+impl VerifyReport {
+    pub fn num_failed(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|result| {
+                matches!(
+                    result.outcome,
+                    VerifyOutcome::Fail { .. } | VerifyOutcome::Error { .. }
+                )
+            })
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifyResult {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+    pub elapsed_seconds: f64,
+    #[serde(flatten)]
+    pub outcome: VerifyOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum VerifyOutcome {
+    Pass { answer: String },
+    Fail { expected: String, actual: String },
+    Error { message: String },
+    Skipped,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub results: Vec<BenchResult>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub year: i32,
+    pub day: u32,
+    pub part: u8,
+    /// Alternative algorithm selected with `--algo`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algo: Option<String>,
+    pub warmup: usize,
+    #[serde(flatten)]
+    pub stats: Stats,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub counters: Option<Counters>,
+}
+
+pub fn print_json(report: Report) -> anyhow::Result<()> {
+    println!("{}", Document::new(report).to_json()?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        DayReport, Document, PartReport, Report, SolveReport, VerifyOutcome, VerifyReport,
+        VerifyResult, SCHEMA_VERSION,
+    };
+
+    fn solve_document() -> Document {
+        Document::new(Report::Solve(SolveReport {
+            days: vec![DayReport {
+                year: 2023,
+                day: 2,
+                title: Some("Cube Conundrum".into()),
+                tags: vec![],
+                parts: vec![PartReport {
+                    part: 1,
+                    description: "Sum of IDs of possible games".into(),
+                    answer: "8".into(),
+                    elapsed_seconds: 0.5,
+                    peak_heap_bytes: None,
+                }],
+            }],
+        }))
+    }
+
+    #[test]
+    fn test_round_trip() -> anyhow::Result<()> {
+        let document = solve_document();
+        assert_eq!(Document::from_json(&document.to_json()?)?, document);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_round_trip() -> anyhow::Result<()> {
+        let document = Document::new(Report::Verify(VerifyReport {
+            results: vec![
+                VerifyResult {
+                    year: 2023,
+                    day: 2,
+                    part: 1,
+                    elapsed_seconds: 0.25,
+                    outcome: VerifyOutcome::Fail {
+                        expected: "8".into(),
+                        actual: "9".into(),
+                    },
+                },
+                VerifyResult {
+                    year: 2023,
+                    day: 2,
+                    part: 2,
+                    elapsed_seconds: 0.0,
+                    outcome: VerifyOutcome::Skipped,
+                },
+            ],
+            concurrency: 4,
+        }));
+        let json = document.to_json()?;
+        assert!(json.contains(r#""status": "fail""#));
+        assert_eq!(Document::from_json(&json)?, document);
+        Ok(())
+    }
+
+    #[test]
+    fn test_document_is_versioned_and_tagged() -> anyhow::Result<()> {
+        let json: serde_json::Value = serde_json::from_str(&solve_document().to_json()?)?;
+        assert_eq!(json["schema_version"], SCHEMA_VERSION);
+        assert_eq!(json["kind"], "solve");
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_fields_are_ignored() -> anyhow::Result<()> {
+        let json = r#"{
+            "schema_version": 1,
+            "kind": "solve",
+            "added_later": true,
+            "days": [{"year": 2023, "day": 2, "parts": [], "also_new": 42}]
+        }"#;
+        match Document::from_json(json)?.report {
+            Report::Solve(report) => assert_eq!(report.days[0].day, 2),
+            report => panic!("unexpected report {report:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_newer_schema_version_is_rejected() {
+        let json = format!(
+            r#"{{"schema_version": {}, "kind": "solve", "days": []}}"#,
+            SCHEMA_VERSION + 1
+        );
+        assert!(Document::from_json(&json).is_err());
+    }
+}
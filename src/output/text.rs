@@ -0,0 +1,240 @@
+//! Human-readable output of the solve, verify, and bench commands.
+//!
+//! Each function renders one block of output including the trailing newline,
+//! so that the rendering can be covered by snapshot tests.
+
+use ansi_term::{Color::Red, Style};
+
+use super::{timing::format_seconds, BenchResult, VerifyOutcome, VerifyResult};
+use crate::{
+    counters::{format_count, format_size},
+    solvers::{InputError, Solution, Value},
+};
+
+/// Header printed before the parts of a day.
+pub fn day_header(year: i32, day: u32, title: Option<&str>) -> String {
+    format!(
+        "📆 {}{}\n",
+        Style::new().underline().paint(format!(
+            "{}, day {}",
+            year,
+            Style::new().bold().paint(day.to_string())
+        )),
+        title.map_or_else(String::new, |title| format!(" — {title}"))
+    )
+}
+
+/// Solution of a part, followed by a warning if it differs from the answer
+/// `expected` by an example.
+pub fn part_solution(solution: &Solution, expected: Option<&Value>) -> String {
+    let mut rendered = format!("⭐ {solution}\n");
+    if let Some(expected) = expected.filter(|&expected| !solution.matches(expected)) {
+        rendered.push_str(&format!(
+            "{}\n",
+            Red.paint(format!("❌ The example expects {expected}"))
+        ));
+    }
+    rendered
+}
+
+/// A part without a solution, e.g. the second part of the last day.
+pub fn part_not_available(part: u8) -> String {
+    format!("➖ Part {part}: n/a\n")
+}
+
+pub fn part_skipped(part: u8) -> String {
+    format!("⏭️  Part {part} already solved\n")
+}
+
+/// Excerpt of the input around a malformed line, or nothing if the error was
+/// not located in the input.
+pub fn input_error(err: &InputError, input: &str) -> String {
+    err.diagnostic(input)
+        .map(|diagnostic| Red.paint(diagnostic).to_string())
+        .unwrap_or_default()
+}
+
+pub fn verify_result(result: &VerifyResult) -> String {
+    let name = format!("{}, day {}, part {}", result.year, result.day, result.part);
+    match &result.outcome {
+        VerifyOutcome::Pass { answer } => format!(
+            "✅ {name}: {} ({:.3}s)\n",
+            Style::new().bold().paint(answer),
+            result.elapsed_seconds
+        ),
+        VerifyOutcome::Fail { expected, actual } => format!(
+            "❌ {name}: expected {}, got {}\n",
+            Style::new().bold().paint(expected),
+            Red.bold().paint(actual)
+        ),
+        VerifyOutcome::Error { message } => format!("💥 {name}: {}\n", Red.paint(message)),
+        VerifyOutcome::Skipped => format!("⏭️  {name}: no verified answer\n"),
+    }
+}
+
+pub fn bench_result(result: &BenchResult) -> String {
+    let stats = &result.stats;
+    let algo = result
+        .algo
+        .as_ref()
+        .map(|algo| format!(" ({algo})"))
+        .unwrap_or_default();
+    let mut rendered = format!(
+        "⏱️  {}, day {}, part {}{algo}: median {} (mean {} ± {}, min {}, max {}, {} runs, {} outliers)\n",
+        result.year,
+        result.day,
+        result.part,
+        Style::new()
+            .bold()
+            .paint(format_seconds(stats.median_seconds)),
+        format_seconds(stats.mean_seconds),
+        format_seconds(stats.std_dev_seconds),
+        format_seconds(stats.min_seconds),
+        format_seconds(stats.max_seconds),
+        stats.runs,
+        stats.outliers,
+    );
+    if let Some(counters) = &result.counters {
+        rendered.push_str(&format!(
+            "   allocations {} ({} bytes, peak heap {}), instructions {}, branch misses {}, \
+             cache misses {}\n",
+            format_count(counters.allocations),
+            format_count(counters.allocated_bytes),
+            counters
+                .peak_heap_bytes
+                .map_or_else(|| "n/a".into(), format_size),
+            format_count(counters.instructions),
+            format_count(counters.branch_misses),
+            format_count(counters.cache_misses),
+        ));
+    }
+    rendered
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        bench_result, day_header, part_not_available, part_skipped, part_solution, verify_result,
+    };
+    use crate::{
+        bench::Stats,
+        counters::Counters,
+        output::{BenchResult, VerifyOutcome, VerifyResult},
+        solvers::{Solution, Value},
+    };
+    use insta::assert_snapshot;
+
+    /// Removes ANSI escape sequences so that snapshots stay readable.
+    fn plain(rendered: String) -> String {
+        let mut plain = String::with_capacity(rendered.len());
+        let mut chars = rendered.chars();
+        while let Some(c) = chars.next() {
+            if c == '\u{1b}' {
+                chars.by_ref().find(|&c| c == 'm');
+            } else {
+                plain.push(c);
+            }
+        }
+        plain
+    }
+
+    #[test]
+    fn test_day_header() {
+        assert_snapshot!(
+            plain(day_header(2023, 2, Some("Cube Conundrum"))),
+            @"📆 2023, day 2 — Cube Conundrum"
+        );
+        assert_snapshot!(plain(day_header(2023, 2, None)), @"📆 2023, day 2");
+    }
+
+    #[test]
+    fn test_part_solution() {
+        let solution = Solution::with_description("Sum of IDs of possible games", 8usize);
+        assert_snapshot!(
+            plain(part_solution(&solution, None)),
+            @"⭐ Sum of IDs of possible games: 8"
+        );
+        assert_snapshot!(
+            "part_solution_matching_example",
+            plain(part_solution(&solution, Some(&Value::Integer(8))))
+        );
+        assert_eq!(
+            part_solution(&solution, Some(&Value::Text("8".into()))),
+            part_solution(&solution, None)
+        );
+        assert_snapshot!(
+            "part_solution_differing_from_example",
+            plain(part_solution(&solution, Some(&Value::Integer(9))))
+        );
+    }
+
+    #[test]
+    fn test_part_without_solution() {
+        assert_snapshot!(plain(part_not_available(2) + &part_skipped(1)));
+    }
+
+    #[cfg(feature = "year2023")]
+    #[test]
+    fn test_input_error() {
+        use super::input_error;
+        use crate::solvers::new_solver;
+
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 red, 2 purple\nGame 3: 8 green\n";
+        let err = new_solver(2023, 2, input).err().unwrap();
+        assert_snapshot!("input_error_message", err.to_string());
+        assert_snapshot!(
+            "input_error_diagnostic",
+            plain(input_error(err.downcast_ref().unwrap(), input))
+        );
+    }
+
+    #[test]
+    fn test_verify_result() {
+        let result = |outcome| VerifyResult {
+            year: 2023,
+            day: 2,
+            part: 1,
+            elapsed_seconds: 0.0123,
+            outcome,
+        };
+        let rendered: String = [
+            VerifyOutcome::Pass { answer: "8".into() },
+            VerifyOutcome::Fail {
+                expected: "8".into(),
+                actual: "9".into(),
+            },
+            VerifyOutcome::Error {
+                message: "expected red, green, or blue, found 'purple'".into(),
+            },
+            VerifyOutcome::Skipped,
+        ]
+        .into_iter()
+        .map(|outcome| verify_result(&result(outcome)))
+        .collect();
+        assert_snapshot!(plain(rendered));
+    }
+
+    #[test]
+    fn test_bench_result() {
+        let mut result = BenchResult {
+            year: 2023,
+            day: 17,
+            part: 2,
+            algo: Some("bucket-queue".into()),
+            warmup: 3,
+            stats: Stats::from_samples(&[0.011, 0.012, 0.013, 0.02]).unwrap(),
+            counters: None,
+        };
+        assert_snapshot!("bench_result_with_algo", plain(bench_result(&result)));
+        result.algo = None;
+        result.counters = Some(Counters {
+            allocations: Some(1200),
+            allocated_bytes: Some(3_500_000),
+            peak_heap_bytes: Some(2048),
+            instructions: Some(45_000_000),
+            branch_misses: None,
+            cache_misses: None,
+        });
+        assert_snapshot!("bench_result_with_counters", plain(bench_result(&result)));
+    }
+}
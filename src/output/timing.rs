@@ -0,0 +1,174 @@
+//! Summary table of solving times for multi-day runs, with the peak heap
+//! usage if allocations are counted.
+
+use std::{fmt::Write, time::Duration};
+
+use ansi_term::{Color::Red, Style};
+
+use super::DayReport;
+use crate::counters::format_size;
+
+/// Number of days highlighted as the slowest ones.
+const NUM_HIGHLIGHTED: usize = 3;
+
+pub fn render(days: &[DayReport]) -> String {
+    let totals: Vec<f64> = days.iter().map(day_total).collect();
+    let mut slowest: Vec<usize> = (0..days.len()).collect();
+    slowest.sort_by(|&a, &b| totals[b].total_cmp(&totals[a]));
+    // Highlighting is only meaningful if some days are not highlighted.
+    slowest.truncate(if days.len() > NUM_HIGHLIGHTED {
+        NUM_HIGHLIGHTED
+    } else {
+        0
+    });
+
+    let with_peak_heap = days.iter().any(|day| day_peak_heap(day).is_some());
+    let separator = if with_peak_heap {
+        format!("{0:─>11}┼{0:─>12}┼{0:─>12}┼{0:─>12}┼{0:─>11}", "")
+    } else {
+        format!("{0:─>11}┼{0:─>12}┼{0:─>12}┼{0:─>11}", "")
+    };
+
+    let mut table = String::new();
+    writeln!(table, "⏱️  {}", Style::new().underline().paint("Timing")).unwrap();
+    write!(
+        table,
+        "{:>10} │ {:>10} │ {:>10} │ {:>10}",
+        "Day", "Part 1", "Part 2", "Total"
+    )
+    .unwrap();
+    if with_peak_heap {
+        write!(table, " │ {:>10}", "Peak heap").unwrap();
+    }
+    writeln!(table).unwrap();
+    writeln!(table, "{separator}").unwrap();
+    for (i, day) in days.iter().enumerate() {
+        let mut row = format!(
+            "{:>10} │ {:>10} │ {:>10} │ {:>10}",
+            format!("{}/{:02}", day.year, day.day),
+            part_elapsed(day, 1).map_or_else(|| "-".into(), format_seconds),
+            part_elapsed(day, 2).map_or_else(|| "-".into(), format_seconds),
+            format_seconds(totals[i]),
+        );
+        if with_peak_heap {
+            let peak_heap = day_peak_heap(day).map_or_else(|| "-".into(), format_size);
+            write!(row, " │ {peak_heap:>10}").unwrap();
+        }
+        if slowest.contains(&i) {
+            writeln!(table, "{}", Red.bold().paint(row)).unwrap();
+        } else {
+            writeln!(table, "{row}").unwrap();
+        }
+    }
+    writeln!(table, "{separator}").unwrap();
+    write!(
+        table,
+        "{:>10} │ {:>10} │ {:>10} │ {:>10}",
+        "Total",
+        format_seconds(days.iter().filter_map(|day| part_elapsed(day, 1)).sum()),
+        format_seconds(days.iter().filter_map(|day| part_elapsed(day, 2)).sum()),
+        format_seconds(totals.iter().sum()),
+    )
+    .unwrap();
+    if with_peak_heap {
+        let peak_heap = days.iter().filter_map(day_peak_heap).max().unwrap_or(0);
+        write!(table, " │ {:>10}", format_size(peak_heap)).unwrap();
+    }
+    writeln!(table).unwrap();
+    table
+}
+
+fn part_elapsed(day: &DayReport, part: u8) -> Option<f64> {
+    day.parts
+        .iter()
+        .find(|p| p.part == part)
+        .map(|p| p.elapsed_seconds)
+}
+
+/// Largest peak heap usage of the parts of a day.
+fn day_peak_heap(day: &DayReport) -> Option<u64> {
+    day.parts
+        .iter()
+        .filter_map(|part| part.peak_heap_bytes)
+        .max()
+}
+
+fn day_total(day: &DayReport) -> f64 {
+    day.parts.iter().map(|part| part.elapsed_seconds).sum()
+}
+
+pub fn format_seconds(seconds: f64) -> String {
+    let duration = Duration::from_secs_f64(seconds);
+    if duration < Duration::from_millis(1) {
+        format!("{}µs", duration.as_micros())
+    } else if duration < Duration::from_secs(1) {
+        format!("{:.1}ms", seconds * 1e3)
+    } else {
+        format!("{:.2}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_seconds, render};
+    use crate::output::{DayReport, PartReport};
+
+    fn day(day: u32, part_seconds: [f64; 2]) -> DayReport {
+        DayReport {
+            year: 2023,
+            day,
+            title: None,
+            tags: vec![],
+            parts: part_seconds
+                .iter()
+                .enumerate()
+                .map(|(i, &elapsed_seconds)| PartReport {
+                    part: i as u8 + 1,
+                    description: String::new(),
+                    answer: String::new(),
+                    elapsed_seconds,
+                    peak_heap_bytes: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_format_seconds() {
+        assert_eq!(format_seconds(0.000_012), "12µs");
+        assert_eq!(format_seconds(0.0123), "12.3ms");
+        assert_eq!(format_seconds(12.345), "12.35s");
+    }
+
+    #[test]
+    fn test_render_highlights_slowest_days() {
+        let days = [
+            day(1, [0.001, 0.002]),
+            day(2, [1.0, 2.0]),
+            day(3, [0.01, 0.02]),
+            day(4, [0.1, 0.2]),
+            day(5, [0.0001, 0.0002]),
+        ];
+        let table = render(&days);
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert!(lines[4].contains("2023/02") && lines[4].contains("\u{1b}[1;31m"));
+        assert!(lines[3].contains("2023/01") && !lines[3].contains("\u{1b}[1;31m"));
+        assert!(lines[9].contains("Total") && lines[9].contains("3.33s"));
+    }
+
+    #[test]
+    fn test_render_peak_heap() {
+        let mut days = [day(1, [0.001, 0.002]), day(2, [0.01, 0.02])];
+        assert!(!render(&days).contains("Peak heap"));
+
+        days[0].parts[0].peak_heap_bytes = Some(2048);
+        days[0].parts[1].peak_heap_bytes = Some(1024);
+        let table = render(&days);
+        let lines: Vec<_> = table.lines().collect();
+        assert!(lines[1].contains("Peak heap"));
+        assert!(lines[3].contains("2023/01") && lines[3].ends_with("2.0 KiB"));
+        assert!(lines[4].contains("2023/02") && lines[4].ends_with("-"));
+        assert!(lines[6].contains("Total") && lines[6].ends_with("2.0 KiB"));
+    }
+}
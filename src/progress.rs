@@ -0,0 +1,60 @@
+use std::{sync::Mutex, time::Duration};
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+use crate::solvers::ProgressSink;
+
+/// Renders solver progress as a progress bar on stderr. Nothing is drawn if
+/// stderr is not a terminal.
+#[derive(Default)]
+pub struct ProgressBarReporter {
+    bar: Mutex<Option<ProgressBar>>,
+}
+
+impl ProgressSink for ProgressBarReporter {
+    fn start(&self, total: u64) {
+        let bar = ProgressBar::new(total).with_style(
+            ProgressStyle::with_template("⏳ [{elapsed_precise}] {wide_bar} {pos}/{len} ({eta})")
+                .expect("valid progress bar template"),
+        );
+        if let Some(previous) = self.bar.lock().unwrap().replace(bar) {
+            previous.finish_and_clear();
+        }
+    }
+
+    fn advance(&self, delta: u64) {
+        if let Some(bar) = self.bar.lock().unwrap().as_ref() {
+            bar.inc(delta);
+        }
+    }
+
+    fn finish(&self) {
+        if let Some(bar) = self.bar.lock().unwrap().take() {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+/// Renders spinners with a byte counter for running downloads on stderr.
+/// Nothing is drawn if stderr is not a terminal.
+#[derive(Debug, Default, Clone)]
+pub struct DownloadProgress {
+    bars: MultiProgress,
+}
+
+impl DownloadProgress {
+    /// Adds a spinner for a new download. Advance it by the number of
+    /// received bytes.
+    pub fn start(&self, message: String) -> ProgressBar {
+        let bar = self.bars.add(
+            ProgressBar::new_spinner()
+                .with_style(
+                    ProgressStyle::with_template("⬇️  {spinner} {msg} {bytes}")
+                        .expect("valid progress bar template"),
+                )
+                .with_message(message),
+        );
+        bar.enable_steady_tick(Duration::from_millis(100));
+        bar
+    }
+}
@@ -0,0 +1,156 @@
+//! Information extracted from puzzle description and calendar pages.
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzlePage {
+    /// Title from the `--- Day N: Title ---` heading.
+    pub title: Option<String>,
+    /// Whether the description of part 2 is shown, i.e. part 1 is solved.
+    pub part_2_unlocked: bool,
+    /// Accepted answers in order of the parts.
+    pub accepted_answers: Vec<String>,
+}
+
+impl PuzzlePage {
+    pub fn from_html(html: &str) -> Self {
+        lazy_static! {
+            static ref ANSWER: Regex =
+                Regex::new(r"Your puzzle answer was <code>(.*?)</code>").unwrap();
+            static ref TITLE: Regex = Regex::new(r"<h2>--- Day \d+: (.*?) ---</h2>").unwrap();
+        }
+
+        Self {
+            title: TITLE
+                .captures(html)
+                .map(|captures| unescape_html(&captures[1])),
+            part_2_unlocked: html.matches(r#"<article class="day-desc">"#).count() > 1,
+            accepted_answers: ANSWER
+                .captures_iter(html)
+                .map(|captures| captures[1].to_string())
+                .collect(),
+        }
+    }
+
+    pub fn is_solved(&self, part: u8) -> bool {
+        self.accepted_answers.len() >= part as usize
+    }
+
+    /// The first part without an accepted answer, if any.
+    pub fn next_unsolved_part(&self) -> Option<u8> {
+        [1, 2].into_iter().find(|&part| !self.is_solved(part))
+    }
+}
+
+/// Stars collected per day, as shown on the calendar page of a year.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YearProgress {
+    pub year: i32,
+    /// Stars by day for all days shown on the calendar.
+    pub stars: BTreeMap<u32, u8>,
+}
+
+impl YearProgress {
+    pub fn from_html(year: i32, html: &str) -> Self {
+        lazy_static! {
+            static ref DAY: Regex =
+                Regex::new(r#"class="calendar-day(\d+)(?: calendar-(very)?(complete))?""#).unwrap();
+        }
+
+        let stars = DAY
+            .captures_iter(html)
+            .map(|captures| {
+                let day = captures[1].parse().expect("regex matches only digits");
+                let stars =
+                    u8::from(captures.get(3).is_some()) + u8::from(captures.get(2).is_some());
+                (day, stars)
+            })
+            .collect();
+        Self { year, stars }
+    }
+
+    pub fn total_stars(&self) -> u32 {
+        self.stars.values().map(|&stars| u32::from(stars)).sum()
+    }
+}
+
+pub fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PuzzlePage, YearProgress};
+
+    const UNSOLVED: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 2: Cube Conundrum ---</h2><p>...</p></article>
+<p>To begin, <a href="2/input" target="_blank">get your puzzle input</a>.</p>
+</main>"#;
+
+    const PART_1_SOLVED: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 2: Cube Conundrum ---</h2><p>...</p></article>
+<p>Your puzzle answer was <code>2563</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>...</p></article>
+</main>"#;
+
+    const SOLVED: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 2: Cube Conundrum ---</h2><p>...</p></article>
+<p>Your puzzle answer was <code>2563</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>...</p></article>
+<p>Your puzzle answer was <code>70768</code>.</p>
+<p class="day-success">Both parts of this puzzle are complete! They provide two gold stars: **</p>
+</main>"#;
+
+    #[test]
+    fn test_unsolved() {
+        let page = PuzzlePage::from_html(UNSOLVED);
+        assert_eq!(page.title.as_deref(), Some("Cube Conundrum"));
+        assert!(!page.part_2_unlocked);
+        assert_eq!(page.next_unsolved_part(), Some(1));
+    }
+
+    #[test]
+    fn test_title_is_unescaped() {
+        let page = PuzzlePage::from_html("<h2>--- Day 1: Trebuchet?! &amp; more ---</h2>");
+        assert_eq!(page.title.as_deref(), Some("Trebuchet?! & more"));
+    }
+
+    #[test]
+    fn test_part_1_solved() {
+        let page = PuzzlePage::from_html(PART_1_SOLVED);
+        assert!(page.part_2_unlocked);
+        assert_eq!(page.accepted_answers, vec!["2563"]);
+        assert_eq!(page.next_unsolved_part(), Some(2));
+    }
+
+    #[test]
+    fn test_solved() {
+        let page = PuzzlePage::from_html(SOLVED);
+        assert_eq!(page.accepted_answers, vec!["2563", "70768"]);
+        assert!(page.is_solved(2));
+        assert_eq!(page.next_unsolved_part(), None);
+    }
+
+    #[test]
+    fn test_year_progress() {
+        let html = r#"<pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2023/day/1" class="calendar-day1 calendar-verycomplete">...</a>
+<a aria-label="Day 2, one star" href="/2023/day/2" class="calendar-day2 calendar-complete">...</a>
+<a aria-label="Day 3" href="/2023/day/3" class="calendar-day3">...</a>
+<span aria-hidden="true" class="calendar-day4">...</span>
+</pre>"#;
+        let progress = YearProgress::from_html(2023, html);
+        assert_eq!(progress.total_stars(), 3);
+        assert_eq!(
+            progress.stars.into_iter().collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1), (3, 0), (4, 0)]
+        );
+    }
+}
@@ -1,31 +1,483 @@
-use anyhow::Context;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, OsRng},
+    KeyInit, XChaCha20Poly1305, XNonce,
+};
+use clap::ValueEnum;
 use inquire::Password;
-use secrecy::SecretBox;
+use keyring::credential::CredentialPersistence;
+use secrecy::{ExposeSecret, SecretBox};
+use tracing::{debug, info, warn};
+
+/// Environment variable with a session ID taking precedence over the stored
+/// one, e.g. for CI pipelines.
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION";
+
+/// Environment variable with the passphrase of an encrypted session ID file.
+pub const PASSPHRASE_ENV_VAR: &str = "AOC_SESSION_PASSPHRASE";
+
+/// Marks a session ID file encrypted with a passphrase. It is followed by the
+/// hex encoded salt, nonce, and ciphertext separated by spaces.
+const ENCRYPTED_PREFIX: &str = "aoc-encrypted-v1 ";
+
+/// Cost parameter of the key derivation from the passphrase.
+const SCRYPT_LOG_N: u8 = 15;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SessionBackend {
+    /// Platform credential store, falling back to a file if there is none.
+    #[default]
+    Keyring,
+    /// Plaintext file in the configuration directory.
+    File,
+}
 
 pub struct SessionIdStore {
-    entry: keyring::Entry,
+    /// Entry in the platform credential store, if one is used.
+    entry: Option<keyring::Entry>,
+    /// File used without a credential store.
+    file: PathBuf,
+    /// Passphrase to encrypt the file with. The credential store is not used
+    /// for storing if it is set.
+    passphrase: Option<SecretBox<String>>,
+}
+
+/// Checks that a profile name only consists of ASCII letters, digits, `-`,
+/// and `_`, so that it can be used in paths.
+pub fn parse_profile(name: &str) -> anyhow::Result<String> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(anyhow::anyhow!(
+            "profile names may only contain letters, digits, '-', and '_'"
+        ));
+    }
+    Ok(name.into())
 }
 
 impl SessionIdStore {
-    pub fn new() -> anyhow::Result<Self> {
-        let entry = keyring::Entry::new("adventofcode", "session_id")?;
-        Ok(Self { entry })
+    /// Opens the store for the session ID of the given profile, or of the
+    /// default profile if there is none.
+    pub fn new(backend: SessionBackend, profile: Option<&str>) -> anyhow::Result<Self> {
+        let config =
+            dirs::config_dir().map_or_else(|| PathBuf::from("."), |config| config.join("aoc"));
+        let (file, user) = match profile {
+            Some(profile) => (
+                config.join("profiles").join(profile).join("session_id"),
+                format!("session_id/{profile}"),
+            ),
+            None => (config.join("session_id"), "session_id".into()),
+        };
+        let entry = match backend {
+            SessionBackend::Keyring if has_persistent_keyring() => {
+                Some(keyring::Entry::new("adventofcode", &user)?)
+            }
+            SessionBackend::Keyring => {
+                info!("no persistent credential store available, using a file");
+                None
+            }
+            SessionBackend::File => None,
+        };
+        Ok(Self {
+            entry,
+            file,
+            passphrase: None,
+        })
+    }
+
+    #[cfg(test)]
+    fn with_entry(entry: Option<keyring::Entry>, file: PathBuf) -> Self {
+        Self {
+            entry,
+            file,
+            passphrase: None,
+        }
+    }
+
+    /// Reads a session ID, e.g. piped into stdin.
+    pub fn read_from<R: std::io::Read>(mut reader: R) -> anyhow::Result<SecretBox<String>> {
+        let mut session_id = String::new();
+        reader
+            .read_to_string(&mut session_id)
+            .context("reading session id")?;
+        let session_id = session_id.trim();
+        if session_id.is_empty() {
+            return Err(anyhow!("no session id given"));
+        }
+        Ok(SecretBox::new(Box::new(session_id.into())))
+    }
+
+    /// Stores the session ID in a file encrypted with the passphrase, even if
+    /// there is a credential store.
+    pub fn with_passphrase(mut self, passphrase: SecretBox<String>) -> Self {
+        self.passphrase = Some(passphrase);
+        self
+    }
+
+    /// Asks for a new passphrase.
+    pub fn read_passphrase_from_prompt() -> anyhow::Result<SecretBox<String>> {
+        let passphrase = Password::new("Passphrase to encrypt the session id with:")
+            .prompt()
+            .context("passphrase input")?;
+        Ok(SecretBox::new(Box::new(passphrase)))
     }
 
     pub fn prompt(&self) -> anyhow::Result<SecretBox<String>> {
+        let session_id = Self::read_from_prompt()?;
+        self.set(&session_id)?;
+        Ok(session_id)
+    }
+
+    /// Asks for a session ID without storing it.
+    pub fn read_from_prompt() -> anyhow::Result<SecretBox<String>> {
         let session_id = Password::new("Your Advent of Code session id:")
             .without_confirmation()
             .prompt()
             .context("password input")?;
-        self.entry.set_password(&session_id)?;
         Ok(SecretBox::new(Box::new(session_id)))
     }
 
+    pub fn set(&self, session_id: &SecretBox<String>) -> anyhow::Result<()> {
+        if let Some(passphrase) = &self.passphrase {
+            // Encryption was asked for explicitly, so the session ID goes into
+            // the file even if there is a credential store.
+            let content = encrypt(session_id.expose_secret(), passphrase.expose_secret())?;
+            write_private_file(&self.file, &content)?;
+            return self.delete_entry();
+        }
+        if let Some(entry) = &self.entry {
+            match entry.set_password(session_id.expose_secret()) {
+                // Do not leave an outdated session ID behind.
+                Ok(()) => return remove_file(&self.file),
+                Err(err) => warn!(error = %err, "cannot use credential store, using a file"),
+            }
+        }
+        write_private_file(&self.file, session_id.expose_secret())
+    }
+
+    /// Removes the session ID from the credential store, where it would take
+    /// precedence over the file.
+    fn delete_entry(&self) -> anyhow::Result<()> {
+        match self.entry.as_ref().map(keyring::Entry::delete_credential) {
+            None | Some(Ok(()) | Err(keyring::Error::NoEntry)) => Ok(()),
+            Some(Err(err)) => Err(err).context(
+                "removing the previous session ID from the credential store, which would take \
+                 precedence over the encrypted file",
+            ),
+        }
+    }
+
     pub fn session_id(&self) -> anyhow::Result<SecretBox<String>> {
-        Ok(match self.entry.get_password() {
-            Ok(password) => SecretBox::new(Box::new(password)),
-            Err(keyring::Error::NoEntry) => self.prompt()?,
-            Err(err) => Err(err).context("credential store")?,
-        })
+        if let Some(session_id) = session_id_from_env() {
+            debug!("using session ID from {SESSION_ENV_VAR}");
+            return Ok(session_id);
+        }
+        match self.get()? {
+            Some(session_id) => Ok(session_id),
+            None => self.prompt(),
+        }
+    }
+
+    /// Returns the stored session ID, moving one stored in a plaintext file
+    /// into the credential store. Encrypted files stay where they are.
+    fn get(&self) -> anyhow::Result<Option<SecretBox<String>>> {
+        if let Some(entry) = &self.entry {
+            match entry.get_password() {
+                Ok(password) => return Ok(Some(SecretBox::new(Box::new(password)))),
+                Err(keyring::Error::NoEntry) => {
+                    let Some((session_id, encrypted)) = self.read_file()? else {
+                        return Ok(None);
+                    };
+                    if !encrypted {
+                        info!(file = %self.file.display(), "moving session ID into credential store");
+                        self.set(&session_id)?;
+                    }
+                    return Ok(Some(session_id));
+                }
+                Err(err) => warn!(error = %err, "cannot use credential store, using a file"),
+            }
+        }
+        Ok(self.read_file()?.map(|(session_id, _)| session_id))
+    }
+
+    /// Reads the session ID from the file and whether it was encrypted.
+    fn read_file(&self) -> anyhow::Result<Option<(SecretBox<String>, bool)>> {
+        let content = match std::fs::read_to_string(&self.file) {
+            Ok(content) => SecretBox::new(Box::new(content)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).context(format!("reading {}", self.file.display())),
+        };
+        check_not_world_readable(&self.file)?;
+        let content = content.expose_secret().trim();
+        let (session_id, encrypted) = match content.strip_prefix(ENCRYPTED_PREFIX) {
+            Some(encrypted) => (
+                decrypt(encrypted, self.passphrase()?.expose_secret())
+                    .with_context(|| format!("decrypting {}", self.file.display()))?,
+                true,
+            ),
+            None => (content.into(), false),
+        };
+        Ok(Some((SecretBox::new(Box::new(session_id)), encrypted)))
+    }
+
+    /// Passphrase to decrypt the file with, asking for it if it is neither
+    /// configured nor given in the environment.
+    fn passphrase(&self) -> anyhow::Result<SecretBox<String>> {
+        if let Some(passphrase) = &self.passphrase {
+            return Ok(SecretBox::new(Box::new(passphrase.expose_secret().clone())));
+        }
+        if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV_VAR) {
+            return Ok(SecretBox::new(Box::new(passphrase)));
+        }
+        let passphrase = Password::new("Passphrase of the stored session id:")
+            .without_confirmation()
+            .prompt()
+            .context("passphrase input")?;
+        Ok(SecretBox::new(Box::new(passphrase)))
+    }
+}
+
+/// The session ID from the [`SESSION_ENV_VAR`] environment variable, if set
+/// and not empty.
+pub fn session_id_from_env() -> Option<SecretBox<String>> {
+    std::env::var(SESSION_ENV_VAR)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .map(|value| SecretBox::new(Box::new(value)))
+}
+
+/// Whether the platform credential store keeps entries until they are
+/// deleted. Without a native store, the keyring crate only keeps them in
+/// memory.
+fn has_persistent_keyring() -> bool {
+    matches!(
+        keyring::default::default_credential_builder().persistence(),
+        CredentialPersistence::UntilDelete
+    )
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> anyhow::Result<chacha20poly1305::Key> {
+    let params = scrypt::Params::new(SCRYPT_LOG_N, 8, 1, 32).map_err(|err| anyhow!("{err}"))?;
+    let mut key = chacha20poly1305::Key::default();
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+        .map_err(|err| anyhow!("{err}"))?;
+    Ok(key)
+}
+
+fn encrypt(session_id: &str, passphrase: &str) -> anyhow::Result<String> {
+    let mut salt = [0; 16];
+    OsRng.fill_bytes(&mut salt);
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, session_id.as_bytes())
+        .map_err(|_| anyhow!("encryption failed"))?;
+    Ok(format!(
+        "{ENCRYPTED_PREFIX}{} {} {}",
+        hex::encode(salt),
+        hex::encode(nonce),
+        hex::encode(ciphertext)
+    ))
+}
+
+fn decrypt(encrypted: &str, passphrase: &str) -> anyhow::Result<String> {
+    let parts: Vec<_> = encrypted
+        .split(' ')
+        .map(hex::decode)
+        .collect::<Result<_, _>>()?;
+    let [salt, nonce, ciphertext] = parts.as_slice() else {
+        return Err(anyhow!("malformed encrypted session id"));
+    };
+    if nonce.len() != 24 {
+        return Err(anyhow!("malformed encrypted session id"));
+    }
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, salt)?);
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("wrong passphrase"))?;
+    Ok(String::from_utf8(plaintext)?)
+}
+
+/// Refuses to use a session ID file that other users can read, since they
+/// could have copied the session ID.
+fn check_not_world_readable(path: &Path) -> anyhow::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(path)?.permissions().mode();
+        if mode & 0o004 != 0 {
+            return Err(anyhow!(
+                "{} is readable by other users, restrict access with `chmod 600` and consider \
+                 replacing the session ID by logging out of AoC",
+                path.display()
+            ));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+fn remove_file(path: &Path) -> anyhow::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            Err(err).context(format!("removing {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Writes a file only readable by the current user.
+fn write_private_file(path: &Path, content: &str) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("creating directory {}", parent.display()))?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))?;
+    std::io::Write::write_all(&mut file, content.as_bytes())
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decrypt, encrypt, parse_profile, SessionIdStore, ENCRYPTED_PREFIX};
+    use keyring::mock::MockCredential;
+    use rstest::rstest;
+    use secrecy::{ExposeSecret, SecretBox};
+
+    fn write_private(path: &std::path::Path, content: &str) {
+        super::write_private_file(path, content).unwrap();
+    }
+
+    fn mock_entry() -> keyring::Entry {
+        keyring::Entry::new_with_credential(Box::new(MockCredential::default()))
+    }
+
+    #[rstest]
+    #[case("work", true)]
+    #[case("alt_2-b", true)]
+    #[case("", false)]
+    #[case("../work", false)]
+    #[case("a b", false)]
+    fn test_parse_profile(#[case] name: &str, #[case] valid: bool) {
+        assert_eq!(parse_profile(name).is_ok(), valid);
+    }
+
+    #[test]
+    fn test_file_store() {
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("aoc").join("session_id");
+        let store = SessionIdStore::with_entry(None, file.clone());
+        assert!(store.get().unwrap().is_none());
+
+        store.set(&SecretBox::new(Box::new("abc".into()))).unwrap();
+        assert_eq!(store.get().unwrap().unwrap().expose_secret(), "abc");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&file).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_migrates_file_into_keyring() {
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("session_id");
+        write_private(&file, "abc\n");
+        let store = SessionIdStore::with_entry(Some(mock_entry()), file.clone());
+
+        assert_eq!(store.get().unwrap().unwrap().expose_secret(), "abc");
+        assert!(!file.exists());
+        assert_eq!(store.get().unwrap().unwrap().expose_secret(), "abc");
+    }
+
+    #[test]
+    fn test_falls_back_to_file() {
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("session_id");
+        let entry = mock_entry();
+        let mock: &MockCredential = entry.get_credential().downcast_ref().unwrap();
+        mock.set_error(keyring::Error::NoStorageAccess("locked".into()));
+        let store = SessionIdStore::with_entry(Some(entry), file.clone());
+
+        store.set(&SecretBox::new(Box::new("abc".into()))).unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "abc");
+    }
+
+    #[test]
+    fn test_read_from() {
+        let session_id = SessionIdStore::read_from("abc\n".as_bytes()).unwrap();
+        assert_eq!(session_id.expose_secret(), "abc");
+        assert!(SessionIdStore::read_from(" \n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_encryption() {
+        let encrypted = encrypt("session-id", "secret").unwrap();
+        let encrypted = encrypted.strip_prefix(ENCRYPTED_PREFIX).unwrap();
+        let ciphertext = hex::decode(encrypted.rsplit(' ').next().unwrap()).unwrap();
+        assert!(!ciphertext
+            .windows("session-id".len())
+            .any(|window| window == b"session-id"));
+        assert_eq!(decrypt(encrypted, "secret").unwrap(), "session-id");
+        assert!(decrypt(encrypted, "wrong").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_file_store() {
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("session_id");
+        let store = SessionIdStore::with_entry(None, file.clone())
+            .with_passphrase(SecretBox::new(Box::new("secret".into())));
+
+        store.set(&SecretBox::new(Box::new("abc".into()))).unwrap();
+        assert!(std::fs::read_to_string(&file)
+            .unwrap()
+            .starts_with(ENCRYPTED_PREFIX));
+        assert_eq!(store.get().unwrap().unwrap().expose_secret(), "abc");
+    }
+
+    #[test]
+    fn test_encrypted_file_store_with_keyring() {
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("session_id");
+        let entry = mock_entry();
+        entry.set_password("old").unwrap();
+        let store = SessionIdStore::with_entry(Some(entry), file.clone())
+            .with_passphrase(SecretBox::new(Box::new("secret".into())));
+
+        store.set(&SecretBox::new(Box::new("abc".into()))).unwrap();
+        assert!(std::fs::read_to_string(&file)
+            .unwrap()
+            .starts_with(ENCRYPTED_PREFIX));
+        assert_eq!(store.get().unwrap().unwrap().expose_secret(), "abc");
+        // The encrypted file is not moved into the credential store.
+        assert!(std::fs::read_to_string(&file)
+            .unwrap()
+            .starts_with(ENCRYPTED_PREFIX));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_refuses_world_readable_file() {
+        use std::os::unix::fs::PermissionsExt;
+        let directory = tempfile::tempdir().unwrap();
+        let file = directory.path().join("session_id");
+        std::fs::write(&file, "abc").unwrap();
+        std::fs::set_permissions(&file, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let store = SessionIdStore::with_entry(None, file);
+        assert!(store.get().is_err());
     }
 }
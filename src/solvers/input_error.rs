@@ -0,0 +1,258 @@
+//! Errors pointing to the part of a puzzle input that could not be parsed.
+//!
+//! Solvers usually parse slices of their input, so an [`InputError`] is
+//! created from the offending slice without knowing the whole input. The
+//! runner locates it in the input afterwards to report the line and column.
+
+use std::fmt::Display;
+
+/// Maximum number of characters of the offending input kept for the message.
+const MAX_SNIPPET_LEN: usize = 40;
+
+/// Number of lines shown before and after the offending line in diagnostics.
+const CONTEXT_LINES: usize = 1;
+
+/// Malformed puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputError {
+    expected: String,
+    snippet: String,
+    /// Address of the offending slice, to find it in the input. It is never
+    /// dereferenced.
+    address: usize,
+    /// Line and column in the input, both starting at 1, once located.
+    position: Option<(usize, usize)>,
+}
+
+impl InputError {
+    /// Error at `span`, which should be a slice of the input, where the input
+    /// did not match `expected`, e.g. "a workflow like `px{a<2006:qkq,rfg}`".
+    pub fn new(span: &str, expected: impl Into<String>) -> Self {
+        let first_line = span.lines().next().unwrap_or_default();
+        let mut snippet: String = first_line.chars().take(MAX_SNIPPET_LEN).collect();
+        if snippet.len() < first_line.len() {
+            snippet.push('…');
+        }
+        Self {
+            expected: expected.into(),
+            snippet,
+            address: span.as_ptr() as usize,
+            position: None,
+        }
+    }
+
+    /// Error at `span` of an input given as bytes, see [`InputError::new`].
+    pub fn from_bytes(span: &[u8], expected: impl Into<String>) -> Self {
+        let first_line = span.split(|&b| b == b'\n').next().unwrap_or_default();
+        Self {
+            address: span.as_ptr() as usize,
+            ..Self::new(&String::from_utf8_lossy(first_line), expected)
+        }
+    }
+
+    /// Determines the position of the error in `input`, which may be given
+    /// as bytes. The position stays unknown if the offending slice is not part
+    /// of `input`.
+    pub fn locate(&mut self, input: &[u8]) {
+        let Some(offset) = self
+            .address
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+        else {
+            return;
+        };
+        let before = &input[..offset];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |index| index + 1);
+        self.position = Some((
+            before.iter().filter(|&&b| b == b'\n').count() + 1,
+            String::from_utf8_lossy(&before[line_start..])
+                .chars()
+                .count()
+                + 1,
+        ));
+    }
+
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    /// Line of the error, starting at 1, if it was located.
+    pub fn line(&self) -> Option<usize> {
+        self.position.map(|(line, _)| line)
+    }
+
+    /// Column of the error in characters, starting at 1, if it was located.
+    pub fn column(&self) -> Option<usize> {
+        self.position.map(|(_, column)| column)
+    }
+
+    /// Renders the offending line of `input` with its neighbours and a caret
+    /// under the offending part, like a compiler diagnostic. Returns `None`
+    /// if the error was not located.
+    pub fn diagnostic(&self, input: &str) -> Option<String> {
+        let (line, column) = self.position?;
+        let lines: Vec<_> = input.lines().collect();
+        let first = line.saturating_sub(CONTEXT_LINES).max(1);
+        let last = (line + CONTEXT_LINES).min(lines.len().max(line));
+        let gutter = last.to_string().len();
+        let underline = self.snippet.trim_end_matches('…').chars().count().max(1);
+
+        let mut diagnostic = format!("{:gutter$}--> line {line}, column {column}\n", "");
+        diagnostic.push_str(&format!("{:gutter$} |\n", ""));
+        for number in first..=last {
+            let text = lines.get(number - 1).copied().unwrap_or_default();
+            diagnostic.push_str(&format!("{number:>gutter$} | {text}\n"));
+            if number == line {
+                diagnostic.push_str(&format!(
+                    "{:gutter$} | {:indent$}{} expected {}\n",
+                    "",
+                    "",
+                    "^".repeat(underline),
+                    self.expected,
+                    indent = column - 1,
+                ));
+            }
+        }
+        Some(diagnostic)
+    }
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some((line, column)) = self.position {
+            write!(f, "line {line}, column {column}: ")?;
+        }
+        write!(f, "expected {}", self.expected)?;
+        if self.snippet.is_empty() {
+            write!(f, ", found nothing")
+        } else {
+            write!(f, ", found '{}'", self.snippet)
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Locates an [`InputError`] returned by a solver constructor in its input.
+pub fn locate_input_error<T>(
+    result: anyhow::Result<T>,
+    input: impl AsRef<[u8]>,
+) -> anyhow::Result<T> {
+    result.map_err(|mut err| {
+        if let Some(input_error) = err.downcast_mut::<InputError>() {
+            input_error.locate(input.as_ref());
+        }
+        err
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{locate_input_error, InputError};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 1, 1)]
+    #[case(2, 1, 3)]
+    #[case(4, 2, 1)]
+    #[case(7, 2, 3)]
+    #[case(9, 3, 1)]
+    #[case(11, 3, 3)]
+    fn test_locate(#[case] offset: usize, #[case] line: usize, #[case] column: usize) {
+        let input = "abc\ndéf\ngh";
+        let mut err = InputError::new(&input[offset..], "something");
+        err.locate(input.as_bytes());
+        assert_eq!(err.line(), Some(line));
+        assert_eq!(err.column(), Some(column));
+    }
+
+    #[test]
+    fn test_span_outside_of_input() {
+        let input = String::from("abc");
+        let mut err = InputError::new("abc", "something");
+        err.locate(input.as_bytes());
+        assert_eq!(err.line(), None);
+        assert_eq!(err.to_string(), "expected something, found 'abc'");
+    }
+
+    #[test]
+    fn test_message() {
+        let input = "1 2\n3 x 4\n";
+        let err =
+            locate_input_error::<()>(Err(InputError::new(&input[6..], "a number").into()), input)
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 3: expected a number, found 'x 4'"
+        );
+    }
+
+    #[test]
+    fn test_locates_error_with_context() {
+        let input = "1 2\n3 x 4\n";
+        let err = locate_input_error::<()>(
+            Err(anyhow::Error::new(InputError::new(&input[6..], "a number")).context("parsing")),
+            input,
+        )
+        .unwrap_err();
+        assert_eq!(err.downcast_ref::<InputError>().unwrap().line(), Some(2));
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let input = "Game 1: 3 blue\nGame 2: 4 purple\nGame 3: 1 red\nGame 4: 2 red";
+        let mut err = InputError::new(&input[25..31], "red, green, or blue");
+        assert_eq!(err.diagnostic(input), None);
+        err.locate(input.as_bytes());
+        assert_eq!(
+            err.diagnostic(input).unwrap(),
+            " --> line 2, column 11
+  |
+1 | Game 1: 3 blue
+2 | Game 2: 4 purple
+  |           ^^^^^^ expected red, green, or blue
+3 | Game 3: 1 red
+"
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_at_end_of_input() {
+        let input = "1 2\n";
+        let mut err = InputError::new(&input[input.len()..], "a number");
+        err.locate(input.as_bytes());
+        assert_eq!(
+            err.diagnostic(input).unwrap(),
+            " --> line 2, column 1
+  |
+1 | 1 2
+2 | 
+  | ^ expected a number
+"
+        );
+    }
+
+    #[test]
+    fn test_locate_bytes() {
+        let input = b"abc\nd\xffef\ngh";
+        let mut err = InputError::from_bytes(&input[5..], "something");
+        err.locate(input);
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.column(), Some(2));
+        assert_eq!(err.snippet(), "\u{fffd}ef");
+    }
+
+    #[test]
+    fn test_truncates_snippet() {
+        let err = InputError::new(&"x".repeat(100), "something");
+        assert_eq!(err.snippet().chars().count(), 41);
+        assert!(err.snippet().ends_with('…'));
+    }
+}
@@ -1,3 +1,4 @@
+#[cfg(feature = "year2023")]
 pub mod year2023 {
     pub mod day1;
     pub mod day10;
@@ -25,6 +26,7 @@ pub mod year2023 {
     pub mod day8;
     pub mod day9;
 }
+#[cfg(feature = "year2024")]
 pub mod year2024 {
     pub mod day1;
     pub mod day10;
@@ -53,6 +55,7 @@ pub mod year2024 {
     pub mod day9;
 }
 
+#[cfg(feature = "year2025")]
 pub mod year2025 {
     pub mod day1;
     pub mod day2;
@@ -69,30 +72,584 @@ pub mod year2025 {
     // <<INSERT MARKER>>
 }
 
+mod input_error;
+mod normalization;
+mod params;
+mod rng;
+
 use ansi_term::Style;
-use std::fmt::Display;
+use anyhow::Context;
+use clap::ValueEnum;
+pub use input_error::{locate_input_error, InputError};
+pub use normalization::{unix_line_endings, Normalization};
+pub use params::{parse_solver_arg, SolverParams};
+pub use rng::{Rng, DEFAULT_SEED};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt::Display,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use crate::visualization::Frame;
 
-pub trait Solver<'input> {
+/// Solver of a day. Solvers are `Send + Sync` so that runners can solve
+/// several days and parts in parallel.
+///
+/// Runners trace the calls of the methods in `tracing` spans. Solvers can
+/// add their own spans for the phases of a part, e.g.
+/// `info_span!("construct_graph").in_scope(|| ...)`, to break down the time
+/// spent in the part with `aoc --trace`.
+pub trait Solver<'input>: Send + Sync {
+    /// Parses the input. Malformed input should be reported with an
+    /// [`InputError`] pointing to the offending part of the input.
     fn new(input: &'input str) -> anyhow::Result<Self>
     where
         Self: Sized;
+
+    /// Parses the input given as bytes. Solvers that only look at the bytes
+    /// of the input, e.g. to parse a grid, can override this to support
+    /// inputs that are not valid UTF-8 and implement [`Self::new`] with it.
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self>
+    where
+        Self: Sized,
+    {
+        Self::new(std::str::from_utf8(input).context("the input is not valid UTF-8")?)
+    }
+
+    /// Computes state shared by both parts, e.g. a parsed structure or a
+    /// distance grid, so that it is computed only once per run. Runners call
+    /// it once after [`Self::new`] and before solving any part.
+    fn precompute(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     fn solve_part_1(&self) -> anyhow::Result<Solution>;
-    fn solve_part_2(&self) -> anyhow::Result<Solution>;
+    /// Solves part 2, or returns `None` for puzzles without a part 2 like
+    /// on day 25.
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution>;
+
+    /// Solves part 2 given the answer to part 1, which the runner passes if
+    /// it solved part 1 first. Solvers whose part 2 extends part 1 can
+    /// override this to avoid recomputing it.
+    fn solve_part_2_with(&self, _part_1: &Solution) -> anyhow::Result<MaybeSolution> {
+        self.solve_part_2()
+    }
+
+    /// Normalization applied to the input before it is passed to [`Self::new`].
+    fn normalization() -> Normalization
+    where
+        Self: Sized,
+    {
+        Normalization::STANDARD
+    }
+
+    /// Title of the puzzle, available without fetching the puzzle page.
+    fn title() -> Option<&'static str>
+    where
+        Self: Sized,
+    {
+        None
+    }
+
+    /// Techniques the puzzle is about.
+    fn tags() -> &'static [Tag]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
+
+    /// Overrides constants the solver takes from the puzzle text with values
+    /// given on the command line.
+    fn set_params(&mut self, _params: &SolverParams) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Seeds randomized or heuristic choices of the solver with an [`Rng`], so
+    /// that runs are reproducible. Solvers start with [`DEFAULT_SEED`].
+    fn set_seed(&mut self, _seed: u64) {}
+
+    /// Frames visualizing the state of the solver, e.g. the paths found in a
+    /// grid. Solvers without visualization return no frames.
+    fn visualize(&self) -> anyhow::Result<Vec<Frame>> {
+        Ok(Vec::new())
+    }
+
+    /// Provides a handle to report progress of long-running parts. Solvers
+    /// that finish quickly can ignore it.
+    fn set_progress(&mut self, _progress: Progress) {}
+
+    /// Provides a handle to emit files for debugging, e.g. a graph of the
+    /// input in DOT format.
+    fn set_artifacts(&mut self, _artifacts: Artifacts) {}
+}
+
+/// Technique a puzzle is about, for filtering solvers.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Tag {
+    Grid,
+    Graph,
+    Vm,
+    Math,
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Grid => "grid",
+            Self::Graph => "graph",
+            Self::Vm => "vm",
+            Self::Math => "math",
+        })
+    }
+}
+
+/// Metadata of the solver of a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolverInfo {
+    pub year: i32,
+    pub day: u32,
+    pub title: Option<&'static str>,
+    pub tags: &'static [Tag],
+}
+
+/// Metadata of the solver for the given day, if there is one.
+pub fn solver_info(year: i32, day: u32) -> Option<SolverInfo> {
+    solver_info_dispatch!(year, day)
+}
+
+/// Receiver of progress updates, e.g. a progress bar rendered by the runner.
+pub trait ProgressSink: Send + Sync {
+    fn start(&self, total: u64);
+    fn advance(&self, delta: u64);
+    fn finish(&self);
+}
+
+/// Handle for solvers to report progress. The default handle discards all
+/// updates.
+#[derive(Clone, Default)]
+pub struct Progress(Option<Arc<dyn ProgressSink>>);
+
+impl Progress {
+    pub fn new(sink: Arc<dyn ProgressSink>) -> Self {
+        Self(Some(sink))
+    }
+
+    /// Starts tracking progress towards completing `total` iterations.
+    pub fn start(&self, total: u64) {
+        if let Some(sink) = &self.0 {
+            sink.start(total);
+        }
+    }
+
+    pub fn advance(&self, delta: u64) {
+        if let Some(sink) = &self.0 {
+            sink.advance(delta);
+        }
+    }
+
+    pub fn finish(&self) {
+        if let Some(sink) = &self.0 {
+            sink.finish();
+        }
+    }
+}
+
+/// Receiver of files emitted by solvers, e.g. a directory chosen by the user.
+pub trait ArtifactSink: Send + Sync {
+    fn write(&self, name: &str, content: &[u8]) -> anyhow::Result<()>;
+}
+
+/// Handle for solvers to emit named files like `graph.dot` instead of
+/// printing them. The default handle discards all artifacts.
+#[derive(Clone, Default)]
+pub struct Artifacts(Option<Arc<dyn ArtifactSink>>);
+
+impl Artifacts {
+    pub fn new(sink: Arc<dyn ArtifactSink>) -> Self {
+        Self(Some(sink))
+    }
+
+    /// Whether emitted artifacts are kept. Solvers can check this to skip
+    /// rendering expensive artifacts.
+    pub fn is_enabled(&self) -> bool {
+        self.0.is_some()
+    }
+
+    pub fn emit(&self, name: &str, content: impl AsRef<[u8]>) -> anyhow::Result<()> {
+        match &self.0 {
+            Some(sink) => sink.write(name, content.as_ref()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Solver registered with `#[register_solver(year = <YYYY>, day = <DD>)]` on
+/// its `SolverImpl` instead of being found by scanning the solver modules.
+pub struct Registration {
+    pub year: i32,
+    pub day: u32,
+    /// Name of the algorithm if this is an alternative to the default solver
+    /// of the day. Alternatives are expected to use the same normalization.
+    pub algo: Option<&'static str>,
+    pub new: for<'input> fn(&'input str) -> anyhow::Result<Box<dyn Solver<'input> + 'input>>,
+    /// Parses the input without boxing the solver, for benchmarks.
+    pub parse: for<'input> fn(&'input str, u64) -> anyhow::Result<Duration>,
+    pub from_bytes:
+        for<'input> fn(&'input [u8]) -> anyhow::Result<Box<dyn Solver<'input> + 'input>>,
+    pub title: fn() -> Option<&'static str>,
+    pub tags: fn() -> &'static [Tag],
+    pub normalization: fn() -> Normalization,
+}
+
+inventory::collect!(Registration);
+
+impl Registration {
+    /// Registration of the default solver of a day.
+    pub fn find(year: i32, day: u32) -> Option<&'static Self> {
+        Self::find_algo(year, day, None)
+    }
+
+    pub fn find_algo(year: i32, day: u32, algo: Option<&str>) -> Option<&'static Self> {
+        inventory::iter::<Self>.into_iter().find(|registration| {
+            registration.year == year && registration.day == day && registration.algo == algo
+        })
+    }
+
+    pub fn info(&self) -> SolverInfo {
+        SolverInfo {
+            year: self.year,
+            day: self.day,
+            title: (self.title)(),
+            tags: (self.tags)(),
+        }
+    }
+
+    pub fn bench_fns(&self) -> BenchFns {
+        BenchFns {
+            parse: self.parse,
+            new: self.new,
+        }
+    }
+}
+
+/// Days with a solver module found by scanning the `year<YYYY>` directories at
+/// compile time. Solvers registered with `#[register_solver]` are not
+/// included, use [`solver_days`] to get all days.
+pub static SOLVER_MANIFEST: &[(i32, u32)] = solver_manifest!();
+
+/// All days with a solver as `(year, day)` in ascending order.
+pub fn solver_days() -> Vec<(i32, u32)> {
+    let mut days: Vec<_> = SOLVER_MANIFEST
+        .iter()
+        .copied()
+        .chain(
+            inventory::iter::<Registration>
+                .into_iter()
+                .map(|registration| (registration.year, registration.day)),
+        )
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+/// Functions to construct the solver of a day in benchmarks.
+#[derive(Clone, Copy)]
+pub struct BenchFns {
+    /// Parses the input the given number of times with the concrete solver
+    /// type and returns the time it took, see [`time_parsing`].
+    pub parse: for<'input> fn(&'input str, u64) -> anyhow::Result<Duration>,
+    /// Creates the solver to benchmark the parts with.
+    pub new: for<'input> fn(&'input str) -> anyhow::Result<Box<dyn Solver<'input> + 'input>>,
+}
+
+/// Creates `iterations` solvers with `new` and returns the time it took. The
+/// solvers are only dropped after the measurement, so that only parsing is
+/// measured.
+pub fn time_parsing<'input, S>(
+    new: fn(&'input str) -> anyhow::Result<S>,
+    input: &'input str,
+    iterations: u64,
+) -> anyhow::Result<Duration> {
+    let mut solvers = Vec::with_capacity(iterations.try_into()?);
+    let start = Instant::now();
+    for _ in 0..iterations {
+        solvers.push(new(input)?);
+    }
+    let elapsed = start.elapsed();
+    drop(solvers);
+    Ok(elapsed)
+}
+
+/// Benchmark functions of the solver for the given day, if there is one.
+pub fn bench_fns(year: i32, day: u32) -> Option<BenchFns> {
+    solver_bench_dispatch!(year, day)
+}
+
+/// Creates the solver for the given day from an already normalized input.
+pub fn new_solver(year: i32, day: u32, input: &str) -> anyhow::Result<Box<dyn Solver<'_> + '_>> {
+    solver_dispatch!(input, year, day)
+}
+
+/// Names of the alternative algorithms registered for the given day, sorted.
+pub fn algorithms(year: i32, day: u32) -> Vec<&'static str> {
+    let mut algorithms: Vec<_> = inventory::iter::<Registration>
+        .into_iter()
+        .filter(|registration| registration.year == year && registration.day == day)
+        .filter_map(|registration| registration.algo)
+        .collect();
+    algorithms.sort_unstable();
+    algorithms
+}
+
+/// Creates the solver implementing the named alternative algorithm for the
+/// given day, or the default solver if no algorithm is given.
+pub fn new_algo_solver<'input>(
+    year: i32,
+    day: u32,
+    algo: Option<&str>,
+    input: &'input [u8],
+) -> anyhow::Result<Box<dyn Solver<'input> + 'input>> {
+    let Some(algo) = algo else {
+        return new_solver_from_bytes(year, day, input);
+    };
+    let registration = Registration::find_algo(year, day, Some(algo)).ok_or_else(|| {
+        let available = algorithms(year, day);
+        if available.is_empty() {
+            anyhow::anyhow!("day {day} of year {year} has no alternative algorithms")
+        } else {
+            anyhow::anyhow!(
+                "no algorithm '{algo}' for day {day} of year {year}, available: {}",
+                available.join(", ")
+            )
+        }
+    })?;
+    locate_input_error((registration.from_bytes)(input), input)
+}
+
+/// Creates the solver for the given day from an already normalized input that
+/// is not necessarily valid UTF-8.
+pub fn new_solver_from_bytes(
+    year: i32,
+    day: u32,
+    input: &[u8],
+) -> anyhow::Result<Box<dyn Solver<'_> + '_>> {
+    solver_dispatch_bytes!(input, year, day)
+}
+
+/// Normalizes an input for the solver of the given day.
+pub fn normalize_input(year: i32, day: u32, input: Vec<u8>) -> Vec<u8> {
+    let normalization: Normalization = normalization_dispatch!(year, day);
+    normalization.apply(input)
+}
+
+/// Example input embedded at compile time from a `day<DD>-<label>.example`
+/// file and its optional `day<DD>-<label>.expected` sidecar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub year: i32,
+    pub day: u32,
+    pub label: &'static str,
+    pub input: &'static str,
+    pub expected: Option<&'static str>,
+}
+
+impl Example {
+    /// Expected answers from the `.expected` sidecar, if there is one.
+    pub fn expected_answers(&self) -> anyhow::Result<Option<ExpectedAnswers>> {
+        self.expected
+            .map(|expected| {
+                toml::from_str(expected).with_context(|| {
+                    format!(
+                        "parsing expected answers of example '{}' of {}, day {}",
+                        self.label, self.year, self.day
+                    )
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Contents of an `.expected` sidecar: the answers to the parts of an example
+/// and the solver arguments that the example needs. Answers match solutions
+/// by their [`Solution::answer_str`], so integers may also be quoted.
+///
+/// ```toml
+/// part1 = 142
+/// part2 = "281"
+///
+/// [params]
+/// steps = 6
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectedAnswers {
+    pub part1: Option<Value>,
+    pub part2: Option<Value>,
+    #[serde(default)]
+    pub params: BTreeMap<String, Value>,
+}
+
+impl ExpectedAnswers {
+    pub fn part(&self, part: u8) -> Option<&Value> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+pub static EXAMPLES: &[Example] = examples_dispatch!();
+
+pub fn find_example(year: i32, day: u32, label: &str) -> Option<&'static Example> {
+    EXAMPLES
+        .iter()
+        .find(|example| example.year == year && example.day == day && example.label == label)
+}
+
+/// Answer to a part that might not exist.
+pub type MaybeSolution = Option<Solution>;
+
+/// Typed answer to a part.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Value {
+    Integer(i128),
+    Text(String),
+}
+
+impl Value {
+    /// The value as it is submitted to the AoC website.
+    pub fn answer_str(&self) -> Cow<'_, str> {
+        match self {
+            Self::Integer(value) => Cow::Owned(value.to_string()),
+            Self::Text(value) => Cow::Borrowed(value),
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Integer(value) => value.fmt(f),
+            Self::Text(value) => value.fmt(f),
+        }
+    }
+}
+
+/// Deserializes from an integer or a string, e.g. in `.expected` sidecars.
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValueVisitor;
+
+        impl serde::de::Visitor<'_> for ValueVisitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("an integer or a string")
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Value, E> {
+                Ok(value.into())
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Value, E> {
+                Ok(value.into())
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Value, E> {
+                Ok(value.into())
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Value {
+                fn from(value: $int) -> Self {
+                    Self::Integer(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Value {
+    fn from(value: usize) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl From<isize> for Value {
+    fn from(value: isize) -> Self {
+        Self::Integer(value as i128)
+    }
+}
+
+impl From<u128> for Value {
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Self::Text(value.to_string()), Self::Integer)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::Text(value.into())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Solution {
     description: &'static str,
-    solution: String,
+    value: Value,
 }
 
 impl Solution {
-    pub fn with_description(description: &'static str, solution: String) -> Self {
+    pub fn with_description(description: &'static str, value: impl Into<Value>) -> Self {
         Self {
             description,
-            solution,
+            value: value.into(),
         }
     }
+
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// The answer as it is submitted to the AoC website.
+    pub fn answer_str(&self) -> Cow<'_, str> {
+        self.value.answer_str()
+    }
+
+    /// Whether the answer is the `expected` one. Like the answer store and
+    /// the golden answers, this compares the submitted strings, so
+    /// `Value::Text("281")` matches `Value::Integer(281)`.
+    pub fn matches(&self, expected: &Value) -> bool {
+        self.answer_str() == expected.answer_str()
+    }
 }
 
 impl Display for Solution {
@@ -100,7 +657,230 @@ impl Display for Solution {
         f.write_fmt(format_args!(
             "{}: {}",
             self.description,
-            Style::new().bold().paint(&self.solution)
+            Style::new().bold().paint(self.answer_str())
         ))
     }
-}
\ No newline at end of file
+}
+
+/// Solves an example with the solver arguments from its `.expected` sidecar
+/// and compares the answers to the expected ones. Only the examples of the
+/// enabled years generate tests calling it.
+#[cfg(all(
+    test,
+    any(feature = "year2023", feature = "year2024", feature = "year2025")
+))]
+fn check_example(year: i32, day: u32, label: &str) -> anyhow::Result<()> {
+    check_example_with_algo(year, day, None, label)
+}
+
+/// Like [`check_example`], but with the alternative algorithm `algo`.
+#[cfg(all(
+    test,
+    any(feature = "year2023", feature = "year2024", feature = "year2025")
+))]
+fn check_example_with_algo(
+    year: i32,
+    day: u32,
+    algo: Option<&str>,
+    label: &str,
+) -> anyhow::Result<()> {
+    let example = find_example(year, day, label)
+        .ok_or_else(|| anyhow::anyhow!("no example '{label}' for day {day} of year {year}"))?;
+    let expected = example
+        .expected_answers()?
+        .ok_or_else(|| anyhow::anyhow!("example '{label}' has no expected answers"))?;
+    let input = normalize_input(year, day, example.input.into());
+    let mut solver = new_algo_solver(year, day, algo, &input)?;
+    let params: SolverParams = expected
+        .params
+        .iter()
+        .map(|(key, value)| (key.clone(), value.to_string()))
+        .collect();
+    solver.set_params(&params)?;
+    params.check_all_used()?;
+    solver.precompute()?;
+    for part in [1, 2] {
+        let Some(expected) = expected.part(part) else {
+            continue;
+        };
+        let solution = match part {
+            1 => Some(solver.solve_part_1()?),
+            _ => solver.solve_part_2()?,
+        }
+        .ok_or_else(|| anyhow::anyhow!("part {part} has no solution"))?;
+        assert_eq!(solution.answer_str(), expected.answer_str(), "part {part}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ExpectedAnswers, Solution, Value};
+
+    example_tests!();
+
+    #[cfg(feature = "year2023")]
+    #[test]
+    fn test_solver_days_are_sorted() {
+        use super::{solver_days, SOLVER_MANIFEST};
+
+        let days = solver_days();
+        assert!(days.contains(&(2023, 10)));
+        assert!(days.windows(2).all(|days| days[0] < days[1]));
+        assert!(SOLVER_MANIFEST.windows(2).all(|days| days[0] < days[1]));
+    }
+
+    #[cfg(feature = "year2025")]
+    #[test]
+    fn test_registered_solver() -> anyhow::Result<()> {
+        use super::{solver_days, solver_info, Registration, SOLVER_MANIFEST};
+
+        let registration = Registration::find(2025, 12).expect("2025 day 12 registers itself");
+        assert!(solver_days().contains(&(2025, 12)));
+        assert!(!SOLVER_MANIFEST.contains(&(2025, 12)));
+        assert_eq!(
+            solver_info(2025, 12).and_then(|info| info.title),
+            Some("Christmas Tree Farm")
+        );
+        let solver = (registration.new)("")?;
+        assert_eq!(solver.solve_part_1()?.answer_str(), "0");
+        Ok(())
+    }
+
+    #[cfg(feature = "year2023")]
+    #[test]
+    fn test_solver_info() {
+        use super::{solver_info, Tag};
+
+        let info = solver_info(2023, 10).unwrap();
+        assert_eq!(info.title, Some("Pipe Maze"));
+        assert_eq!(info.tags, &[Tag::Grid]);
+        assert!(solver_info(2023, 26).is_none());
+    }
+
+    #[cfg(all(feature = "year2023", feature = "year2025"))]
+    #[test]
+    fn test_bench_fns() -> anyhow::Result<()> {
+        use super::bench_fns;
+
+        let scanned = bench_fns(2023, 2).expect("2023 day 2 has a solver");
+        (scanned.parse)("Game 1: 3 blue", 2)?;
+        assert!((scanned.parse)("Game 1: 3 purple", 1).is_err());
+        let solver = (scanned.new)("Game 1: 3 blue")?;
+        assert_eq!(solver.solve_part_1()?.answer_str(), "1");
+
+        let registered = bench_fns(2025, 12).expect("2025 day 12 registers itself");
+        (registered.parse)("", 1)?;
+        assert!(bench_fns(2015, 1).is_none());
+        Ok(())
+    }
+
+    #[cfg(not(feature = "year2023"))]
+    #[test]
+    fn test_disabled_year_is_not_dispatched() {
+        use super::{bench_fns, solver_days, solver_info, EXAMPLES, SOLVER_MANIFEST};
+
+        assert!(SOLVER_MANIFEST.iter().all(|&(year, _)| year != 2023));
+        assert!(solver_days().iter().all(|&(year, _)| year != 2023));
+        assert!(EXAMPLES.iter().all(|example| example.year != 2023));
+        assert!(solver_info(2023, 10).is_none());
+        assert!(bench_fns(2023, 10).is_none());
+        assert!(super::new_solver(2023, 10, "S").is_err());
+    }
+
+    #[test]
+    fn test_new_solver_for_unknown_day() {
+        assert!(super::new_solver(2015, 1, "").is_err());
+    }
+
+    #[cfg(feature = "year2023")]
+    #[test]
+    fn test_new_solver_from_bytes() {
+        // Day 10 overrides `from_bytes`, day 2 falls back to `new`.
+        let solver = super::new_solver_from_bytes(2023, 10, b"S-7\n|\xff|\nL-J").unwrap();
+        assert_eq!(solver.solve_part_1().unwrap().answer_str(), "4");
+        assert!(super::new_solver_from_bytes(2023, 2, b"Game 1: 3 \xff").is_err());
+        assert!(super::new_solver_from_bytes(2023, 2, b"Game 1: 3 blue").is_ok());
+        // The runner creates solvers from bytes, too.
+        let solver = super::new_algo_solver(2023, 10, None, b"S-7\n|\xff|\nL-J").unwrap();
+        assert_eq!(solver.solve_part_1().unwrap().answer_str(), "4");
+    }
+
+    #[cfg(feature = "year2023")]
+    #[test]
+    fn test_new_solver_locates_input_error() {
+        let err = super::new_solver(2023, 2, "Game 1: 3 blue\nGame 2: 4 purple")
+            .err()
+            .unwrap();
+        let err = err.downcast_ref::<super::InputError>().unwrap();
+        assert_eq!((err.line(), err.column()), (Some(2), Some(11)));
+        assert_eq!(err.snippet(), "purple");
+    }
+
+    #[cfg(feature = "year2023")]
+    #[test]
+    fn test_alternative_algorithm() -> anyhow::Result<()> {
+        use super::{algorithms, check_example_with_algo, new_algo_solver, solver_days};
+
+        assert_eq!(algorithms(2023, 17), ["bucket-queue"]);
+        assert!(algorithms(2023, 10).is_empty());
+        assert_eq!(
+            solver_days()
+                .iter()
+                .filter(|&&day| day == (2023, 17))
+                .count(),
+            1
+        );
+        check_example_with_algo(2023, 17, Some("bucket-queue"), "1")?;
+        check_example_with_algo(2023, 17, Some("bucket-queue"), "2")?;
+        assert!(new_algo_solver(2023, 17, Some("fibonacci-heap"), b"1").is_err());
+        assert!(new_algo_solver(2023, 10, Some("bucket-queue"), b"S").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_solution_values() {
+        let solution = Solution::with_description("Sum", 42usize);
+        assert_eq!(solution.value(), &Value::Integer(42));
+        assert_eq!(solution.answer_str(), "42");
+
+        let solution = Solution::with_description("Code", "abc");
+        assert_eq!(solution.value(), &Value::Text("abc".into()));
+        assert_eq!(solution.answer_str(), "abc");
+
+        let solution = Solution::with_description("Large", u128::MAX);
+        assert_eq!(solution.answer_str(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn test_solution_matches_quoted_integer() -> anyhow::Result<()> {
+        let expected: ExpectedAnswers = toml::from_str("part1 = 142\npart2 = \"281\"\n")?;
+        let solution = Solution::with_description("Sum", 142usize);
+        assert!(solution.matches(expected.part(1).unwrap()));
+        let solution = Solution::with_description("Sum", 281usize);
+        assert!(solution.matches(expected.part(2).unwrap()));
+        assert!(!solution.matches(&Value::Text("0281".into())));
+        Ok(())
+    }
+
+    #[cfg(all(feature = "year2023", feature = "year2024"))]
+    #[test]
+    fn test_examples_are_embedded() {
+        use super::find_example;
+
+        let example = find_example(2023, 10, "2a").expect("example should be embedded");
+        assert_eq!(example.input, include_str!("./year2023/day10-2a.example"));
+        assert!(find_example(2024, 15, "1-large").is_some());
+        assert!(find_example(2023, 10, "missing").is_none());
+    }
+
+    #[test]
+    fn test_expected_answers() -> anyhow::Result<()> {
+        let expected: ExpectedAnswers = toml::from_str("part2 = \"abc\"\n\n[params]\nsteps = 6\n")?;
+        assert_eq!(expected.part1, None);
+        assert_eq!(expected.part2, Some(Value::Text("abc".into())));
+        assert_eq!(expected.params["steps"], Value::Integer(6));
+        assert!(toml::from_str::<ExpectedAnswers>("part3 = 1\n").is_err());
+        Ok(())
+    }
+}
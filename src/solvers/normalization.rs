@@ -0,0 +1,116 @@
+//! Uniform preprocessing of puzzle inputs before they are passed to solvers.
+//!
+//! Inputs may have been fetched or edited on different platforms, so line
+//! endings and trailing whitespace can differ. Normalizing them in one place
+//! lets solvers rely on a single canonical form.
+
+/// Capability flags selecting the normalization steps applied to an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// Convert CRLF line endings to LF.
+    pub unix_line_endings: bool,
+    /// Remove trailing newlines at the end of the input.
+    pub strip_trailing_newlines: bool,
+    /// Remove trailing spaces and tabs from each line. Implies LF line
+    /// endings.
+    pub trim_line_ends: bool,
+}
+
+impl Normalization {
+    /// Normalization applied unless a solver requests otherwise.
+    pub const STANDARD: Self = Self {
+        unix_line_endings: true,
+        strip_trailing_newlines: true,
+        trim_line_ends: false,
+    };
+
+    /// Passes the input through unchanged, for solvers that handle line
+    /// endings and whitespace themselves.
+    pub const NONE: Self = Self {
+        unix_line_endings: false,
+        strip_trailing_newlines: false,
+        trim_line_ends: false,
+    };
+
+    /// Normalizes the input. Only ASCII whitespace is removed, so valid
+    /// UTF-8 stays valid.
+    pub fn apply(&self, mut input: Vec<u8>) -> Vec<u8> {
+        if self.unix_line_endings || self.trim_line_ends {
+            input = unix_line_endings(input);
+        }
+        if self.trim_line_ends {
+            let mut trimmed = Vec::with_capacity(input.len());
+            for (index, line) in input.split(|&b| b == b'\n').enumerate() {
+                if index > 0 {
+                    trimmed.push(b'\n');
+                }
+                let len = line
+                    .iter()
+                    .rposition(|&b| b != b' ' && b != b'\t')
+                    .map_or(0, |index| index + 1);
+                trimmed.extend_from_slice(&line[..len]);
+            }
+            input = trimmed;
+        }
+        if self.strip_trailing_newlines {
+            let len = input
+                .iter()
+                .rposition(|&b| b != b'\n' && b != b'\r')
+                .map_or(0, |index| index + 1);
+            input.truncate(len);
+        }
+        input
+    }
+}
+
+/// Converts CRLF line endings to LF.
+pub fn unix_line_endings(input: Vec<u8>) -> Vec<u8> {
+    if !input.contains(&b'\r') {
+        return input;
+    }
+    let mut converted = Vec::with_capacity(input.len());
+    for (index, &b) in input.iter().enumerate() {
+        if b != b'\r' || input.get(index + 1) != Some(&b'\n') {
+            converted.push(b);
+        }
+    }
+    converted
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Normalization;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Normalization::STANDARD, "a \r\nb\r\n\r\n", "a \nb")]
+    #[case(Normalization::NONE, "a \r\nb\r\n", "a \r\nb\r\n")]
+    #[case(
+        Normalization { trim_line_ends: true, ..Normalization::STANDARD },
+        "a \t\r\n b  \n",
+        "a\n b"
+    )]
+    #[case(
+        Normalization { trim_line_ends: true, strip_trailing_newlines: false, ..Normalization::NONE },
+        "a \r\n\tb\t\n",
+        "a\n\tb\n"
+    )]
+    #[case(
+        Normalization { strip_trailing_newlines: false, ..Normalization::STANDARD },
+        "a\r\nb\r\n",
+        "a\nb\n"
+    )]
+    fn test_apply(
+        #[case] normalization: Normalization,
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(normalization.apply(input.into()), expected.as_bytes());
+    }
+}
@@ -0,0 +1,120 @@
+//! Overrides for constants that solvers take from the puzzle text.
+//!
+//! Puzzles often state constants like a number of steps or the bounds of an
+//! area, and the examples use different values than the actual inputs. Solvers
+//! read such constants from [`SolverParams`] so that they can be changed with
+//! `--solver-arg key=value`, e.g. to solve an example or to experiment.
+
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    str::FromStr,
+};
+
+use anyhow::anyhow;
+
+/// Parameter values given on the command line.
+#[derive(Debug, Default, Clone)]
+pub struct SolverParams {
+    values: BTreeMap<String, String>,
+    /// Keys read by the solver, to detect misspelled parameters.
+    used: RefCell<BTreeSet<String>>,
+}
+
+impl SolverParams {
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Value of the parameter, or `default` if it was not given.
+    pub fn get<T>(&self, key: &str, default: T) -> anyhow::Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.used.borrow_mut().insert(key.into());
+        match self.values.get(key) {
+            Some(value) => value
+                .parse()
+                .map_err(|err| anyhow!("invalid value '{value}' for solver argument {key}: {err}")),
+            None => Ok(default),
+        }
+    }
+
+    /// Fails if a parameter was given that the solver did not read.
+    pub fn check_all_used(&self) -> anyhow::Result<()> {
+        let used = self.used.borrow();
+        match self.values.keys().find(|key| !used.contains(*key)) {
+            Some(key) => Err(anyhow!("the solver has no parameter {key}")),
+            None => Ok(()),
+        }
+    }
+}
+
+impl FromIterator<(String, String)> for SolverParams {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+            used: RefCell::default(),
+        }
+    }
+}
+
+/// Parses a solver argument in the form `key=value`.
+pub fn parse_solver_arg(arg: &str) -> anyhow::Result<(String, String)> {
+    let (key, value) = arg
+        .split_once('=')
+        .ok_or_else(|| anyhow!("expected KEY=VALUE"))?;
+    if key.is_empty() {
+        return Err(anyhow!("the key must not be empty"));
+    }
+    Ok((key.into(), value.into()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_solver_arg, SolverParams};
+
+    #[test]
+    fn test_get() -> anyhow::Result<()> {
+        let params: SolverParams = [("steps".to_string(), "6".to_string())]
+            .into_iter()
+            .collect();
+        assert_eq!(params.get("steps", 64)?, 6);
+        assert_eq!(params.get("threshold", 100)?, 100);
+        params.check_all_used()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_value() {
+        let params: SolverParams = [("steps".to_string(), "many".to_string())]
+            .into_iter()
+            .collect();
+        assert!(params.get("steps", 64).is_err());
+    }
+
+    #[test]
+    fn test_unused_parameter() {
+        let params: SolverParams = [("stepz".to_string(), "6".to_string())]
+            .into_iter()
+            .collect();
+        params.get("steps", 64).unwrap();
+        assert!(params.check_all_used().is_err());
+    }
+
+    #[test]
+    fn test_parse_solver_arg() {
+        assert_eq!(
+            parse_solver_arg("bounds=7").unwrap(),
+            ("bounds".into(), "7".into())
+        );
+        assert_eq!(
+            parse_solver_arg("a=b=c").unwrap(),
+            ("a".into(), "b=c".into())
+        );
+        assert!(parse_solver_arg("steps").is_err());
+        assert!(parse_solver_arg("=6").is_err());
+    }
+}
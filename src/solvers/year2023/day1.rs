@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 use regex::Regex;
 
 #[derive(Debug)]
@@ -7,6 +7,10 @@ pub struct SolverImpl<'a> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Trebuchet?!")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self>
     where
         Self: Sized,
@@ -34,11 +38,11 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .sum();
         Ok(Solution::with_description(
             "Calibration sum (part 1)",
-            solution.to_string(),
+            solution,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         lazy_static! {
             static ref DIGITS: Regex =
                 Regex::new("[1-9]|one|two|three|four|five|six|seven|eight|nine").unwrap();
@@ -62,7 +66,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             })
             .sum();
 
-        Ok(Solution::with_description("Calibration sum (part 2)", solution.to_string()))
+        Ok(Some(Solution::with_description(
+            "Calibration sum (part 2)",
+            solution,
+        )))
     }
 }
 
@@ -84,24 +91,3 @@ fn parse_spelled_digit(digit: &str) -> u8 {
         _ => panic!("not a digit"),
     }
 }
-
-#[cfg(test)]
-mod test {
-    use crate::solvers::Solver;
-
-    use super::SolverImpl;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day1-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "142");
-        Ok(())
-    }
-
-    #[test]
-    fn test_exapmle_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day1-2.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "281");
-        Ok(())
-    }
-}
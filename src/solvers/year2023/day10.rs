@@ -3,23 +3,16 @@ use std::{collections::HashSet, ops::Index};
 use anyhow::anyhow;
 
 use crate::{
-    datastructures::{grid::GridView, iterators::NeighborIterator2d},
-    solvers::{Solution, Solver},
+    datastructures::{direction::Direction4, grid::GridView, iterators::NeighborIterator2d},
+    solvers::{MaybeSolution, Solution, Solver, Tag},
+    visualization::{Frame, Image},
 };
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum Dir {
-    North,
-    South,
-    East,
-    West,
-}
-
 struct PipesIterator<'a, T> {
     grid: &'a GridView<T>,
     row: usize,
     col: usize,
-    next_dir: Option<Dir>,
+    next_dir: Option<Direction4>,
 }
 
 impl<'a, T> TryFrom<&'a GridView<T>> for PipesIterator<'a, T>
@@ -29,27 +22,25 @@ where
     type Error = anyhow::Error;
 
     fn try_from(grid: &'a GridView<T>) -> Result<Self, Self::Error> {
-        let start_tile = grid.nth_index(
-            grid.iter()
-                .enumerate()
-                .find(|&(_, value)| value == b'S')
-                .ok_or_else(|| anyhow!("start position is required"))?
-                .0,
-        );
+        let start_tile = grid
+            .indexed_iter()
+            .find(|&(_, value)| value == b'S')
+            .ok_or_else(|| anyhow!("start position is required"))?
+            .0;
 
         let start_dir =
             if start_tile.0 > 0 && b"F7|".contains(&grid[(start_tile.0 - 1, start_tile.1)]) {
-                Dir::North
+                Direction4::Up
             } else if start_tile.0 < grid.height()
                 && b"LJ|".contains(&grid[(start_tile.0 + 1, start_tile.1)])
             {
-                Dir::South
+                Direction4::Down
             } else if start_tile.1 > 0 && b"FL-".contains(&grid[(start_tile.0, start_tile.1 - 1)]) {
-                Dir::West
+                Direction4::Left
             } else if start_tile.1 < grid.width()
                 && b"7J-".contains(&grid[(start_tile.0, start_tile.1 + 1)])
             {
-                Dir::East
+                Direction4::Right
             } else {
                 anyhow::bail!("Pipe leaving start tile required.")
             };
@@ -70,29 +61,23 @@ where
     type Item = (usize, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next_tile = match self.next_dir {
-            Some(Dir::North) if self.row > 0 => Some((self.row - 1, self.col)),
-            Some(Dir::South) if self.row < self.grid.height() => Some((self.row + 1, self.col)),
-            Some(Dir::East) if self.col < self.grid.width() => Some((self.row, self.col + 1)),
-            Some(Dir::West) if self.col > 0 => Some((self.row, self.col - 1)),
-            _ => None,
-        }?;
+        let next_tile = self.next_dir?.step((self.row, self.col), self.grid.size())?;
         let shape = self.grid[next_tile];
 
         self.next_dir = match (shape, &self.next_dir) {
             (b'S', _) => None,
-            (b'F', Some(Dir::North)) => Some(Dir::East),
-            (b'F', Some(Dir::West)) => Some(Dir::South),
-            (b'L', Some(Dir::South)) => Some(Dir::East),
-            (b'L', Some(Dir::West)) => Some(Dir::North),
-            (b'7', Some(Dir::East)) => Some(Dir::South),
-            (b'7', Some(Dir::North)) => Some(Dir::West),
-            (b'J', Some(Dir::South)) => Some(Dir::West),
-            (b'J', Some(Dir::East)) => Some(Dir::North),
-            (b'|', Some(Dir::North)) => Some(Dir::North),
-            (b'|', Some(Dir::South)) => Some(Dir::South),
-            (b'-', Some(Dir::East)) => Some(Dir::East),
-            (b'-', Some(Dir::West)) => Some(Dir::West),
+            (b'F', Some(Direction4::Up)) => Some(Direction4::Right),
+            (b'F', Some(Direction4::Left)) => Some(Direction4::Down),
+            (b'L', Some(Direction4::Down)) => Some(Direction4::Right),
+            (b'L', Some(Direction4::Left)) => Some(Direction4::Up),
+            (b'7', Some(Direction4::Right)) => Some(Direction4::Down),
+            (b'7', Some(Direction4::Up)) => Some(Direction4::Left),
+            (b'J', Some(Direction4::Down)) => Some(Direction4::Left),
+            (b'J', Some(Direction4::Right)) => Some(Direction4::Up),
+            (b'|', Some(Direction4::Up)) => Some(Direction4::Up),
+            (b'|', Some(Direction4::Down)) => Some(Direction4::Down),
+            (b'-', Some(Direction4::Right)) => Some(Direction4::Right),
+            (b'-', Some(Direction4::Left)) => Some(Direction4::Left),
             _ => None,
         };
 
@@ -175,11 +160,7 @@ impl FloodFill {
 
 fn enlarge(grid: &GridView<&[u8]>) -> GridView<Vec<u8>> {
     let enlarged_width = grid.width() * 2 - 1;
-    let mut enlarged_grid = GridView::from_vec(
-        enlarged_width,
-        0,
-        vec![b'.'; enlarged_width * (grid.height() * 2 - 1)],
-    );
+    let mut enlarged_grid = GridView::filled((grid.height() * 2 - 1, enlarged_width), b'.');
 
     for i in 0..enlarged_grid.height() {
         for j in 0..enlarged_grid.width() {
@@ -210,8 +191,20 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Pipe Maze")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let grid = GridView::from_separated(b'\n', input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let grid = GridView::from_separated(b'\n', input);
 
         Ok(Self { grid })
     }
@@ -220,21 +213,62 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
         let loop_length = PipesIterator::try_from(&self.grid)?.count();
         Ok(Solution::with_description(
             "Distance of farthest point from starting position",
-            (loop_length / 2).to_string(),
+            loop_length / 2,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        let enlarged = enlarge(&self.grid);
-        let inner_tiles = FloodFill::count_inner(enlarged)?;
-        let num_inner_tiles = inner_tiles
-            .iter()
-            .filter(|(row, col)| row % 2 == 0 && col % 2 == 0)
-            .count();
-        Ok(Solution::with_description(
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description(
             "Tiles inside the loop",
-            num_inner_tiles.to_string(),
-        ))
+            self.inner_tiles()?.len(),
+        )))
+    }
+
+    /// Draws the loop with box-drawing characters and marks the enclosed
+    /// tiles, once as text and once as an image.
+    fn visualize(&self) -> anyhow::Result<Vec<Frame>> {
+        const LOOP_COLOR: [u8; 3] = [255, 255, 255];
+        const INNER_COLOR: [u8; 3] = [0, 200, 0];
+
+        let pipeloop: HashSet<_> = PipesIterator::try_from(&self.grid)?.collect();
+        let inner_tiles = self.inner_tiles()?;
+
+        let mut text = String::with_capacity((self.grid.width() + 1) * self.grid.height());
+        let mut image = Image::new(self.grid.width(), self.grid.height(), [0, 0, 0]);
+        for row in 0..self.grid.height() {
+            for col in 0..self.grid.width() {
+                let tile = (row, col);
+                if pipeloop.contains(&tile) {
+                    text.push(match self.grid[tile] {
+                        b'F' => '┌',
+                        b'7' => '┐',
+                        b'L' => '└',
+                        b'J' => '┘',
+                        b'|' => '│',
+                        b'-' => '─',
+                        _ => 'S',
+                    });
+                    image.set(tile, LOOP_COLOR);
+                } else if inner_tiles.contains(&tile) {
+                    text.push('I');
+                    image.set(tile, INNER_COLOR);
+                } else {
+                    text.push('.');
+                }
+            }
+            text.push('\n');
+        }
+        Ok(vec![Frame::Text(text), Frame::Image(image)])
+    }
+}
+
+impl SolverImpl<'_> {
+    fn inner_tiles(&self) -> anyhow::Result<HashSet<(usize, usize)>> {
+        Ok(FloodFill::count_inner(enlarge(&self.grid))?
+            .into_iter()
+            .filter(|(row, col)| row % 2 == 0 && col % 2 == 0)
+            .map(|(row, col)| (row / 2, col / 2))
+            .collect())
     }
 }
 
@@ -242,25 +276,15 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
 mod test {
     use super::SolverImpl;
     use crate::solvers::Solver;
+    use crate::visualization::assert_goldens;
 
     #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day10-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "8");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2a() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day10-2a.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "4");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2b() -> anyhow::Result<()> {
+    fn test_visualization_golden() -> anyhow::Result<()> {
         let solver = SolverImpl::new(include_str!("./day10-2b.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "8");
+        assert_goldens(
+            "src/solvers/year2023/day10-2b.visualization",
+            &solver.visualize()?,
+        );
         Ok(())
     }
 }
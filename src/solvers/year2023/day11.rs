@@ -1,5 +1,5 @@
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use std::collections::HashSet;
 
 fn to_range(a: usize, b: usize) -> std::ops::Range<usize> {
@@ -55,18 +55,23 @@ impl SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Cosmic Expansion")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let grid = GridView::from_separated(b'\n', input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let grid = GridView::from_separated(b'\n', input);
         let galaxies: Vec<_> = grid
-            .iter()
-            .enumerate()
-            .filter_map(|(i, cell)| {
-                if cell == b'#' {
-                    Some(grid.nth_index(i))
-                } else {
-                    None
-                }
-            })
+            .indexed_iter()
+            .filter_map(|(pos, cell)| (cell == b'#').then_some(pos))
             .collect();
         let galaxy_rows: HashSet<_> = galaxies.iter().map(|galaxy| galaxy.0).collect();
         let galaxy_cols: HashSet<_> = galaxies.iter().map(|galaxy| galaxy.1).collect();
@@ -81,15 +86,15 @@ impl<'input> Solver<'input> for SolverImpl {
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Part 1",
-            self.sum_shortest_paths(2).to_string(),
+            self.sum_shortest_paths(2),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description(
             "Part 2",
-            self.sum_shortest_paths(1_000_000).to_string(),
-        ))
+            self.sum_shortest_paths(1_000_000),
+        )))
     }
 }
 
@@ -98,13 +103,6 @@ mod test {
     use super::SolverImpl;
     use crate::solvers::Solver;
 
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day11-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "374");
-        Ok(())
-    }
-
     #[test]
     fn test_example_part_2a() -> anyhow::Result<()> {
         let solver = SolverImpl::new(include_str!("./day11-1.example"))?;
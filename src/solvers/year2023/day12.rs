@@ -1,6 +1,5 @@
-use crate::solvers::{Solution, Solver};
-use anyhow::anyhow;
-use std::collections::HashMap;
+use crate::datastructures::hash::FastMap;
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum StateClass {
@@ -130,7 +129,7 @@ impl Default for State {
 struct ArrangementCounter<'input> {
     input: &'input [u8],
     groups: &'input [usize],
-    cache: HashMap<(usize, State), usize>,
+    cache: FastMap<(usize, State), usize>,
 }
 
 impl<'input> ArrangementCounter<'input> {
@@ -138,7 +137,7 @@ impl<'input> ArrangementCounter<'input> {
         Self {
             input,
             groups,
-            cache: HashMap::new(),
+            cache: FastMap::default(),
         }
         .process()
     }
@@ -178,16 +177,24 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Hot Springs")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let lines = input
             .lines()
             .map(|line| {
-                let (springs, group_def) = line
-                    .split_once(' ')
-                    .ok_or_else(|| anyhow!("invalid input line"))?;
+                let (springs, group_def) = line.split_once(' ').ok_or_else(|| {
+                    InputError::new(line, "springs and groups like ???.### 1,1,3")
+                })?;
                 let groups = group_def
                     .split(',')
-                    .map(|group| group.parse::<usize>())
+                    .map(|group| {
+                        group
+                            .parse::<usize>()
+                            .map_err(|_| InputError::new(group, "a group size"))
+                    })
                     .collect::<Result<Vec<_>, _>>()?;
                 Ok(ParsedLine { springs, groups })
             })
@@ -203,11 +210,11 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .sum();
         Ok(Solution::with_description(
             "Possible arrangements sum (part 1)",
-            num_arrangements.to_string(),
+            num_arrangements,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let num_arrangements: usize = self
             .lines
             .iter()
@@ -219,29 +226,9 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
                 ArrangementCounter::count(springs, &groups)
             })
             .sum();
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Possible arrangements sum (part 2)",
-            num_arrangements.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day12-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "21");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day12-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "525152");
-        Ok(())
+            num_arrangements,
+        )))
     }
 }
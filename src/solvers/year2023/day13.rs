@@ -1,5 +1,5 @@
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 
 fn is_horizontal_reflection(grid: &GridView<&[u8]>, index: usize, expected_smudges: usize) -> bool {
     let mut top = index;
@@ -68,6 +68,14 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Point of Incidence")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let grids = input
             .split("\n\n")
@@ -83,35 +91,15 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .iter()
             .filter_map(|grid| find_grid_reflection(grid, 0))
             .sum();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let result: usize = self
             .grids
             .iter()
             .filter_map(|grid| find_grid_reflection(grid, 1))
             .sum();
-        Ok(Solution::with_description("Part 2", result.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day13-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "405");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day13-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "400");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
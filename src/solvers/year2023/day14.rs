@@ -1,16 +1,17 @@
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
-use std::collections::HashMap;
+use crate::datastructures::hash::FastMap;
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 
 fn roll_north(mut input: GridView<Vec<u8>>) -> GridView<Vec<u8>> {
     for col_idx in 0..input.width() {
+        let mut col = input.col_mut(col_idx);
         let mut min_free_idx = 0;
-        for row_idx in 0..input.height() {
-            match input[(row_idx, col_idx)] {
+        for row_idx in 0..col.len() {
+            match col[row_idx] {
                 b'O' => {
                     if min_free_idx < row_idx {
-                        input[(min_free_idx, col_idx)] = b'O';
-                        input[(row_idx, col_idx)] = b'.';
+                        col[min_free_idx] = b'O';
+                        col[row_idx] = b'.';
                     }
                     min_free_idx += 1;
                 }
@@ -22,73 +23,15 @@ fn roll_north(mut input: GridView<Vec<u8>>) -> GridView<Vec<u8>> {
     input
 }
 
-fn roll_south(mut input: GridView<Vec<u8>>) -> GridView<Vec<u8>> {
-    for col_idx in 0..input.width() {
-        let mut max_free_idx = input.height() - 1;
-        for row_idx in (0..input.height()).rev() {
-            match input[(row_idx, col_idx)] {
-                b'O' => {
-                    if max_free_idx > row_idx {
-                        input[(max_free_idx, col_idx)] = b'O';
-                        input[(row_idx, col_idx)] = b'.';
-                    }
-                    max_free_idx = max_free_idx.saturating_sub(1)
-                }
-                b'#' => max_free_idx = if row_idx > 0 { row_idx - 1 } else { 0 },
-                _ => (),
-            }
-        }
-    }
-    input
-}
-
-fn roll_west(mut input: GridView<Vec<u8>>) -> GridView<Vec<u8>> {
-    for row_idx in 0..input.height() {
-        let mut min_free_idx = 0;
-        for col_idx in 0..input.width() {
-            match input[(row_idx, col_idx)] {
-                b'O' => {
-                    if min_free_idx < col_idx {
-                        input[(row_idx, min_free_idx)] = b'O';
-                        input[(row_idx, col_idx)] = b'.';
-                    }
-                    min_free_idx += 1;
-                }
-                b'#' => min_free_idx = col_idx + 1,
-                _ => (),
-            }
-        }
+/// Rolls the rocks north, west, south, and east by rolling them north and
+/// rotating the grid clockwise four times.
+fn spin_one_cycle(mut input: GridView<Vec<u8>>) -> GridView<Vec<u8>> {
+    for _ in 0..4 {
+        input = roll_north(input).rotate_cw();
     }
     input
 }
 
-fn roll_east(mut input: GridView<Vec<u8>>) -> GridView<Vec<u8>> {
-    for row_idx in 0..input.height() {
-        let mut max_free_idx = input.width() - 1;
-        for col_idx in (0..input.width()).rev() {
-            match input[(row_idx, col_idx)] {
-                b'O' => {
-                    if max_free_idx > col_idx {
-                        input[(row_idx, max_free_idx)] = b'O';
-                        input[(row_idx, col_idx)] = b'.';
-                    }
-                    max_free_idx = max_free_idx.saturating_sub(1)
-                }
-                b'#' => max_free_idx = if col_idx > 0 { col_idx - 1 } else { 0 },
-                _ => (),
-            }
-        }
-    }
-    input
-}
-
-fn spin_one_cycle(input: GridView<Vec<u8>>) -> GridView<Vec<u8>> {
-    let input = roll_north(input);
-    let input = roll_west(input);
-    let input = roll_south(input);
-    roll_east(input)
-}
-
 fn determine_load_rolled_north(grid: &GridView<Vec<u8>>) -> usize {
     let mut load: usize = 0;
     for col_idx in 0..grid.width() {
@@ -135,24 +78,33 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Parabolic Reflector Dish")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let buf = Vec::from(input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let buf = Vec::from(input);
         let grid = GridView::from_separated_vec(b'\n', buf);
         Ok(Self { grid })
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         let load = determine_load_rolled_north(&self.grid);
-        Ok(Solution::with_description(
-            "Total load (part 1)",
-            load.to_string(),
-        ))
+        Ok(Solution::with_description("Total load (part 1)", load))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut grid = self.grid.clone();
 
-        let mut seen = HashMap::new();
+        let mut seen = FastMap::default();
         seen.insert(grid.clone(), 0);
 
         const MAX_CYCLES: usize = 1_000_000_000;
@@ -170,29 +122,9 @@ impl<'input> Solver<'input> for SolverImpl {
 
         let load = determine_load(&grid);
 
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Total load (part 2)",
-            load.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day14-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "136");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day14-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "64");
-        Ok(())
+            load,
+        )))
     }
 }
@@ -1,5 +1,4 @@
-use crate::solvers::{Solution, Solver};
-use anyhow::anyhow;
+use crate::solvers::{InputError, MaybeSolution, Normalization, Solution, Solver};
 
 pub fn hash(input: &[u8]) -> u8 {
     input
@@ -37,8 +36,10 @@ impl<'input> TryFrom<&'input str> for Step<'input> {
         } else {
             let (label, focal_length) = value
                 .split_once('=')
-                .ok_or_else(|| anyhow!("invalid step syntax"))?;
-            let focal_length = focal_length.parse::<u8>()?;
+                .ok_or_else(|| InputError::new(value, "a step like rn=1 or cm-"))?;
+            let focal_length = focal_length
+                .parse::<u8>()
+                .map_err(|_| InputError::new(focal_length, "a focal length"))?;
             Ok(Self {
                 label,
                 operation: Operation::Install(focal_length),
@@ -51,29 +52,43 @@ pub struct SolverImpl<'input> {
     input: &'input str,
 }
 
+impl<'input> SolverImpl<'input> {
+    /// Steps of the initialization sequence. The puzzle asks to ignore
+    /// newline characters, so they are dropped here instead of normalizing the
+    /// input.
+    fn steps(&self) -> impl Iterator<Item = &'input str> {
+        self.input
+            .split(',')
+            .map(|step| step.trim_matches(['\r', '\n']))
+    }
+}
+
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Lens Library")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         Ok(Self { input })
     }
 
+    fn normalization() -> Normalization {
+        Normalization::NONE
+    }
+
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         let hashsum = self
-            .input
-            .trim()
-            .split(',')
+            .steps()
             .map(|step| hash(step.as_bytes()) as u64)
             .sum::<u64>();
-        Ok(Solution::with_description(
-            "Sum of HASHes",
-            hashsum.to_string(),
-        ))
+        Ok(Solution::with_description("Sum of HASHes", hashsum))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         const EMPTY_VEC: Vec<Lens> = Vec::new();
         let mut hashmap = [EMPTY_VEC; 256];
 
-        for step in self.input.trim().split(',') {
+        for step in self.steps() {
             let step = Step::try_from(step)?;
             let key = hash(step.label.as_bytes()) as usize;
             let lensbox = &mut hashmap[key];
@@ -109,29 +124,25 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             })
             .sum::<usize>();
 
-        Ok(Solution::with_description(
-            "Part 2",
-            focusing_power.to_string(),
-        ))
+        Ok(Some(Solution::with_description("Part 2", focusing_power)))
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day15-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "1320");
-        Ok(())
-    }
+    use crate::solvers::{new_solver, normalize_input, Normalization, Solver};
 
     #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day15-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "145");
+    fn test_ignores_newlines_in_raw_input() -> anyhow::Result<()> {
+        assert_eq!(SolverImpl::normalization(), Normalization::NONE);
+        let raw = normalize_input(2023, 15, b"rn=1,cm-,qp=3\r\n".to_vec());
+        assert_eq!(raw, b"rn=1,cm-,qp=3\r\n");
+
+        let solver = new_solver(2023, 15, std::str::from_utf8(&raw)?)?;
+        let expected = new_solver(2023, 15, "rn=1,cm-,qp=3")?;
+        assert_eq!(solver.solve_part_1()?, expected.solve_part_1()?);
+        assert_eq!(solver.solve_part_2()?, expected.solve_part_2()?);
         Ok(())
     }
 }
@@ -1,49 +1,23 @@
+use crate::datastructures::direction::Direction4;
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
+use crate::datastructures::hash::FastSet;
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use rayon::prelude::*;
-use std::collections::HashSet;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Dir {
-    Left,
-    Right,
-    Up,
-    Down,
-}
 
 fn push_dir(
-    queue: &mut Vec<(Dir, (usize, usize))>,
+    queue: &mut Vec<(Direction4, (usize, usize))>,
     grid: &GridView<&[u8]>,
-    dir: Dir,
+    dir: Direction4,
     tile_idx: (usize, usize),
 ) {
-    match dir {
-        Dir::Left => {
-            if tile_idx.1 > 0 {
-                queue.push((Dir::Left, (tile_idx.0, tile_idx.1 - 1)));
-            }
-        }
-        Dir::Right => {
-            if tile_idx.1 < grid.width() - 1 {
-                queue.push((Dir::Right, (tile_idx.0, tile_idx.1 + 1)));
-            }
-        }
-        Dir::Up => {
-            if tile_idx.0 > 0 {
-                queue.push((Dir::Up, (tile_idx.0 - 1, tile_idx.1)));
-            }
-        }
-        Dir::Down => {
-            if tile_idx.0 < grid.height() - 1 {
-                queue.push((Dir::Down, (tile_idx.0 + 1, tile_idx.1)));
-            }
-        }
+    if let Some(next_idx) = dir.step(tile_idx, grid.size()) {
+        queue.push((dir, next_idx));
     }
 }
 
-fn count_energized_tiles(grid: &GridView<&[u8]>, start: (Dir, (usize, usize))) -> usize {
-    let mut energized = HashSet::new();
-    let mut seen = HashSet::new();
+fn count_energized_tiles(grid: &GridView<&[u8]>, start: (Direction4, (usize, usize))) -> usize {
+    let mut energized = FastSet::default();
+    let mut seen = FastSet::default();
     let mut queue = vec![start];
     while let Some((dir, tile_idx)) = queue.pop() {
         if seen.contains(&(dir, tile_idx)) {
@@ -53,25 +27,25 @@ fn count_energized_tiles(grid: &GridView<&[u8]>, start: (Dir, (usize, usize))) -
         seen.insert((dir, tile_idx));
         let tile = grid[tile_idx];
         match (dir, tile) {
-            (Dir::Left, b'\\') | (Dir::Right, b'/') => {
-                push_dir(&mut queue, grid, Dir::Up, tile_idx);
+            (Direction4::Left, b'\\') | (Direction4::Right, b'/') => {
+                push_dir(&mut queue, grid, Direction4::Up, tile_idx);
             }
-            (Dir::Right, b'\\') | (Dir::Left, b'/') => {
-                push_dir(&mut queue, grid, Dir::Down, tile_idx);
+            (Direction4::Right, b'\\') | (Direction4::Left, b'/') => {
+                push_dir(&mut queue, grid, Direction4::Down, tile_idx);
             }
-            (Dir::Up, b'\\') | (Dir::Down, b'/') => {
-                push_dir(&mut queue, grid, Dir::Left, tile_idx);
+            (Direction4::Up, b'\\') | (Direction4::Down, b'/') => {
+                push_dir(&mut queue, grid, Direction4::Left, tile_idx);
             }
-            (Dir::Down, b'\\') | (Dir::Up, b'/') => {
-                push_dir(&mut queue, grid, Dir::Right, tile_idx);
+            (Direction4::Down, b'\\') | (Direction4::Up, b'/') => {
+                push_dir(&mut queue, grid, Direction4::Right, tile_idx);
             }
-            (Dir::Left, b'|') | (Dir::Right, b'|') => {
-                push_dir(&mut queue, grid, Dir::Up, tile_idx);
-                push_dir(&mut queue, grid, Dir::Down, tile_idx);
+            (Direction4::Left, b'|') | (Direction4::Right, b'|') => {
+                push_dir(&mut queue, grid, Direction4::Up, tile_idx);
+                push_dir(&mut queue, grid, Direction4::Down, tile_idx);
             }
-            (Dir::Up, b'-') | (Dir::Down, b'-') => {
-                push_dir(&mut queue, grid, Dir::Left, tile_idx);
-                push_dir(&mut queue, grid, Dir::Right, tile_idx);
+            (Direction4::Up, b'-') | (Direction4::Down, b'-') => {
+                push_dir(&mut queue, grid, Direction4::Left, tile_idx);
+                push_dir(&mut queue, grid, Direction4::Right, tile_idx);
             }
             (dir, _) => {
                 push_dir(&mut queue, grid, dir, tile_idx);
@@ -86,25 +60,42 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("The Floor Will Be Lava")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let grid = GridView::from_separated(b'\n', input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let grid = GridView::from_separated(b'\n', input);
         Ok(Self { grid })
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Energized tiles",
-            count_energized_tiles(&self.grid, (Dir::Right, (0, 0))).to_string(),
+            count_energized_tiles(&self.grid, (Direction4::Right, (0, 0))),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let possible_starts: Vec<_> = (0..self.grid.width())
-            .flat_map(|i| [(Dir::Up, (self.grid.height() - 1, i)), (Dir::Down, (0, i))])
+            .flat_map(|i| {
+                [
+                    (Direction4::Up, (self.grid.height() - 1, i)),
+                    (Direction4::Down, (0, i)),
+                ]
+            })
             .chain((0..self.grid.height()).flat_map(|i| {
                 [
-                    (Dir::Left, (i, self.grid.width() - 1)),
-                    (Dir::Right, (i, 0)),
+                    (Direction4::Left, (i, self.grid.width() - 1)),
+                    (Direction4::Right, (i, 0)),
                 ]
             }))
             .collect();
@@ -113,29 +104,6 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .map(|start| count_energized_tiles(&self.grid, start))
             .max()
             .unwrap_or_default();
-        Ok(Solution::with_description(
-            "Part 2",
-            max_energization.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day16-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "46");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day16-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "51");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", max_energization)))
     }
 }
@@ -1,215 +1,246 @@
-use std::{
-    cmp::Reverse,
-    collections::{BinaryHeap, HashMap},
-};
-
 use crate::{
-    datastructures::grid::GridView,
-    solvers::{Solution, Solver},
+    datastructures::{
+        direction::{Direction4, Pose},
+        grid::GridView,
+        search::astar,
+    },
+    solvers::{InputError, MaybeSolution, Solution, Solver, Tag},
 };
+use anyhow::anyhow;
+
+/// Parses the heat loss of each city block, requiring at least one block.
+fn parse_grid(input: &[u8]) -> anyhow::Result<GridView<Vec<u8>>> {
+    let grid = GridView::parse_digits(input)?;
+    if grid.height() == 0 || grid.width() == 0 {
+        return Err(InputError::from_bytes(input, "a grid of at least one city block").into());
+    }
+    Ok(grid)
+}
+
+fn no_path() -> anyhow::Error {
+    anyhow!("the crucible cannot reach the machine parts factory")
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Dir {
-    Left,
-    Right,
-    Up,
-    Down,
+struct Crucible {
+    pose: Pose,
+    steps_since_last_dir_change: usize,
 }
 
-impl Dir {
-    fn invert(&self) -> Dir {
-        match self {
-            Dir::Left => Dir::Right,
-            Dir::Right => Dir::Left,
-            Dir::Down => Dir::Up,
-            Dir::Up => Dir::Down,
+impl Crucible {
+    fn can_travel(&self, dir: Direction4, min_steps: usize, max_steps: usize) -> bool {
+        if self.pose.dir.invert() == dir {
+            return false;
+        }
+        if self.pose.dir == dir {
+            return self.steps_since_last_dir_change < max_steps;
         }
+        // The crucible may start in any direction.
+        self.steps_since_last_dir_change == 0 || self.steps_since_last_dir_change >= min_steps
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct VisitedKey {
-    pos: (usize, usize),
-    dir: Dir,
-    steps_since_last_dir_change: usize,
+fn find_min_heatloss(
+    grid: &GridView<Vec<u8>>,
+    min_steps: usize,
+    max_steps: usize,
+) -> Option<usize> {
+    let target = (grid.height() - 1, grid.width() - 1);
+    let start = Crucible {
+        pose: Pose::new((0, 0), Direction4::Right),
+        steps_since_last_dir_change: 0,
+    };
+    astar(
+        start,
+        |&crucible| {
+            Direction4::ALL
+                .into_iter()
+                .filter(move |&dir| crucible.can_travel(dir, min_steps, max_steps))
+                .filter_map(move |dir| {
+                    let pos = dir.step(crucible.pose.pos, grid.size())?;
+                    let steps_since_last_dir_change = if crucible.pose.dir == dir {
+                        crucible.steps_since_last_dir_change + 1
+                    } else {
+                        1
+                    };
+                    let next = Crucible {
+                        pose: Pose::new(pos, dir),
+                        steps_since_last_dir_change,
+                    };
+                    Some((next, grid[pos] as usize))
+                })
+        },
+        // Every tile costs at least 1 heat loss.
+        |crucible| (target.0 - crucible.pose.pos.0) + (target.1 - crucible.pose.pos.1),
+        |crucible| crucible.pose.pos == target && crucible.steps_since_last_dir_change >= min_steps,
+    )
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct PathState {
-    heatloss: usize,
-    current_pos: (usize, usize),
-    travel_direction: Dir,
-    steps_since_last_dir_change: usize,
-    target: (usize, usize),
+pub struct SolverImpl {
+    grid: GridView<Vec<u8>>,
 }
 
-impl PartialOrd for PathState {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
+impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Clumsy Crucible")
     }
-}
 
-impl Ord for PathState {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.min_heatloss_bound().cmp(&other.min_heatloss_bound())
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
     }
-}
 
-impl PathState {
-    fn min_heatloss_bound(&self) -> usize {
-        self.heatloss + (self.target.0 - self.current_pos.0) + (self.target.1 - self.current_pos.1)
+    fn new(input: &'input str) -> anyhow::Result<Self> {
+        Self::from_bytes(input.as_bytes())
     }
 
-    fn is_valid_travel_dir(
-        &self,
-        dir: Dir,
-        min_steps: Option<usize>,
-        max_steps: usize,
-        grid_size: (usize, usize),
-    ) -> bool {
-        if self.travel_direction.invert() == dir {
-            return false;
-        }
-        if self.travel_direction == dir && self.steps_since_last_dir_change >= max_steps {
-            return false;
-        }
-        if let Some(min_steps) = min_steps {
-            if self.travel_direction != dir && self.steps_since_last_dir_change < min_steps {
-                return false;
-            }
-        }
-        match dir {
-            Dir::Down => self.current_pos.0 < grid_size.0 - 1,
-            Dir::Up => self.current_pos.0 > 0,
-            Dir::Left => self.current_pos.1 > 0,
-            Dir::Right => self.current_pos.1 < grid_size.1 - 1,
-        }
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let grid = parse_grid(input)?;
+        Ok(Self { grid })
+    }
+
+    fn solve_part_1(&self) -> anyhow::Result<Solution> {
+        let min_heatloss = find_min_heatloss(&self.grid, 1, 3).ok_or_else(no_path)?;
+        Ok(Solution::with_description(
+            "Minimal heat loss",
+            min_heatloss,
+        ))
+    }
+
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        let min_heatloss = find_min_heatloss(&self.grid, 4, 10).ok_or_else(no_path)?;
+        Ok(Some(Solution::with_description(
+            "Minimal heat loss with ultra crucible",
+            min_heatloss,
+        )))
     }
 }
 
-fn find_min_heatloss(
-    grid: &GridView<&[u8]>,
-    min_steps: Option<usize>,
+/// Finds the minimal heat loss with Dijkstra's algorithm on a bucket queue.
+/// Since every tile adds at most 9 heat loss, the states can be kept in one
+/// bucket per total heat loss instead of a binary heap. A state is a position
+/// with the axis of the last move, and every move turns and goes straight
+/// for `min_steps..=max_steps` tiles.
+fn find_min_heatloss_bucket_queue(
+    grid: &GridView<Vec<u8>>,
+    min_steps: usize,
     max_steps: usize,
 ) -> Option<usize> {
-    let target = (grid.height() - 1, grid.width() - 1);
-    let mut queue = BinaryHeap::from([
-        Reverse(PathState {
-            heatloss: 0,
-            current_pos: (0, 0),
-            travel_direction: Dir::Down,
-            steps_since_last_dir_change: 0,
-            target,
-        }),
-        Reverse(PathState {
-            heatloss: 0,
-            current_pos: (0, 0),
-            travel_direction: Dir::Right,
-            steps_since_last_dir_change: 0,
-            target,
-        }),
-    ]);
-    let mut visited: HashMap<VisitedKey, usize> = HashMap::new();
-    while let Some(state) = queue.pop() {
-        let state = state.0;
-        if state.current_pos == target
-            && state.steps_since_last_dir_change >= min_steps.unwrap_or_default()
-        {
-            return Some(state.heatloss);
-        }
-
-        let visited_key = VisitedKey {
-            pos: state.current_pos,
-            dir: state.travel_direction,
-            steps_since_last_dir_change: state.steps_since_last_dir_change,
-        };
-        if let Some(&prior_heatloss) = visited.get(&visited_key) {
-            if prior_heatloss <= state.heatloss {
+    let (height, width) = grid.size();
+    let target = (height - 1, width - 1);
+    let index =
+        |(row, col): (usize, usize), vertical: bool| (row * width + col) * 2 + vertical as usize;
+
+    let mut min_heatloss = vec![usize::MAX; height * width * 2];
+    min_heatloss[index((0, 0), false)] = 0;
+    min_heatloss[index((0, 0), true)] = 0;
+    let mut buckets = vec![vec![((0, 0), false), ((0, 0), true)]];
+    let mut heatloss = 0;
+    while heatloss < buckets.len() {
+        while let Some((pos, vertical)) = buckets[heatloss].pop() {
+            if min_heatloss[index(pos, vertical)] < heatloss {
                 continue;
             }
-        }
-        visited.insert(visited_key, state.heatloss);
-
-        for dir in [Dir::Left, Dir::Right, Dir::Down, Dir::Up] {
-            if !state.is_valid_travel_dir(dir, min_steps, max_steps, grid.size()) {
-                continue;
+            if pos == target {
+                return Some(heatloss);
             }
 
-            let new_pos = match dir {
-                Dir::Up => (state.current_pos.0 - 1, state.current_pos.1),
-                Dir::Down => (state.current_pos.0 + 1, state.current_pos.1),
-                Dir::Left => (state.current_pos.0, state.current_pos.1 - 1),
-                Dir::Right => (state.current_pos.0, state.current_pos.1 + 1),
-            };
-
-            queue.push(Reverse(PathState {
-                heatloss: state.heatloss + ((grid[new_pos] - b'0') as usize),
-                current_pos: new_pos,
-                travel_direction: dir,
-                steps_since_last_dir_change: if state.travel_direction == dir {
-                    state.steps_since_last_dir_change + 1
-                } else {
-                    1
-                },
-                target,
-            }));
+            for forward in [false, true] {
+                let mut next_heatloss = heatloss;
+                for steps in 1..=max_steps {
+                    let next = match (vertical, forward) {
+                        (false, false) => pos.0.checked_sub(steps).map(|row| (row, pos.1)),
+                        (false, true) => {
+                            Some((pos.0 + steps, pos.1)).filter(|next| next.0 < height)
+                        }
+                        (true, false) => pos.1.checked_sub(steps).map(|col| (pos.0, col)),
+                        (true, true) => Some((pos.0, pos.1 + steps)).filter(|next| next.1 < width),
+                    };
+                    let Some(next) = next else {
+                        break;
+                    };
+                    next_heatloss += grid[next] as usize;
+                    if steps < min_steps {
+                        continue;
+                    }
+                    let next_index = index(next, !vertical);
+                    if next_heatloss < min_heatloss[next_index] {
+                        min_heatloss[next_index] = next_heatloss;
+                        if buckets.len() <= next_heatloss {
+                            buckets.resize_with(next_heatloss + 1, Vec::new);
+                        }
+                        buckets[next_heatloss].push((next, !vertical));
+                    }
+                }
+            }
         }
+        heatloss += 1;
     }
 
     None
 }
 
-pub struct SolverImpl<'input> {
-    grid: GridView<&'input [u8]>,
+/// Alternative to [`SolverImpl`] using a bucket queue and moves that turn
+/// and go straight for several tiles at once.
+#[register_solver(year = 2023, day = 17, algo = "bucket-queue")]
+pub struct BucketQueueSolver {
+    grid: GridView<Vec<u8>>,
 }
 
-impl<'input> Solver<'input> for SolverImpl<'input> {
+impl<'input> Solver<'input> for BucketQueueSolver {
+    fn title() -> Option<&'static str> {
+        SolverImpl::title()
+    }
+
+    fn tags() -> &'static [Tag] {
+        SolverImpl::tags()
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let grid = GridView::from_separated(b'\n', input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let grid = parse_grid(input)?;
         Ok(Self { grid })
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
-        let min_heatloss = find_min_heatloss(&self.grid, None, 3).expect("a solution should exist");
+        let min_heatloss = find_min_heatloss_bucket_queue(&self.grid, 1, 3).ok_or_else(no_path)?;
         Ok(Solution::with_description(
             "Minimal heat loss",
-            min_heatloss.to_string(),
+            min_heatloss,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        let min_heatloss =
-            find_min_heatloss(&self.grid, Some(4), 10).expect("a solution should exist");
-        Ok(Solution::with_description(
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        let min_heatloss = find_min_heatloss_bucket_queue(&self.grid, 4, 10).ok_or_else(no_path)?;
+        Ok(Some(Solution::with_description(
             "Minimal heat loss with ultra crucible",
-            min_heatloss.to_string(),
-        ))
+            min_heatloss,
+        )))
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
+    use super::{BucketQueueSolver, SolverImpl};
+    use crate::solvers::{InputError, Solver};
 
     #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day17-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "102");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day17-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "94");
-        Ok(())
+    fn test_rejects_empty_grid() {
+        for input in ["", "\n"] {
+            let err = SolverImpl::new(input).err().unwrap();
+            assert!(err.downcast_ref::<InputError>().is_some());
+            let err = BucketQueueSolver::new(input).err().unwrap();
+            assert!(err.downcast_ref::<InputError>().is_some());
+        }
     }
 
     #[test]
-    fn test_example2_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day17-2.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "71");
+    fn test_no_path() -> anyhow::Result<()> {
+        // The ultra crucible cannot stop after fewer than 4 blocks.
+        assert!(SolverImpl::new("12")?.solve_part_2().is_err());
+        assert!(BucketQueueSolver::new("12")?.solve_part_2().is_err());
         Ok(())
     }
 }
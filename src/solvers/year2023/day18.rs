@@ -1,46 +1,31 @@
-use crate::solvers::{Solution, Solver};
+use crate::datastructures::direction::Direction4;
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver, Tag};
 use anyhow::anyhow;
 use regex::Regex;
 use std::collections::BTreeSet;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Dir {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl TryFrom<&str> for Dir {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "L" => Ok(Self::Left),
-            "R" => Ok(Self::Right),
-            "U" => Ok(Self::Up),
-            "D" => Ok(Self::Down),
-            _ => Err(anyhow!("invalid direction")),
-        }
+fn parse_dir(value: &str) -> anyhow::Result<Direction4> {
+    match value {
+        "L" => Ok(Direction4::Left),
+        "R" => Ok(Direction4::Right),
+        "U" => Ok(Direction4::Up),
+        "D" => Ok(Direction4::Down),
+        _ => Err(InputError::new(value, "a direction L, R, U, or D").into()),
     }
 }
 
-impl TryFrom<u8> for Dir {
-    type Error = anyhow::Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            b'0' => Ok(Self::Right),
-            b'1' => Ok(Self::Down),
-            b'2' => Ok(Self::Left),
-            b'3' => Ok(Self::Up),
-            _ => Err(anyhow!("invalid direction")),
-        }
+fn parse_color_dir(value: u8) -> anyhow::Result<Direction4> {
+    match value {
+        b'0' => Ok(Direction4::Right),
+        b'1' => Ok(Direction4::Down),
+        b'2' => Ok(Direction4::Left),
+        b'3' => Ok(Direction4::Up),
+        _ => Err(anyhow!("invalid direction")),
     }
 }
 
 struct DigInstruction {
-    dir: Dir,
+    dir: Direction4,
     count: usize,
 }
 
@@ -53,9 +38,11 @@ impl TryFrom<&str> for DigInstruction {
                 Regex::new(r"^(?P<dir>.)\s+(?P<count>\d+)\s+\((?P<color>#[0-9a-fA-F]{6})\)\s*$")
                     .unwrap();
         }
-        let captures = RE.captures(value).ok_or(anyhow!("invalid instruction"))?;
+        let captures = RE
+            .captures(value)
+            .ok_or_else(|| InputError::new(value, "an instruction like R 6 (#70c710)"))?;
         Ok(Self {
-            dir: Dir::try_from(captures.name("dir").unwrap().as_str())?,
+            dir: parse_dir(captures.name("dir").unwrap().as_str())?,
             count: captures.name("count").unwrap().as_str().parse()?,
         })
     }
@@ -67,9 +54,11 @@ impl DigInstruction {
             static ref RE: Regex =
                 Regex::new(r"^.\s+\d+\s+\(#(?P<count>[0-9a-fA-F]{5})(?P<dir>[0-3])\)\s*$").unwrap();
         }
-        let captures = RE.captures(value).ok_or(anyhow!("invalid instruction"))?;
+        let captures = RE
+            .captures(value)
+            .ok_or_else(|| InputError::new(value, "an instruction like R 6 (#70c710)"))?;
         let count = usize::from_str_radix(captures.name("count").unwrap().as_str(), 16)?;
-        let dir = Dir::try_from(captures.name("dir").unwrap().as_str().as_bytes()[0])?;
+        let dir = parse_color_dir(captures.name("dir").unwrap().as_str().as_bytes()[0])?;
         Ok(Self { dir, count })
     }
 }
@@ -78,20 +67,19 @@ impl DigInstruction {
 struct Bound {
     row_range: (isize, isize),
     col: isize,
-    dir: Dir,
+    dir: Direction4,
 }
 
 fn dig_yourself_a_hole(instructions: &[DigInstruction]) -> usize {
     let mut vertical_bounds = Vec::new();
     let mut current_pos = (0isize, 0isize);
     for instruction in instructions.iter() {
-        let next_pos = match instruction.dir {
-            Dir::Up => (current_pos.0 - instruction.count as isize, current_pos.1),
-            Dir::Down => (current_pos.0 + instruction.count as isize, current_pos.1),
-            Dir::Left => (current_pos.0, current_pos.1 - instruction.count as isize),
-            Dir::Right => (current_pos.0, current_pos.1 + instruction.count as isize),
-        };
-        if instruction.dir == Dir::Up || instruction.dir == Dir::Down {
+        let (d_row, d_col) = instruction.dir.delta();
+        let next_pos = (
+            current_pos.0 + d_row * instruction.count as isize,
+            current_pos.1 + d_col * instruction.count as isize,
+        );
+        if instruction.dir.is_vertical() {
             vertical_bounds.push(Bound {
                 row_range: if current_pos.0 < next_pos.0 {
                     (current_pos.0, next_pos.0)
@@ -128,7 +116,7 @@ fn dig_yourself_a_hole(instructions: &[DigInstruction]) -> usize {
             .copied()
             .filter(|bounds| bounds.row_range.0 <= row && bounds.row_range.1 >= row)
             .collect::<Vec<_>>();
-        intersected_bounds.sort_by(|a, b| a.col.cmp(&b.col));
+        intersected_bounds.sort_by_key(|a| a.col);
 
         let mut last_corner: Option<Bound> = None;
         let mut last_bound: Option<Bound> = None;
@@ -171,6 +159,14 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Lavaduct Lagoon")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         Ok(Self { input })
     }
@@ -184,40 +180,20 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
 
         Ok(Solution::with_description(
             "Capacity of the lagoon (part 1)",
-            dig_yourself_a_hole(&instructions).to_string(),
+            dig_yourself_a_hole(&instructions),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let instructions = self
             .input
             .lines()
             .map(DigInstruction::from_color)
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Capacity of the lagoon (part 2)",
-            dig_yourself_a_hole(&instructions).to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day18-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "62");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day18-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "952408144115");
-        Ok(())
+            dig_yourself_a_hole(&instructions),
+        )))
     }
 }
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver};
 use anyhow::anyhow;
 use regex::Regex;
 use std::collections::HashMap;
@@ -78,28 +78,21 @@ impl TryFrom<&str> for MachinePart {
                 Regex::new(r"\{x=(?P<x>\d+),m=(?P<m>\d+),a=(?P<a>\d+),s=(?P<s>\d+)\}").unwrap();
         }
 
-        let caps = RE.captures(value).ok_or(anyhow!("Invalid machine part"))?;
-        let x = caps
-            .name("x")
-            .ok_or(anyhow!("expected x"))?
-            .as_str()
-            .parse::<u64>()?;
-        let m = caps
-            .name("m")
-            .ok_or(anyhow!("expected m"))?
-            .as_str()
-            .parse::<u64>()?;
-        let a = caps
-            .name("a")
-            .ok_or(anyhow!("expected a"))?
-            .as_str()
-            .parse::<u64>()?;
-        let s = caps
-            .name("s")
-            .ok_or(anyhow!("expected s"))?
-            .as_str()
-            .parse::<u64>()?;
-        Ok(Self { x, m, a, s })
+        let caps = RE.captures(value).ok_or_else(|| {
+            InputError::new(value, "a machine part like {x=787,m=2655,a=1222,s=2876}")
+        })?;
+        let rating = |name| {
+            let rating = caps.name(name).expect("group of the pattern").as_str();
+            rating
+                .parse::<u64>()
+                .map_err(|_| InputError::new(rating, "a rating"))
+        };
+        Ok(Self {
+            x: rating("x")?,
+            m: rating("m")?,
+            a: rating("a")?,
+            s: rating("s")?,
+        })
     }
 }
 
@@ -197,27 +190,21 @@ impl TryFrom<&str> for Condition {
                 Regex::new(r"^(?P<var>[xmas])(?P<cmp>[<>])(?P<threshold>.*)").unwrap();
         }
 
-        let caps = RE.captures(value).ok_or(anyhow!("Invalid condition"))?;
-        let var = Category::try_from(
-            caps.name("var")
-                .ok_or(anyhow!("expected var"))?
-                .as_str()
-                .as_bytes()[0],
-        )?;
-        let comparison = match caps
-            .name("cmp")
-            .ok_or(anyhow!("expected operation"))?
-            .as_str()
-        {
+        let caps = RE
+            .captures(value)
+            .ok_or_else(|| InputError::new(value, "a condition like a<2006"))?;
+        let var = Category::try_from(caps["var"].as_bytes()[0])?;
+        let comparison = match &caps["cmp"] {
             "<" => Comparison::Lower,
-            ">" => Comparison::Greater,
-            _ => return Err(anyhow!("Invalid operation")),
+            _ => Comparison::Greater,
         };
         let threshold = caps
             .name("threshold")
-            .ok_or(anyhow!("expected threshold"))?
-            .as_str()
-            .parse::<u64>()?;
+            .expect("group of the pattern")
+            .as_str();
+        let threshold = threshold
+            .parse::<u64>()
+            .map_err(|_| InputError::new(threshold, "a threshold"))?;
         Ok(Self {
             var,
             threshold,
@@ -276,7 +263,9 @@ impl<'a> TryFrom<&'a str> for Rule<'a> {
     type Error = anyhow::Error;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        let (condition, operation) = value.split_once(':').ok_or(anyhow!("Invalid rule"))?;
+        let (condition, operation) = value
+            .split_once(':')
+            .ok_or_else(|| InputError::new(value, "a rule like a<2006:qkq"))?;
         Ok(Self(
             Condition::try_from(condition)?,
             Operation::try_from(operation)?,
@@ -338,6 +327,10 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Aplenty")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         lazy_static! {
             static ref RE: Regex = Regex::new(r"(?P<label>\w+)\{(?P<rules>.*)\}").unwrap();
@@ -348,17 +341,11 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .by_ref()
             .take_while(|line| !line.trim().is_empty())
             .map(|line| {
-                let caps = RE
-                    .captures(line)
-                    .ok_or(anyhow!("Invalid workflow declaration"))?;
-                let label = caps
-                    .name("label")
-                    .ok_or(anyhow!("expected label"))?
-                    .as_str();
-                let rules = caps
-                    .name("rules")
-                    .ok_or(anyhow!("expected rules"))?
-                    .as_str();
+                let caps = RE.captures(line).ok_or_else(|| {
+                    InputError::new(line, "a workflow like px{a<2006:qkq,m>2090:A,rfg}")
+                })?;
+                let label = caps.name("label").expect("group of the pattern").as_str();
+                let rules = caps.name("rules").expect("group of the pattern").as_str();
                 Ok((label, Workflow::try_from(rules)?))
             })
             .collect::<anyhow::Result<HashMap<_, _>>>()?;
@@ -384,13 +371,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
                 }
             })
             .sum();
-        Ok(Solution::with_description(
-            "Part 1",
-            accepted_rating.to_string(),
-        ))
+        Ok(Solution::with_description("Part 1", accepted_rating))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut ranges = vec![(
             MachinePartRange {
                 x: (1, 4001),
@@ -425,29 +409,6 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .map(|(range, _)| range)
             .map(|range| range.num_combinations())
             .sum();
-        Ok(Solution::with_description(
-            "Part 2",
-            num_combinations.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day19-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "19114");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day19-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "167409079868000");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", num_combinations)))
     }
 }
@@ -1,7 +1,6 @@
-use anyhow::anyhow;
 use regex::Regex;
 
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver};
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 struct Reveal {
@@ -27,15 +26,17 @@ impl Reveal {
         let mut reveal = Reveal::default();
         for color_count_expr in expr.split(',').map(|e| e.trim()) {
             if let Some((count, color)) = color_count_expr.split_once(' ') {
-                let count: u32 = count.parse()?;
+                let count: u32 = count
+                    .parse()
+                    .map_err(|_| InputError::new(count, "a count"))?;
                 match color {
                     "red" => reveal.red += count,
                     "green" => reveal.green += count,
                     "blue" => reveal.blue += count,
-                    _ => Err(anyhow!("invalid color {}", color))?,
+                    _ => Err(InputError::new(color, "red, green, or blue"))?,
                 }
             } else {
-                return Err(anyhow!("expected expression of the form '<count> <color>'"));
+                return Err(InputError::new(color_count_expr, "<count> <color>").into());
             }
         }
         Ok(reveal)
@@ -77,12 +78,16 @@ impl Game {
                     .collect::<anyhow::Result<Vec<Reveal>>>()?,
             })
         } else {
-            Err(anyhow!("invalid game syntax: '{}'", expr))
+            Err(InputError::new(expr, "a game like Game 1: 3 blue, 4 red; 2 green").into())
         }
     }
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Cube Conundrum")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         static REFERENCE_BAG: Reveal = Reveal {
             red: 12,
@@ -115,35 +120,14 @@ impl<'input> Solver<'input> for SolverImpl {
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Sum of IDs of possible games",
-            self.part1.to_string(),
+            self.part1,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description(
             "Sum of the power",
-            self.part2.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use crate::solvers::Solver;
-
-    use super::SolverImpl;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day2-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "8");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day2-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "2286");
-        Ok(())
+            self.part2,
+        )))
     }
 }
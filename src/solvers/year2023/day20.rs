@@ -1,14 +1,14 @@
 use std::{
     collections::{BTreeMap, VecDeque},
     convert::identity,
-    fmt::Debug,
+    fmt::{Debug, Write},
     num::NonZeroUsize,
 };
 
 use anyhow::anyhow;
 use num::Integer;
 
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{Artifacts, MaybeSolution, Solution, Solver, Tag};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Pulse {
@@ -96,6 +96,7 @@ impl Module for Conjunction<'_> {
 
 pub struct SolverImpl<'input> {
     wiring: BTreeMap<&'input str, (&'input str, Vec<&'input str>)>,
+    artifacts: Artifacts,
 }
 
 type InstantiatedWiring<'solver, 'input> =
@@ -124,22 +125,30 @@ impl<'input> SolverImpl<'input> {
             .collect()
     }
 
-    #[allow(unused)]
-    fn print_graph(&self) {
-        println!("digraph aoc20 {{");
-        println!("  {{");
+    /// Renders the wiring in DOT format.
+    fn dot_graph(&self) -> String {
+        let mut dot = String::from("digraph aoc20 {\n  {\n");
         for (key, (module_type, _)) in self.wiring.iter() {
-            println!("    {key} [label=\"{module_type}{key}\"]");
+            writeln!(dot, "    {key} [label=\"{module_type}{key}\"]").unwrap();
         }
-        println!("  }}");
+        dot.push_str("  }\n");
         for (key, (_, edges)) in self.wiring.iter() {
-            println!("  {} -> {{{}}}", key, edges.join(" "));
+            writeln!(dot, "  {} -> {{{}}}", key, edges.join(" ")).unwrap();
         }
-        println!("}}");
+        dot.push_str("}\n");
+        dot
     }
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Pulse Propagation")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph, Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let wiring: BTreeMap<_, _> = input
             .lines()
@@ -156,7 +165,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             })
             .collect();
 
-        Ok(Self { wiring })
+        Ok(Self {
+            wiring,
+            artifacts: Artifacts::default(),
+        })
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
@@ -184,10 +196,16 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
         }
 
         let result = num_low_pulses * num_high_pulses;
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        // The solution relies on the structure of the wiring, which is easiest
+        // to see in the graph.
+        if self.artifacts.is_enabled() {
+            self.artifacts.emit("graph.dot", self.dot_graph())?;
+        }
+
         let precursors: Vec<_> = self
             .wiring
             .iter()
@@ -234,26 +252,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .reduce(|acc, value| acc.lcm(&value))
             .unwrap_or_default();
 
-        Ok(Solution::with_description("Part 2", solution.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1a() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day20-1a.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "32000000");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", solution)))
     }
 
-    #[test]
-    fn test_example_part_1b() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day20-1b.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "11687500");
-        Ok(())
+    fn set_artifacts(&mut self, artifacts: Artifacts) {
+        self.artifacts = artifacts;
     }
 }
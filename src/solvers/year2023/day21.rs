@@ -1,34 +1,26 @@
-use std::collections::HashSet;
-
 use anyhow::anyhow;
 
 use crate::{
-    datastructures::{grid::GridView, iterators::NeighborIterator2d},
-    solvers::{Solution, Solver},
+    datastructures::{grid::GridView, search::bfs_grid},
+    solvers::{MaybeSolution, Solution, Solver, SolverParams, Tag},
 };
 
 pub struct SolverImpl<'input> {
     grid: GridView<&'input [u8]>,
     start: (usize, usize),
+    steps_part_1: usize,
+    steps_part_2: usize,
 }
 
 impl SolverImpl<'_> {
-    pub fn reachable_in_steps(
-        &self,
-        start: (usize, usize),
-        steps: usize,
-    ) -> HashSet<(usize, usize)> {
-        let mut positions = HashSet::from([start]);
-        for _ in 0..steps {
-            positions = positions
-                .iter()
-                .flat_map(|&from| {
-                    NeighborIterator2d::new(from, self.grid.size())
-                        .filter(|&neighbor| self.grid[neighbor] != b'#')
-                })
-                .collect();
-        }
-        positions
+    /// Number of plots reachable in exactly `steps` steps. Since the elf can
+    /// step back and forth, these are the plots within `steps` steps with the
+    /// same parity.
+    pub fn reachable_in_steps(&self, start: (usize, usize), steps: usize) -> usize {
+        bfs_grid(self.grid.size(), [start], |pos| self.grid[pos] != b'#')
+            .reached()
+            .filter(|&(_, distance)| distance <= steps && distance % 2 == steps % 2)
+            .count()
     }
 
     pub fn reachable_in_steps_with_assumptions(&self, steps: usize) -> usize {
@@ -82,8 +74,8 @@ impl SolverImpl<'_> {
             self.reachable_in_steps(top_left, steps_full_tile + steps_half_tile - 1);
 
         let inner_odd = self.reachable_in_steps(top_mid, 3 * w - 1);
-        let inner_even = &self.reachable_in_steps(top_mid, 2 * w - 1);
-        let center = &self.reachable_in_steps(self.start, 2 * w + w / 2);
+        let inner_even = self.reachable_in_steps(top_mid, 2 * w - 1);
+        let center = self.reachable_in_steps(self.start, 2 * w + w / 2);
 
         let corner_upper_left = self.reachable_in_steps(bottom_right, steps_half_tile - 1);
         let corner_upper_right = self.reachable_in_steps(bottom_left, steps_half_tile - 1);
@@ -91,65 +83,64 @@ impl SolverImpl<'_> {
         let corner_lower_right = self.reachable_in_steps(top_left, steps_half_tile - 1);
 
         let n = replication_steps - 1;
-        center.len()
-            + down.len()
-            + up.len()
-            + right.len()
-            + left.len()
-            + n * (upper_left_diag.len()
-                + upper_right_diag.len()
-                + lower_left_diag.len()
-                + lower_right_diag.len())
+        center
+            + down
+            + up
+            + right
+            + left
+            + n * (upper_left_diag + upper_right_diag + lower_left_diag + lower_right_diag)
             + replication_steps
-                * (corner_lower_left.len()
-                    + corner_lower_right.len()
-                    + corner_upper_left.len()
-                    + corner_upper_right.len())
-            + (n * n - 1) * inner_odd.len()
-            + replication_steps * replication_steps * inner_even.len()
+                * (corner_lower_left + corner_lower_right + corner_upper_left + corner_upper_right)
+            + (n * n - 1) * inner_odd
+            + replication_steps * replication_steps * inner_even
     }
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Step Counter")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let grid = GridView::from_separated(b'\n', input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let grid = GridView::from_separated(b'\n', input);
         let start = grid
-            .iter()
-            .enumerate()
-            .find_map(|(i, tile)| match tile {
-                b'S' => Some(grid.nth_index(i)),
-                _ => None,
-            })
+            .indexed_iter()
+            .find_map(|(pos, tile)| (tile == b'S').then_some(pos))
             .ok_or_else(|| anyhow!("Start position required."))?;
 
-        Ok(Self { grid, start })
+        Ok(Self {
+            grid,
+            start,
+            steps_part_1: 64,
+            steps_part_2: 26501365,
+        })
     }
 
-    fn solve_part_1(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
-            "Garden plots reachable in 64 steps",
-            self.reachable_in_steps(self.start, 64).len().to_string(),
-        ))
+    fn set_params(&mut self, params: &SolverParams) -> anyhow::Result<()> {
+        self.steps_part_1 = params.get("steps", self.steps_part_1)?;
+        self.steps_part_2 = params.get("steps_part_2", self.steps_part_2)?;
+        Ok(())
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
-            "Garden plots reachable in 26501365",
-            self.reachable_in_steps_with_assumptions(26501365)
-                .to_string(),
+            "Reachable garden plots",
+            self.reachable_in_steps(self.start, self.steps_part_1),
         ))
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day21-1.example"))?;
-        assert_eq!(solver.reachable_in_steps(solver.start, 6).len(), 16);
-        Ok(())
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description(
+            "Reachable garden plots on the infinite map",
+            self.reachable_in_steps_with_assumptions(self.steps_part_2),
+        )))
     }
 }
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use anyhow::anyhow;
 
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 
 type Coord = usize;
 
@@ -47,7 +47,7 @@ impl TryFrom<&str> for Brick {
 
 fn let_bricks_fall(mut bricks: Vec<Brick>) -> (Vec<Brick>, usize) {
     let mut num_fallen = 0;
-    bricks.sort_unstable_by(|a, b| a.z.1.cmp(&b.z.1));
+    bricks.sort_unstable_by_key(|a| a.z.1);
     for i in 0..bricks.len() {
         let mut supported_by_other_brick = false;
         for j in (0..i).rev() {
@@ -82,6 +82,10 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Sand Slabs")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut bricks = input
             .lines()
@@ -123,11 +127,11 @@ impl<'input> Solver<'input> for SolverImpl {
         let disintegratable = self.bricks.len() - self.required_supports.len();
         Ok(Solution::with_description(
             "Bricks safe to disintegrate",
-            disintegratable.to_string(),
+            disintegratable,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let total_falling: usize = self
             .required_supports
             .iter()
@@ -143,29 +147,9 @@ impl<'input> Solver<'input> for SolverImpl {
             })
             .sum();
 
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Bricks that could fall",
-            total_falling.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day22-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "5");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day22-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "7");
-        Ok(())
+            total_falling,
+        )))
     }
 }
@@ -1,9 +1,13 @@
-use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::{
+    collections::{BinaryHeap, HashMap, HashSet},
+    fmt::Write,
+};
 
 use crate::{
     datastructures::{grid::GridView, iterators::NeighborIterator2d},
-    solvers::{Solution, Solver},
+    solvers::{Artifacts, MaybeSolution, Solution, Solver, Tag},
 };
+use tracing::info_span;
 
 #[derive(Clone, PartialEq, Eq)]
 struct QueueItem(usize, (usize, usize), HashSet<(usize, usize)>);
@@ -22,13 +26,29 @@ impl PartialOrd for QueueItem {
 
 pub struct SolverImpl<'input> {
     grid: GridView<&'input [u8]>,
+    artifacts: Artifacts,
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("A Long Walk")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let grid = GridView::from_separated(b'\n', input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let grid = GridView::from_separated(b'\n', input);
 
-        Ok(Self { grid })
+        Ok(Self {
+            grid,
+            artifacts: Artifacts::default(),
+        })
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
@@ -61,13 +81,17 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
 
         Ok(Solution::with_description(
             "Longest hike with icy patches",
-            longest_path.len().to_string(),
+            longest_path.len(),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        let graph = self.construct_graph();
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        let graph = info_span!("construct_graph").in_scope(|| self.construct_graph());
+        if self.artifacts.is_enabled() {
+            self.artifacts.emit("graph.dot", Self::dot_graph(&graph))?;
+        }
 
+        let _span = info_span!("search").entered();
         let mut longest_path_len = 0;
         let mut queue = BinaryHeap::from([QueueItem(0, (0, 1), HashSet::new())]);
         while let Some(QueueItem(path_len, pos, mut visited)) = queue.pop() {
@@ -87,10 +111,14 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             }
         }
 
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Longest hike",
-            longest_path_len.to_string(),
-        ))
+            longest_path_len,
+        )))
+    }
+
+    fn set_artifacts(&mut self, artifacts: Artifacts) {
+        self.artifacts = artifacts;
     }
 }
 
@@ -131,16 +159,20 @@ impl SolverImpl<'_> {
         nodes
     }
 
-    #[allow(unused)]
-    fn print_dot_graph(nodes: &Nodes) {
-        for (from, edges) in nodes {
-            for (to, length) in edges {
-                if from < to {
-                    println!("  \"{from:?}\" -- \"{to:?}\" [label=\"{length}\"];");
-                }
-            }
+    /// Renders the compressed graph of junctions in DOT format.
+    fn dot_graph(nodes: &Nodes) -> String {
+        let mut edges: Vec<_> = nodes
+            .iter()
+            .flat_map(|(from, edges)| edges.iter().map(move |(to, length)| (from, to, length)))
+            .filter(|(from, to, _)| from < to)
+            .collect();
+        edges.sort_unstable();
+        let mut dot = String::from("graph aoc23 {\n");
+        for (from, to, length) in edges {
+            writeln!(dot, "  \"{from:?}\" -- \"{to:?}\" [label=\"{length}\"];").unwrap();
         }
-        println!("}}");
+        dot.push_str("}\n");
+        dot
     }
 
     fn path_neighbors(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
@@ -150,20 +182,35 @@ impl SolverImpl<'_> {
 
 #[cfg(test)]
 mod test {
+    use std::sync::{Arc, Mutex};
+
     use super::SolverImpl;
-    use crate::solvers::Solver;
+    use crate::solvers::{ArtifactSink, Artifacts, Solver};
 
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day23-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "94");
-        Ok(())
+    #[derive(Default)]
+    struct CollectingSink(Mutex<Vec<(String, String)>>);
+
+    impl ArtifactSink for CollectingSink {
+        fn write(&self, name: &str, content: &[u8]) -> anyhow::Result<()> {
+            let content = String::from_utf8(content.to_vec())?;
+            self.0.lock().unwrap().push((name.into(), content));
+            Ok(())
+        }
     }
 
     #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day23-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "154");
+    fn test_graph_artifact() -> anyhow::Result<()> {
+        let sink = Arc::new(CollectingSink::default());
+        let mut solver = SolverImpl::new(include_str!("./day23-1.example"))?;
+        solver.set_artifacts(Artifacts::new(sink.clone()));
+        solver.solve_part_2()?;
+        let artifacts = sink.0.lock().unwrap();
+        let [(name, dot)] = artifacts.as_slice() else {
+            panic!("expected a single artifact");
+        };
+        assert_eq!(name, "graph.dot");
+        assert!(dot.starts_with("graph aoc23 {\n"));
+        assert!(dot.contains("\"(0, 1)\" -- \"(5, 3)\" [label=\"15\"];"));
         Ok(())
     }
 }
@@ -1,50 +1,12 @@
-use std::ops::{Add, Mul, Sub};
-
 use anyhow::anyhow;
 use nalgebra::{Matrix6, Matrix6x1};
 
-use crate::solvers::{Solution, Solver};
-
-#[derive(Debug, Copy, Clone, PartialEq, PartialOrd)]
-struct V3d(f64, f64, f64);
-
-impl TryFrom<&str> for V3d {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let mut values = value.split(',').map(str::trim).map(str::parse::<f64>);
-        let mut next_value = || values.next().ok_or_else(|| anyhow!("too few values"));
-        Ok(V3d(next_value()??, next_value()??, next_value()??))
-    }
-}
-
-impl Add for V3d {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
-    }
-}
-
-impl Sub for V3d {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
-    }
-}
-
-impl Mul<f64> for V3d {
-    type Output = Self;
-
-    fn mul(self, rhs: f64) -> Self::Output {
-        Self(self.0 * rhs, self.1 * rhs, self.2 * rhs)
-    }
-}
+use crate::datastructures::point::P3;
+use crate::solvers::{MaybeSolution, Solution, Solver, SolverParams, Tag};
 
 struct Hailstone {
-    position: V3d,
-    velocity: V3d,
+    position: P3<f64>,
+    velocity: P3<f64>,
 }
 
 impl TryFrom<&str> for Hailstone {
@@ -55,8 +17,8 @@ impl TryFrom<&str> for Hailstone {
             .split_once('@')
             .ok_or_else(|| anyhow!("require position and velocity"))?;
         Ok(Hailstone {
-            position: V3d::try_from(position)?,
-            velocity: V3d::try_from(velocity)?,
+            position: position.parse()?,
+            velocity: velocity.parse()?,
         })
     }
 }
@@ -86,6 +48,8 @@ impl Hailstone {
 
 pub struct SolverImpl {
     hailstones: Vec<Hailstone>,
+    /// Bounds of the test area on both axes.
+    bounds: (f64, f64),
 }
 
 impl SolverImpl {
@@ -111,23 +75,41 @@ impl SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Never Tell Me The Odds")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let hailstones = input
             .lines()
             .map(Hailstone::try_from)
             .collect::<anyhow::Result<Vec<_>>>()?;
-        Ok(Self { hailstones })
+        Ok(Self {
+            hailstones,
+            bounds: (200000000000000., 400000000000000.),
+        })
+    }
+
+    fn set_params(&mut self, params: &SolverParams) -> anyhow::Result<()> {
+        self.bounds = (
+            params.get("min", self.bounds.0)?,
+            params.get("max", self.bounds.1)?,
+        );
+        Ok(())
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Intersections",
-            self.count_intersections_2d((200000000000000., 400000000000000.))
-                .to_string(),
+            self.count_intersections_2d(self.bounds),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let a = &self.hailstones[0];
         let b = &self.hailstones[1];
         let c = &self.hailstones[2];
@@ -202,29 +184,9 @@ impl<'input> Solver<'input> for SolverImpl {
             );
         let solution = solved.iter().copied().take(3).map(f64::round).sum::<f64>() as i64;
 
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Sum of initial coordinates",
-            solution.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day24-1.example"))?;
-        assert_eq!(solver.count_intersections_2d((7., 27.)), 2);
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day24-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "47");
-        Ok(())
+            solution,
+        )))
     }
 }
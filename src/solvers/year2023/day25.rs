@@ -3,27 +3,34 @@ use std::collections::{BTreeSet, HashMap, HashSet};
 use anyhow::anyhow;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Rng, Solution, Solver, Tag, DEFAULT_SEED};
 
-pub struct SolverImpl {
-    solution: usize,
+pub struct SolverImpl<'input> {
+    graph: HashMap<&'input str, BTreeSet<&'input str>>,
+    seed: u64,
 }
 
+/// Splits the nodes randomly into two groups and moves nodes to the group
+/// they have more edges to. The edges between the groups are candidates for
+/// the cut. The nodes are visited in a random order independent of the
+/// hash map's, so that the candidates only depend on the seed.
 fn determine_canidates_with_clustering_heuristic<'a>(
     graph: &HashMap<&'a str, BTreeSet<&'a str>>,
+    seed: u64,
 ) -> Vec<(&'a str, &'a str)> {
+    let mut nodes: Vec<_> = graph.keys().copied().collect();
+    nodes.sort_unstable();
+    Rng::new(seed).shuffle(&mut nodes);
     let mut groups = [HashSet::<&str>::new(), HashSet::<&str>::new()];
-    {
-        let keys_iter = &mut graph.keys();
-        groups[0].extend(keys_iter.take(graph.len() / 2));
-        groups[1].extend(keys_iter);
-    }
+    groups[0].extend(&nodes[..nodes.len() / 2]);
+    groups[1].extend(&nodes[nodes.len() / 2..]);
 
     let mut threshold = 1;
     let max_edges = graph.values().map(BTreeSet::len).max().unwrap_or_default();
     loop {
         let mut settled = true;
-        for (n, edges) in graph.iter() {
+        for n in &nodes {
+            let edges = &graph[n];
             if groups[0].contains(n) {
                 if edges.len() - edges.iter().filter(|&e| groups[1].contains(e)).count()
                     < threshold.min(edges.len() - 1)
@@ -51,8 +58,9 @@ fn determine_canidates_with_clustering_heuristic<'a>(
         }
     }
 
-    groups[0]
+    nodes
         .iter()
+        .filter(|&n| groups[0].contains(n))
         .filter(|&n| graph.get(n).unwrap().iter().any(|e| groups[1].contains(e)))
         .map(|&n| {
             (
@@ -69,7 +77,15 @@ fn determine_canidates_with_clustering_heuristic<'a>(
         .collect()
 }
 
-impl<'input> Solver<'input> for SolverImpl {
+impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Snowverload")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut graph: HashMap<_, _> = input
             .lines()
@@ -87,8 +103,20 @@ impl<'input> Solver<'input> for SolverImpl {
             }
         }
 
+        Ok(Self {
+            graph,
+            seed: DEFAULT_SEED,
+        })
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    fn solve_part_1(&self) -> anyhow::Result<Solution> {
+        let graph = &self.graph;
         let candidates = if graph.len() > 100 {
-            determine_canidates_with_clustering_heuristic(&graph)
+            determine_canidates_with_clustering_heuristic(graph, self.seed)
         } else {
             graph
                 .iter()
@@ -134,32 +162,61 @@ impl<'input> Solver<'input> for SolverImpl {
             })
             .unwrap_or_default();
 
-        let solution = solution.0 * solution.1;
-
-        Ok(Self { solution })
-    }
-
-    fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Group size product",
-            self.solution.to_string(),
+            solution.0 * solution.1,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description("Part 2", "n/a".to_string()))
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(None)
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
+    use super::{determine_canidates_with_clustering_heuristic, SolverImpl};
+    use crate::solvers::{Rng, Solver};
+
+    /// Two clusters of `size` randomly connected components joined by three
+    /// wires, large enough for the clustering heuristic.
+    fn clustered_input(size: usize) -> String {
+        let mut rng = Rng::new(42);
+        let mut lines = Vec::new();
+        for cluster in ["a", "b"] {
+            for i in 0..size {
+                let connected: Vec<_> = (0..2)
+                    .map(|_| (i + 1 + rng.below(size - 1)) % size)
+                    .chain([(i + 1) % size])
+                    .map(|j| format!("{cluster}{j:02}"))
+                    .collect();
+                lines.push(format!("{cluster}{i:02}: {}", connected.join(" ")));
+            }
+        }
+        lines.push("a00: b00".into());
+        lines.push("a10: b10".into());
+        lines.push("a20: b20".into());
+        lines.join("\n")
+    }
+
+    #[test]
+    fn test_heuristic_depends_only_on_seed() -> anyhow::Result<()> {
+        let input = clustered_input(60);
+        // The graphs are hash maps with different random states.
+        let solvers = [SolverImpl::new(&input)?, SolverImpl::new(&input)?];
+        let candidates =
+            |i: usize, seed| determine_canidates_with_clustering_heuristic(&solvers[i].graph, seed);
+        for seed in [1, 2, 3] {
+            assert_eq!(candidates(0, seed), candidates(1, seed));
+            assert_eq!(candidates(1, seed), candidates(1, seed));
+        }
+        Ok(())
+    }
 
     #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
+    fn test_no_part_2() -> anyhow::Result<()> {
         let solver = SolverImpl::new(include_str!("./day25-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "54");
+        assert_eq!(solver.solve_part_2()?, None);
         Ok(())
     }
 }
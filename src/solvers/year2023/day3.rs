@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     datastructures::{grid::GridView, iterators::SurroundIterator2d},
-    solvers::{Solution, Solver},
+    solvers::{MaybeSolution, Solution, Solver, Tag},
 };
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Eq, Hash)]
@@ -59,8 +59,20 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Gear Ratios")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let schematic = GridView::from_separated(b'\n', input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let schematic = GridView::from_separated(b'\n', input);
 
         let mut part_number_sum: u32 = 0;
         let mut gear_ratio_sum: u32 = 0;
@@ -99,34 +111,14 @@ impl<'input> Solver<'input> for SolverImpl {
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Sum of part numbers",
-            self.part_number_sum.to_string(),
+            self.part_number_sum,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description(
             "Sum of gear ratios",
-            self.gear_ratio_sum.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day3-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "4361");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day3-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "467835");
-        Ok(())
+            self.gear_ratio_sum,
+        )))
     }
 }
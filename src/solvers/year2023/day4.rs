@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 use regex::Regex;
 use std::collections::BTreeSet;
 use std::num::ParseIntError;
@@ -17,6 +17,10 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Scratchcards")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let line_pattern = Regex::new(r"^Card\s+(\d+): ([0-9 ]*) \| ([0-9 ]*)$").unwrap();
         let num_winning = input
@@ -40,10 +44,10 @@ impl<'input> Solver<'input> for SolverImpl {
             .filter(|&value| value > 0)
             .map(|num_winning| 1u32 << (num_winning - 1))
             .sum();
-        Ok(Solution::with_description("Points", points.to_string()))
+        Ok(Solution::with_description("Points", points))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut n_copies = vec![1; self.num_winning.len()];
         let mut total_cards = 0;
         for i in 0..self.num_winning.len() {
@@ -53,29 +57,9 @@ impl<'input> Solver<'input> for SolverImpl {
             }
         }
 
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Number of scratch cards",
-            total_cards.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day4-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "13");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day4-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "30");
-        Ok(())
+            total_cards,
+        )))
     }
 }
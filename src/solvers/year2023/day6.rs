@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use std::num::ParseIntError;
 
 fn parse_line(line: &str, prefix: &str) -> anyhow::Result<Vec<u64>> {
@@ -37,6 +37,14 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Wait For It")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut lines = input.lines();
         let times = parse_line(lines.next().expect("times line"), "Time:")?;
@@ -58,37 +66,14 @@ impl<'input> Solver<'input> for SolverImpl {
             .product();
         Ok(Solution::with_description(
             "Product of ways to win (part 1)",
-            ways_to_win.to_string(),
+            ways_to_win,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let time = join_numbers(&self.times)?;
         let distance = join_numbers(&self.distances)?;
         let ways_to_win = calc_ways_to_win(time, distance);
-        Ok(Solution::with_description(
-            "Part 2",
-            ways_to_win.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day6-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "288");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day6-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "71503");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", ways_to_win)))
     }
 }
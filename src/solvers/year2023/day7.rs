@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::convert::{TryFrom, TryInto};
@@ -123,6 +123,10 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Camel Cards")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let hands = input
             .lines()
@@ -149,13 +153,10 @@ impl<'input> Solver<'input> for SolverImpl {
             .enumerate()
             .map(|(i, (_, bid))| (i as u64 + 1) * bid)
             .sum();
-        Ok(Solution::with_description(
-            "Total winnings",
-            winnings.to_string(),
-        ))
+        Ok(Solution::with_description("Total winnings", winnings))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut hands: Vec<_> = self
             .hands
             .iter()
@@ -177,29 +178,9 @@ impl<'input> Solver<'input> for SolverImpl {
             .enumerate()
             .map(|(i, (_, bid))| (i as u64 + 1) * bid)
             .sum();
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Total winnings with jokers",
-            winnings.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day7-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "6440");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day7-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "5905");
-        Ok(())
+            winnings,
+        )))
     }
 }
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use num;
 use regex::Regex;
 use std::collections::HashMap;
@@ -34,6 +34,14 @@ impl SolverImpl<'_> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Haunted Wasteland")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph, Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut lines = input.lines();
         let instructions = lines
@@ -63,13 +71,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         let n_steps = self.solve("AAA", |node| node == "ZZZ")?;
-        Ok(Solution::with_description(
-            "Steps to reach ZZZ",
-            n_steps.to_string(),
-        ))
+        Ok(Solution::with_description("Steps to reach ZZZ", n_steps))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         // Note this is not a general solution, but based on the assumption that for each
         // start node we will reach a target node every n steps with n being constant for the
         // n being constant for a start node.
@@ -85,36 +90,9 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .into_iter()
             .reduce(num::integer::lcm)
             .expect("at least one start node must exist");
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Steps to be only on nodes ending with Z",
-            n_steps.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1a() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day8-1a.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "2");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_1b() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day8-1b.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "6");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day8-2.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "6");
-        Ok(())
+            n_steps,
+        )))
     }
 }
@@ -1,6 +1,6 @@
 use std::num::ParseIntError;
 
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 
 pub struct SolverImpl {
     histories: Vec<Vec<i64>>,
@@ -33,6 +33,14 @@ fn extrapolate_right(input: &[i64]) -> i64 {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Mirage Maintenance")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let histories = input
             .lines()
@@ -53,39 +61,16 @@ impl<'input> Solver<'input> for SolverImpl {
             .sum();
         Ok(Solution::with_description(
             "Sum of extrapolated values",
-            extrapolation.to_string(),
+            extrapolation,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let extrapolation: i64 = self
             .histories
             .iter()
             .map(|history| extrapolate_left(history))
             .sum();
-        Ok(Solution::with_description(
-            "Part 2",
-            extrapolation.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day9-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "114");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day9-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "2");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", extrapolation)))
     }
 }
@@ -1,10 +1,14 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 
 pub struct SolverImpl {
     lists: [Vec<usize>; 2],
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Historian Hysteria")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut lists = [vec![], vec![]];
         for line in input.lines() {
@@ -28,10 +32,10 @@ impl<'input> Solver<'input> for SolverImpl {
             .zip(&self.lists[1])
             .map(|(a, b)| a.abs_diff(*b))
             .sum();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let result: usize = self.lists[0]
             .iter()
             .map(|a| {
@@ -39,26 +43,6 @@ impl<'input> Solver<'input> for SolverImpl {
                     - self.lists[1].partition_point(|x| x < a))
             })
             .sum();
-        Ok(Solution::with_description("Part 2", result.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day1-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "11");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day1-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "31");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
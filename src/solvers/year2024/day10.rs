@@ -1,20 +1,26 @@
 use crate::datastructures::grid::GridView;
 use crate::datastructures::iterators::NeighborIterator2d;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use std::collections::BTreeSet;
 
-fn find_summits(map: &GridView<&[u8]>, trailhead: (usize, usize)) -> Vec<(usize, usize)> {
+/// Heights of the map, where impassable tiles (`.` in some examples) are
+/// `None`.
+type TopographicMap = GridView<Vec<Option<u8>>>;
+
+fn find_summits(map: &TopographicMap, trailhead: (usize, usize)) -> Vec<(usize, usize)> {
     let mut summits = Vec::new();
     let mut to_visit = vec![trailhead];
     while let Some(pos) = to_visit.pop() {
-        let elevation = map[pos];
-        if elevation == b'9' {
+        let Some(elevation) = map[pos] else {
+            continue;
+        };
+        if elevation == 9 {
             summits.push(pos);
             continue;
         }
 
         for neighbor in NeighborIterator2d::new(pos, map.size()) {
-            if let Some(1) = map[neighbor].checked_sub(elevation) {
+            if map[neighbor].and_then(|height| height.checked_sub(elevation)) == Some(1) {
                 to_visit.push(neighbor);
             }
         }
@@ -28,13 +34,25 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Hoof It")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let map = GridView::from_separated(b'\n', input.as_bytes());
+        Self::from_bytes(input.as_bytes())
+    }
+
+    fn from_bytes(input: &'input [u8]) -> anyhow::Result<Self> {
+        let map = GridView::parse_digits_or(input, b'.')?;
         let mut score_sum = 0;
         let mut rating_sum = 0;
         for row in 0..map.height() {
             for col in 0..map.width() {
-                if map[(row, col)] == b'0' {
+                if map[(row, col)] == Some(0) {
                     let summits = find_summits(&map, (row, col));
                     score_sum += summits.iter().collect::<BTreeSet<_>>().len();
                     rating_sum += summits.len();
@@ -48,36 +66,10 @@ impl<'input> Solver<'input> for SolverImpl {
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
-            "Part 1",
-            self.score_sum.to_string(),
-        ))
-    }
-
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
-            "Part 2",
-            self.rating_sum.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day10-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "36");
-        Ok(())
+        Ok(Solution::with_description("Part 1", self.score_sum))
     }
 
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day10-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "81");
-        Ok(())
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description("Part 2", self.rating_sum)))
     }
 }
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 use std::collections::HashMap;
 
 struct StoneOracle {
@@ -27,7 +27,7 @@ impl StoneOracle {
             return result;
         }
         let digits = stone.to_string();
-        if digits.len() % 2 == 0 {
+        if digits.len().is_multiple_of(2) {
             let left = digits[..digits.len() / 2].parse().unwrap();
             let right = digits[digits.len() / 2..].parse().unwrap();
             let result = self.blink(left, steps - 1) + self.blink(right, steps - 1);
@@ -46,6 +46,10 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Plutonian Pebbles")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let stones = input
             .split(' ')
@@ -60,28 +64,15 @@ impl<'input> Solver<'input> for SolverImpl {
         for &stone in &self.stones {
             result += stone_oracle.blink(stone, 25);
         }
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut stone_oracle = StoneOracle::new();
         let mut result = 0;
         for &stone in &self.stones {
             result += stone_oracle.blink(stone, 75);
         }
-        Ok(Solution::with_description("Part 2", result.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day11-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "55312");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
@@ -1,5 +1,4 @@
-use crate::solvers::{Solution, Solver};
-use anyhow::anyhow;
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver, Tag};
 use regex::Regex;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -13,7 +12,9 @@ impl TryFrom<&str> for Prize {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let pattern = Regex::new(r"^Prize: X=(\d+), Y=(\d+)$")?;
-        let captures = pattern.captures(value).ok_or(anyhow!("Invalid input"))?;
+        let captures = pattern
+            .captures(value)
+            .ok_or_else(|| InputError::new(value, "a prize like Prize: X=8400, Y=5400"))?;
         Ok(Self {
             x: captures[1].parse()?,
             y: captures[2].parse()?,
@@ -32,7 +33,9 @@ impl TryFrom<&str> for Button {
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let pattern = Regex::new(r"^Button [AB]: X\+(\d+), Y\+(\d+)$")?;
-        let captures = pattern.captures(value).ok_or(anyhow!("Invalid input"))?;
+        let captures = pattern
+            .captures(value)
+            .ok_or_else(|| InputError::new(value, "a button like Button A: X+94, Y+34"))?;
         Ok(Self {
             dx: captures[1].parse()?,
             dy: captures[2].parse()?,
@@ -78,6 +81,14 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Claw Contraption")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut input = input.lines().filter(|line| !line.trim().is_empty());
         let mut claw_machines = Vec::new();
@@ -100,10 +111,10 @@ impl<'input> Solver<'input> for SolverImpl {
             .iter()
             .filter_map(ClawMachine::fewest_tokens_to_win)
             .sum();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         const CONVERSION: i64 = 10_000_000_000_000;
         let result: u64 = self
             .claw_machines
@@ -117,19 +128,6 @@ impl<'input> Solver<'input> for SolverImpl {
             })
             .filter_map(|cm| cm.fewest_tokens_to_win())
             .sum();
-        Ok(Solution::with_description("Part 2", result.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day13-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "480");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
@@ -1,29 +1,24 @@
-use crate::solvers::{Solution, Solver};
-use anyhow::anyhow;
+use crate::datastructures::point::P2;
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver, SolverParams, Tag};
+use crate::visualization::{Frame, Image, Rgb};
 use regex::Regex;
 use std::cmp::Ordering;
 use std::collections::BTreeSet;
 
-const WIDTH: i64 = 101; //11;
-const HEIGHT: i64 = 103; //7;
+const WIDTH: i64 = 101;
+const HEIGHT: i64 = 103;
 
 #[derive(Debug, Clone)]
 struct Robot {
-    p: (i64, i64),
-    v: (i64, i64),
+    p: P2<i64>,
+    v: P2<i64>,
 }
 
 impl Robot {
     fn position_after(&self, steps: i64, width: i64, height: i64) -> (i64, i64) {
-        let mut x = (self.p.0 + self.v.0 * steps) % width;
-        let mut y = (self.p.1 + steps * self.v.1) % height;
-        if x < 0 {
-            x += width;
-        }
-        if y < 0 {
-            y += height;
-        }
-        (x, y)
+        (self.p + self.v * steps)
+            .mod_floor(&P2(width, height))
+            .into()
     }
 }
 
@@ -31,17 +26,21 @@ impl TryFrom<&str> for Robot {
     type Error = anyhow::Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let pattern = Regex::new(r"^p=(\d+),(\d+) v=(-?\d+),(-?\d+)$")?;
-        let captures = pattern.captures(value).ok_or(anyhow!("Invalid input"))?;
+        let pattern = Regex::new(r"^p=(\d+,\d+) v=(-?\d+,-?\d+)$")?;
+        let captures = pattern
+            .captures(value)
+            .ok_or_else(|| InputError::new(value, "a robot like p=0,4 v=3,-3"))?;
         Ok(Self {
-            p: (captures[1].parse()?, captures[2].parse()?),
-            v: (captures[3].parse()?, captures[4].parse()?),
+            p: captures[1].parse()?,
+            v: captures[2].parse()?,
         })
     }
 }
 
 pub struct SolverImpl {
     robots: Vec<Robot>,
+    /// Width and height of the area, which are smaller in the example.
+    size: (i64, i64),
 }
 
 impl SolverImpl {
@@ -97,38 +96,62 @@ impl SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Restroom Redoubt")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let robots = input
             .lines()
             .map(Robot::try_from)
             .collect::<Result<_, _>>()?;
-        Ok(Self { robots })
+        Ok(Self {
+            robots,
+            size: (WIDTH, HEIGHT),
+        })
+    }
+
+    fn set_params(&mut self, params: &SolverParams) -> anyhow::Result<()> {
+        self.size = (
+            params.get("width", self.size.0)?,
+            params.get("height", self.size.1)?,
+        );
+        Ok(())
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Part 1",
-            self.solve_part_1_for_size(WIDTH, HEIGHT).to_string(),
+            self.solve_part_1_for_size(self.size.0, self.size.1),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description(
             "Part 2",
-            self.solve_part_2_impl().to_string(),
-        ))
+            self.solve_part_2_impl(),
+        )))
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
+    /// Animates the robots in the last seconds before they arrange into the
+    /// Christmas tree.
+    fn visualize(&self) -> anyhow::Result<Vec<Frame>> {
+        const ROBOT_COLOR: Rgb = [0, 200, 0];
 
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day14-1.example"))?;
-        assert_eq!(solver.solve_part_1_for_size(11, 7), 12);
-        Ok(())
+        let tree_time = self.solve_part_2_impl();
+        Ok(((tree_time - 10).max(0)..=tree_time)
+            .map(|i| {
+                let mut image = Image::new(WIDTH as usize, HEIGHT as usize, [0, 0, 0]);
+                for robot in &self.robots {
+                    let (x, y) = robot.position_after(i, WIDTH, HEIGHT);
+                    image.set((y as usize, x as usize), ROBOT_COLOR);
+                }
+                Frame::Image(image)
+            })
+            .collect())
     }
 }
@@ -1,51 +1,22 @@
+use crate::datastructures::direction::Direction4;
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
-use anyhow::anyhow;
+use crate::datastructures::point::P2;
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
+/// Position one step from `pos` by `delta`. The warehouse is walled in, so
+/// that this never leaves the grid.
+fn step(pos: (usize, usize), delta: P2<isize>) -> (usize, usize) {
+    P2::from(pos).wrapping_add_signed(delta).into()
 }
 
-impl TryFrom<u8> for Direction {
-    type Error = anyhow::Error;
-
-    fn try_from(value: u8) -> Result<Self, Self::Error> {
-        match value {
-            b'^' => Ok(Direction::Up),
-            b'v' => Ok(Direction::Down),
-            b'<' => Ok(Direction::Left),
-            b'>' => Ok(Direction::Right),
-            _ => Err(anyhow!("invalid direction")),
-        }
-    }
-}
-
-impl Direction {
-    fn delta(self) -> (isize, isize) {
-        match self {
-            Self::Up => (-1, 0),
-            Self::Down => (1, 0),
-            Self::Left => (0, -1),
-            Self::Right => (0, 1),
-        }
-    }
-}
-
-fn push(grid: &mut GridView<Vec<u8>>, pos: (usize, usize), direction: Direction) -> bool {
-    let (dx, dy) = direction.delta();
-    let push_target = (pos.0.wrapping_add_signed(dx), pos.1.wrapping_add_signed(dy));
+fn push(grid: &mut GridView<Vec<u8>>, pos: (usize, usize), direction: Direction4) -> bool {
+    let delta = P2::from(direction.delta());
+    let push_target = step(pos, delta);
     match grid[push_target] {
         b'#' => false,
         b'O' => {
             if push(grid, push_target, direction) {
-                grid[(
-                    push_target.0.wrapping_add_signed(dx),
-                    push_target.1.wrapping_add_signed(dy),
-                )] = b'O';
+                grid[step(push_target, delta)] = b'O';
                 grid[push_target] = b'.';
                 true
             } else {
@@ -59,21 +30,18 @@ fn push(grid: &mut GridView<Vec<u8>>, pos: (usize, usize), direction: Direction)
 fn push_wide(
     grid: &mut GridView<Vec<u8>>,
     pos: (usize, usize),
-    direction: Direction,
+    direction: Direction4,
     dry_run: bool,
 ) -> bool {
-    let (dx, dy) = direction.delta();
-    let push_target = (pos.0.wrapping_add_signed(dx), pos.1.wrapping_add_signed(dy));
+    let delta = P2::from(direction.delta());
+    let push_target = step(pos, delta);
     match grid[push_target] {
         b'#' => false,
         b'[' | b']' => {
-            if direction == Direction::Right || direction == Direction::Left {
+            if !direction.is_vertical() {
                 if push_wide(grid, push_target, direction, dry_run) {
                     if !dry_run {
-                        grid[(
-                            push_target.0.wrapping_add_signed(dx),
-                            push_target.1.wrapping_add_signed(dy),
-                        )] = grid[push_target];
+                        grid[step(push_target, delta)] = grid[push_target];
                         grid[push_target] = b'.';
                     }
                     true
@@ -85,28 +53,16 @@ fn push_wide(
                 if push_wide(grid, push_target, direction, dry_run)
                     && push_wide(
                         grid,
-                        (
-                            push_target.0,
-                            push_target.1.wrapping_add_signed(delta_other_half),
-                        ),
+                        step(push_target, P2(0, delta_other_half)),
                         direction,
                         dry_run,
                     )
                 {
                     if !dry_run {
-                        grid[(
-                            push_target.0.wrapping_add_signed(dx),
-                            push_target.1.wrapping_add_signed(dy),
-                        )] = grid[push_target];
+                        grid[step(push_target, delta)] = grid[push_target];
                         grid[push_target] = b'.';
-                        let neighbor_target = (
-                            push_target.0,
-                            push_target.1.wrapping_add_signed(delta_other_half),
-                        );
-                        grid[(
-                            push_target.0.wrapping_add_signed(dx),
-                            push_target.1.wrapping_add_signed(dy + delta_other_half),
-                        )] = grid[neighbor_target];
+                        let neighbor_target = step(push_target, P2(0, delta_other_half));
+                        grid[step(neighbor_target, delta)] = grid[neighbor_target];
                         grid[neighbor_target] = b'.';
                     }
                     true
@@ -121,10 +77,18 @@ fn push_wide(
 
 pub struct SolverImpl {
     grid: GridView<Vec<u8>>,
-    movements: Vec<Direction>,
+    movements: Vec<Direction4>,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Warehouse Woes")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut lines_iter = input.lines().peekable();
         let width = lines_iter.peek().map(|line| line.len()).unwrap_or(0);
@@ -142,7 +106,7 @@ impl<'input> Solver<'input> for SolverImpl {
                     .iter()
                     .copied()
                     .filter(|c| !c.is_ascii_whitespace())
-                    .map(Direction::try_from)
+                    .map(Direction4::try_from)
             })
             .collect::<Result<_, _>>()?;
 
@@ -153,19 +117,18 @@ impl<'input> Solver<'input> for SolverImpl {
         let mut grid = self.grid.clone();
         let mut pos = Self::find_starting_pos(&grid);
         for movement in self.movements.iter().copied() {
-            let (dx, dy) = movement.delta();
             if push(&mut grid, pos, movement) {
-                pos = (pos.0.wrapping_add_signed(dx), pos.1.wrapping_add_signed(dy));
+                pos = step(pos, movement.delta().into());
             }
         }
 
         Ok(Solution::with_description(
             "Part 1",
-            Self::sum_gps(&grid, b'O').to_string(),
+            Self::sum_gps(&grid, b'O'),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let widened: Vec<u8> = self
             .grid
             .iter()
@@ -178,65 +141,33 @@ impl<'input> Solver<'input> for SolverImpl {
         let mut grid = GridView::from_vec(self.grid.width() * 2, 0, widened);
         let mut pos = Self::find_starting_pos(&grid);
         for movement in self.movements.iter().copied() {
-            let (dx, dy) = movement.delta();
             if push_wide(&mut grid, pos, movement, true) {
                 push_wide(&mut grid, pos, movement, false);
-                grid[(pos.0.wrapping_add_signed(dx), pos.1.wrapping_add_signed(dy))] = grid[pos];
+                let next_pos = step(pos, movement.delta().into());
+                grid[next_pos] = grid[pos];
                 grid[pos] = b'.';
-                pos = (pos.0.wrapping_add_signed(dx), pos.1.wrapping_add_signed(dy));
+                pos = next_pos;
             }
         }
 
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Part 2",
-            Self::sum_gps(&grid, b'[').to_string(),
-        ))
+            Self::sum_gps(&grid, b'['),
+        )))
     }
 }
 
 impl SolverImpl {
     fn find_starting_pos(grid: &GridView<Vec<u8>>) -> (usize, usize) {
-        grid.iter()
-            .position(|c| c == b'@')
-            .map(|i| grid.nth_index(i))
+        grid.indexed_iter()
+            .find_map(|(pos, c)| (c == b'@').then_some(pos))
             .expect("no starting position")
     }
 
     fn sum_gps(grid: &GridView<Vec<u8>>, marker: u8) -> usize {
-        grid.iter()
-            .enumerate()
-            .filter(|(_, c)| *c == marker)
-            .map(|(i, _)| {
-                let (row, col) = grid.nth_index(i);
-                100 * row + col
-            })
+        grid.indexed_iter()
+            .filter(|&(_, c)| c == marker)
+            .map(|((row, col), _)| 100 * row + col)
             .sum()
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1_small() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day15-1-small.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "2028");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_1_large() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day15-1-large.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "10092");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day15-1-large.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "9021");
-        Ok(())
-    }
-}
@@ -1,123 +1,9 @@
-use crate::solvers::{Solution, Solver};
+mod vm;
+
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use anyhow::anyhow;
 use regex::Regex;
-
-type Word = u128;
-
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
-enum Operand {
-    Literal(Word),
-    Combo(Word),
-    Ignored(Word),
-}
-
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
-enum OpCode {
-    Adv(Operand),
-    Bxl(Operand),
-    Bst(Operand),
-    Jnz(Operand),
-    Bxc(Operand),
-    Out(Operand),
-    Bdv(Operand),
-    Cdv(Operand),
-}
-
-impl TryFrom<(&str, &str)> for OpCode {
-    type Error = anyhow::Error;
-
-    fn try_from(value: (&str, &str)) -> Result<Self, Self::Error> {
-        match value.0 {
-            "0" => Ok(OpCode::Adv(Operand::Combo(value.1.parse()?))),
-            "1" => Ok(OpCode::Bxl(Operand::Literal(value.1.parse()?))),
-            "2" => Ok(OpCode::Bst(Operand::Combo(value.1.parse()?))),
-            "3" => Ok(OpCode::Jnz(Operand::Literal(value.1.parse()?))),
-            "4" => Ok(OpCode::Bxc(Operand::Ignored(value.1.parse()?))),
-            "5" => Ok(OpCode::Out(Operand::Combo(value.1.parse()?))),
-            "6" => Ok(OpCode::Bdv(Operand::Combo(value.1.parse()?))),
-            "7" => Ok(OpCode::Cdv(Operand::Combo(value.1.parse()?))),
-            _ => Err(anyhow!("Invalid opcode")),
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-struct Processor<'mem> {
-    registers: [Word; 3],
-    instruction_pointer: usize,
-    memory: &'mem [OpCode],
-    output: Vec<String>,
-}
-
-impl<'mem> Processor<'mem> {
-    fn new(registers: [Word; 3], memory: &'mem [OpCode]) -> Self {
-        Self {
-            registers,
-            instruction_pointer: 0,
-            memory,
-            output: Vec::new(),
-        }
-    }
-
-    fn run(&mut self) -> String {
-        while self.instruction_pointer < self.memory.len() {
-            self.step();
-        }
-        self.output.join(",")
-    }
-
-    fn load(&self, operand: Operand) -> Word {
-        match operand {
-            Operand::Literal(value) => value,
-            Operand::Combo(index) => match index {
-                0..=3 => index,
-                4..=6 => self.registers[(index - 4) as usize],
-                _ => panic!("Invalid combo operand."),
-            },
-            Operand::Ignored(_) => panic!("Usage of ignored operand."),
-        }
-    }
-
-    fn step(&mut self) {
-        let instruction = self.memory[self.instruction_pointer];
-        self.instruction_pointer += 1;
-        match instruction {
-            OpCode::Adv(operand) => {
-                let operand_value = self.load(operand);
-                self.registers[0] /= 1 << operand_value;
-            }
-            OpCode::Bxl(operand) => {
-                let operand_value = self.load(operand);
-                self.registers[1] ^= operand_value;
-            }
-            OpCode::Bst(operand) => {
-                let operand_value = self.load(operand);
-                self.registers[1] = operand_value & 0b0111;
-            }
-            OpCode::Jnz(operand) => {
-                if self.registers[0] != 0 {
-                    self.instruction_pointer = self.load(operand) as usize;
-                }
-            }
-            OpCode::Bxc(_) => {
-                self.registers[1] ^= self.registers[2];
-            }
-            OpCode::Out(operand) => {
-                let operand_value = self.load(operand);
-                self.output
-                    .push(((operand_value & 0b0111) as u8).to_string());
-            }
-            OpCode::Bdv(operand) => {
-                let operand_value = self.load(operand);
-                self.registers[1] = self.registers[0] / (1 << operand_value);
-            }
-            OpCode::Cdv(operand) => {
-                let operand_value = self.load(operand);
-                self.registers[2] = self.registers[0] / (1 << operand_value);
-            }
-        }
-    }
-}
+use vm::{OpCode, Processor, Word};
 
 fn extract_input_value(line: &str) -> anyhow::Result<&str> {
     let regex = Regex::new(r".*:\s*(.*)$")?;
@@ -136,6 +22,14 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Chronospatial Computer")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Vm]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut lines = input.lines();
         let registers = [
@@ -167,7 +61,7 @@ impl<'input> Solver<'input> for SolverImpl {
         Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let target_string = self
             .targets
             .iter()
@@ -232,14 +126,10 @@ impl<'input> Solver<'input> for SolverImpl {
                 self.inc_chosen_producers(15, &mut chosen_producers, &producing_bits);
             }
         }
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Part 1",
-            candidates
-                .iter()
-                .min()
-                .ok_or(anyhow!("No solution"))?
-                .to_string(),
-        ))
+            *candidates.iter().min().ok_or(anyhow!("No solution"))?,
+        )))
     }
 }
 
@@ -259,16 +149,3 @@ impl SolverImpl {
         }
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day17-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "4,6,3,5,6,3,5,2,1,0");
-        Ok(())
-    }
-}
@@ -0,0 +1,120 @@
+//! Interpreter for the 3-bit computer of the puzzle.
+
+use anyhow::anyhow;
+
+pub type Word = u128;
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum Operand {
+    Literal(Word),
+    Combo(Word),
+    Ignored(Word),
+}
+
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum OpCode {
+    Adv(Operand),
+    Bxl(Operand),
+    Bst(Operand),
+    Jnz(Operand),
+    Bxc(Operand),
+    Out(Operand),
+    Bdv(Operand),
+    Cdv(Operand),
+}
+
+impl TryFrom<(&str, &str)> for OpCode {
+    type Error = anyhow::Error;
+
+    fn try_from(value: (&str, &str)) -> Result<Self, Self::Error> {
+        match value.0 {
+            "0" => Ok(OpCode::Adv(Operand::Combo(value.1.parse()?))),
+            "1" => Ok(OpCode::Bxl(Operand::Literal(value.1.parse()?))),
+            "2" => Ok(OpCode::Bst(Operand::Combo(value.1.parse()?))),
+            "3" => Ok(OpCode::Jnz(Operand::Literal(value.1.parse()?))),
+            "4" => Ok(OpCode::Bxc(Operand::Ignored(value.1.parse()?))),
+            "5" => Ok(OpCode::Out(Operand::Combo(value.1.parse()?))),
+            "6" => Ok(OpCode::Bdv(Operand::Combo(value.1.parse()?))),
+            "7" => Ok(OpCode::Cdv(Operand::Combo(value.1.parse()?))),
+            _ => Err(anyhow!("Invalid opcode")),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Processor<'mem> {
+    registers: [Word; 3],
+    instruction_pointer: usize,
+    memory: &'mem [OpCode],
+    output: Vec<String>,
+}
+
+impl<'mem> Processor<'mem> {
+    pub fn new(registers: [Word; 3], memory: &'mem [OpCode]) -> Self {
+        Self {
+            registers,
+            instruction_pointer: 0,
+            memory,
+            output: Vec::new(),
+        }
+    }
+
+    pub fn run(&mut self) -> String {
+        while self.instruction_pointer < self.memory.len() {
+            self.step();
+        }
+        self.output.join(",")
+    }
+
+    fn load(&self, operand: Operand) -> Word {
+        match operand {
+            Operand::Literal(value) => value,
+            Operand::Combo(index) => match index {
+                0..=3 => index,
+                4..=6 => self.registers[(index - 4) as usize],
+                _ => panic!("Invalid combo operand."),
+            },
+            Operand::Ignored(_) => panic!("Usage of ignored operand."),
+        }
+    }
+
+    fn step(&mut self) {
+        let instruction = self.memory[self.instruction_pointer];
+        self.instruction_pointer += 1;
+        match instruction {
+            OpCode::Adv(operand) => {
+                let operand_value = self.load(operand);
+                self.registers[0] /= 1 << operand_value;
+            }
+            OpCode::Bxl(operand) => {
+                let operand_value = self.load(operand);
+                self.registers[1] ^= operand_value;
+            }
+            OpCode::Bst(operand) => {
+                let operand_value = self.load(operand);
+                self.registers[1] = operand_value & 0b0111;
+            }
+            OpCode::Jnz(operand) => {
+                if self.registers[0] != 0 {
+                    self.instruction_pointer = self.load(operand) as usize;
+                }
+            }
+            OpCode::Bxc(_) => {
+                self.registers[1] ^= self.registers[2];
+            }
+            OpCode::Out(operand) => {
+                let operand_value = self.load(operand);
+                self.output
+                    .push(((operand_value & 0b0111) as u8).to_string());
+            }
+            OpCode::Bdv(operand) => {
+                let operand_value = self.load(operand);
+                self.registers[1] = self.registers[0] / (1 << operand_value);
+            }
+            OpCode::Cdv(operand) => {
+                let operand_value = self.load(operand);
+                self.registers[2] = self.registers[0] / (1 << operand_value);
+            }
+        }
+    }
+}
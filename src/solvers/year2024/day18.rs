@@ -1,5 +1,5 @@
 use crate::datastructures::iterators::NeighborIterator2d;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, SolverParams, Tag};
 use anyhow::anyhow;
 use std::collections::{BTreeSet, VecDeque};
 
@@ -7,9 +7,21 @@ type Pos = (usize, usize);
 
 pub struct SolverImpl {
     byte_positions: Vec<Pos>,
+    /// Width and height of the memory space, which is smaller in the example.
+    size: usize,
+    /// Number of bytes fallen for part 1.
+    n_fallen: usize,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("RAM Run")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let byte_positions = input
             .lines()
@@ -21,19 +33,32 @@ impl<'input> Solver<'input> for SolverImpl {
                 }
             })
             .collect();
-        Ok(Self { byte_positions })
+        Ok(Self {
+            byte_positions,
+            size: 71,
+            n_fallen: 1024,
+        })
+    }
+
+    fn set_params(&mut self, params: &SolverParams) -> anyhow::Result<()> {
+        self.size = params.get("size", self.size)?;
+        self.n_fallen = params.get("bytes", self.n_fallen)?;
+        Ok(())
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Part 1",
-            self.solve_part_1_general((71, 71), 1024)?.to_string(),
+            self.solve_part_1_general((self.size, self.size), self.n_fallen)?,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        let (x, y) = self.solve_part_2_general((71, 71));
-        Ok(Solution::with_description("Part 2", format!("{x},{y}")))
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        let (x, y) = self.solve_part_2_general((self.size, self.size));
+        Ok(Some(Solution::with_description(
+            "Part 2",
+            format!("{x},{y}"),
+        )))
     }
 }
 
@@ -77,23 +102,3 @@ impl SolverImpl {
         self.byte_positions[left_bound - 1]
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day18-1.example"))?;
-        assert_eq!(solver.solve_part_1_general((7, 7), 12)?, 22);
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day18-1.example"))?;
-        assert_eq!(solver.solve_part_2_general((7, 7)), (6, 1));
-        Ok(())
-    }
-}
@@ -1,5 +1,5 @@
-use crate::solvers::{Solution, Solver};
-use std::collections::HashMap;
+use crate::datastructures::hash::FastMap;
+use crate::solvers::{MaybeSolution, Solution, Solver};
 
 #[derive(Clone, Debug)]
 struct Trie {
@@ -59,6 +59,10 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Linen Layout")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut trie = Trie::new();
         let mut max_word_len = 0;
@@ -85,17 +89,17 @@ impl<'input> Solver<'input> for SolverImpl {
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         let result = self.counts.iter().filter(|&&count| count > 0).count();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let result: usize = self.counts.iter().sum();
-        Ok(Solution::with_description("Part 2", result.to_string()))
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
 
 struct ArrangementCounter<'a> {
-    memo: HashMap<&'a [u8], usize>,
+    memo: FastMap<&'a [u8], usize>,
     trie: &'a Trie,
     max_word_len: usize,
 }
@@ -103,7 +107,7 @@ struct ArrangementCounter<'a> {
 impl<'a> ArrangementCounter<'a> {
     pub fn new(trie: &'a Trie, max_word_len: usize) -> Self {
         Self {
-            memo: HashMap::new(),
+            memo: FastMap::default(),
             trie,
             max_word_len,
         }
@@ -131,23 +135,3 @@ impl<'a> ArrangementCounter<'a> {
         result
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day19-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "6");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day19-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "16");
-        Ok(())
-    }
-}
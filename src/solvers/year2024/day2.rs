@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 
 pub struct SolverImpl {
     reports: Vec<Vec<i64>>,
@@ -21,6 +21,10 @@ fn is_safe<'a>(levels: impl IntoIterator<Item = &'a i64>) -> bool {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Red-Nosed Reports")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let reports = input
             .lines()
@@ -40,10 +44,10 @@ impl<'input> Solver<'input> for SolverImpl {
             .iter()
             .filter(|&report| is_safe(report))
             .count();
-        Ok(Solution::with_description("Part 1", num_safe.to_string()))
+        Ok(Solution::with_description("Part 1", num_safe))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let num_safe = self
             .reports
             .iter()
@@ -55,26 +59,6 @@ impl<'input> Solver<'input> for SolverImpl {
             })
             .count();
 
-        Ok(Solution::with_description("Part 2", num_safe.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day2-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "2");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day2-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "4");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", num_safe)))
     }
 }
@@ -1,112 +1,117 @@
 use crate::datastructures::grid::GridView;
-use crate::datastructures::iterators::NeighborIterator2d;
-use crate::solvers::{Solution, Solver};
-use std::collections::{BTreeSet, VecDeque};
+use crate::datastructures::iterators::ManhattanIterator2d;
+use crate::datastructures::search::{bfs_grid, DistanceMap};
+use crate::solvers::{MaybeSolution, Solution, Solver, SolverParams, Tag, Value};
+use std::ops::RangeInclusive;
 
 pub struct SolverImpl<'input> {
     grid: GridView<&'input [u8]>,
-    distance_grid: GridView<Vec<(usize, (usize, usize))>>,
+    /// Distances to the target along the track.
+    distances: DistanceMap,
     start_pos: (usize, usize),
     target: (usize, usize),
+    /// Minimum number of picoseconds a cheat must save to be counted.
+    threshold: usize,
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Race Condition")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid, Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let grid = GridView::from_separated(b'\n', input.as_bytes());
         let start_pos = grid
-            .iter()
-            .position(|c| c == b'S')
-            .map(|i| grid.nth_index(i))
+            .indexed_iter()
+            .find_map(|(pos, c)| (c == b'S').then_some(pos))
             .expect("No start position found");
         let target = grid
-            .iter()
-            .position(|c| c == b'E')
-            .map(|i| grid.nth_index(i))
+            .indexed_iter()
+            .find_map(|(pos, c)| (c == b'E').then_some(pos))
             .expect("No target found");
 
-        let distances = vec![(0usize, target); grid.width() * grid.height()];
-        let mut distance_grid = GridView::from_vec(grid.width(), 0, distances);
-
-        let mut to_visit = VecDeque::new();
-        to_visit.push_back((target, 0, target));
-        let mut visited = BTreeSet::new();
-
-        while let Some((pos, distance, prev_pos)) = to_visit.pop_front() {
-            if visited.contains(&pos) {
-                continue;
-            }
-            visited.insert(pos);
-
-            distance_grid[pos] = (distance, prev_pos);
-
-            for neighbor in NeighborIterator2d::new(pos, grid.size()) {
-                if grid[neighbor] != b'#' {
-                    to_visit.push_back((neighbor, distance + 1, pos));
-                }
-            }
-        }
+        let distances = bfs_grid(grid.size(), [target], |pos| grid[pos] != b'#');
 
         Ok(Self {
             grid,
             start_pos,
             target,
-            distance_grid,
+            distances,
+            threshold: 100,
         })
     }
 
+    fn set_params(&mut self, params: &SolverParams) -> anyhow::Result<()> {
+        self.threshold = params.get("threshold", self.threshold)?;
+        Ok(())
+    }
+
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         Ok(Solution::with_description(
             "Part 1",
-            self.count_cheats(2, 100).to_string(),
+            self.count_cheats(2, self.threshold),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description(
             "Part 2",
-            self.count_cheats(20, 100).to_string(),
-        ))
+            self.count_cheats(20, self.threshold),
+        )))
+    }
+
+    fn solve_part_2_with(&self, part_1: &Solution) -> anyhow::Result<MaybeSolution> {
+        // Part 1 already counted the cheats of 2 ps.
+        let &Value::Integer(num_short_cheats) = part_1.value() else {
+            return self.solve_part_2();
+        };
+        Ok(Some(Solution::with_description(
+            "Part 2",
+            num_short_cheats + self.count_cheats_in(3..=20, self.threshold) as i128,
+        )))
     }
 }
 
 impl SolverImpl<'_> {
-    fn count_cheats(&self, max_cheat_ps: isize, saved_ps_threshold_to_count: usize) -> usize {
+    fn count_cheats(&self, max_cheat_ps: usize, saved_ps_threshold_to_count: usize) -> usize {
+        self.count_cheats_in(2..=max_cheat_ps, saved_ps_threshold_to_count)
+    }
+
+    fn count_cheats_in(
+        &self,
+        cheat_ps: RangeInclusive<usize>,
+        saved_ps_threshold_to_count: usize,
+    ) -> usize {
+        let max_cheat_ps = *cheat_ps.end();
         let mut pos = self.start_pos;
         let mut num_cheats = 0;
         while pos != self.target {
-            let (distance, prev_pos) = self.distance_grid[pos];
-
-            for dx in -max_cheat_ps..=max_cheat_ps {
-                for dy in -max_cheat_ps..=max_cheat_ps {
-                    let cheat_steps: usize = dx.unsigned_abs() + dy.unsigned_abs();
-                    if cheat_steps > max_cheat_ps as usize || cheat_steps < 2 {
-                        continue;
-                    }
-
-                    if let Some(cheat_target) = pos
-                        .0
-                        .checked_add_signed(dx)
-                        .and_then(|x| pos.1.checked_add_signed(dy).map(|y| (x, y)))
-                    {
-                        if cheat_target.0 >= self.grid.height()
-                            || cheat_target.1 >= self.grid.width()
-                        {
-                            continue;
-                        }
-                        if self.grid[cheat_target] != b'#' {
-                            if let Some(saving) = distance
-                                .checked_sub(self.distance_grid[cheat_target].0 + cheat_steps)
-                            {
-                                if saving >= saved_ps_threshold_to_count {
-                                    num_cheats += 1;
-                                }
-                            }
-                        }
+            let distance = self
+                .distances
+                .distance(pos)
+                .expect("start should be connected to the target");
+
+            for (cheat_target, cheat_steps) in
+                ManhattanIterator2d::new(pos, self.grid.size(), max_cheat_ps)
+            {
+                if !cheat_ps.contains(&cheat_steps) || self.grid[cheat_target] == b'#' {
+                    continue;
+                }
+                let Some(target_distance) = self.distances.distance(cheat_target) else {
+                    continue;
+                };
+                if let Some(saving) = distance.checked_sub(target_distance + cheat_steps) {
+                    if saving >= saved_ps_threshold_to_count {
+                        num_cheats += 1;
                     }
                 }
             }
 
-            pos = prev_pos;
+            pos = self.distances.predecessor(pos).unwrap();
         }
 
         num_cheats
@@ -154,4 +159,14 @@ mod test {
         assert_eq!(solver.count_cheats(20, 50), 285);
         Ok(())
     }
+
+    #[test]
+    fn test_part_2_extends_part_1() -> anyhow::Result<()> {
+        let solver = SolverImpl::new(include_str!("./day20-1.example"))?;
+        assert_eq!(
+            solver.count_cheats(2, 50) + solver.count_cheats_in(3..=20, 50),
+            solver.count_cheats(20, 50)
+        );
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
-use crate::solvers::{Solution, Solver};
-use std::collections::HashMap;
+use crate::datastructures::hash::FastMap;
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use std::fmt::{Display, Formatter};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -191,6 +191,14 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Keypad Conundrum")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let codes = input
             .lines()
@@ -201,17 +209,11 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
-            "Part 1",
-            self.solve(2).to_string(),
-        ))
+        Ok(Solution::with_description("Part 1", self.solve(2)))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
-            "Part 2",
-            self.solve(25).to_string(),
-        ))
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(Some(Solution::with_description("Part 2", self.solve(25))))
     }
 }
 
@@ -265,13 +267,13 @@ impl SolverImpl<'_> {
 }
 
 struct MemoizedMinPathLengthStackedDirKeypads {
-    memo: HashMap<(Vec<u8>, usize), usize>,
+    memo: FastMap<(Vec<u8>, usize), usize>,
 }
 
 impl MemoizedMinPathLengthStackedDirKeypads {
     pub fn new() -> Self {
         Self {
-            memo: HashMap::new(),
+            memo: FastMap::default(),
         }
     }
 
@@ -308,16 +310,3 @@ impl MemoizedMinPathLengthStackedDirKeypads {
         steps
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day21-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "126384");
-        Ok(())
-    }
-}
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 use std::collections::VecDeque;
 
 struct Rng {
@@ -27,6 +27,10 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Monkey Market")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let seeds = input
             .lines()
@@ -42,10 +46,10 @@ impl<'input> Solver<'input> for SolverImpl {
             .iter()
             .map(|&seed| Rng::new(seed).nth(1999).unwrap())
             .sum();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut bananas = [0usize; 19 * 19 * 19 * 19];
         for &seed in self.seeds.iter() {
             let mut sold = [false; 19 * 19 * 19 * 19];
@@ -74,26 +78,6 @@ impl<'input> Solver<'input> for SolverImpl {
             }
         }
         let result = bananas.iter().max().unwrap();
-        Ok(Solution::with_description("Part 2", result.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day22-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "37327623");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day22-2.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "23");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", *result)))
     }
 }
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use std::collections::BTreeSet;
 
 struct Graph {
@@ -47,6 +47,14 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("LAN Party")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut graph = Graph::new(26 * 26);
         for line in input.lines() {
@@ -79,10 +87,10 @@ impl<'input> Solver<'input> for SolverImpl {
                 }
             }
         }
-        Ok(Solution::with_description("Part 1", count.to_string()))
+        Ok(Solution::with_description("Part 1", count))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut largest_clique = BTreeSet::new();
         for seed_node in 0..26 * 26 {
             let mut clique = BTreeSet::new();
@@ -101,29 +109,9 @@ impl<'input> Solver<'input> for SolverImpl {
         }
         let mut node_names: Vec<_> = largest_clique.iter().copied().map(computer_name).collect();
         node_names.sort_unstable();
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Part 2",
-            node_names.join(",").to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day23-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "7");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day23-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "co,de,ka,ta");
-        Ok(())
+            node_names.join(","),
+        )))
     }
 }
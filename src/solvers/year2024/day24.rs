@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Progress, Solution, Solver, Tag};
 use anyhow::anyhow;
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
@@ -178,9 +178,18 @@ fn swap(circuit: &mut HashMap<String, Signal>, a_ref: &str, b_ref: &str) {
 
 pub struct SolverImpl {
     circuit: HashMap<String, Signal>,
+    progress: Progress,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Crossed Wires")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Vm]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut circuit = HashMap::new();
         let mut lines = input.lines();
@@ -204,7 +213,10 @@ impl<'input> Solver<'input> for SolverImpl {
             }
         }
 
-        Ok(Self { circuit })
+        Ok(Self {
+            circuit,
+            progress: Progress::default(),
+        })
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
@@ -217,10 +229,10 @@ impl<'input> Solver<'input> for SolverImpl {
             }
         }
 
-        Ok(Solution::with_description("Part 1", acc.to_string()))
+        Ok(Solution::with_description("Part 1", acc))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut circuit = self.circuit.clone();
         let nodes: Vec<_> = circuit
             .keys()
@@ -229,6 +241,7 @@ impl<'input> Solver<'input> for SolverImpl {
             .collect();
         let mut correct_outputs: HashSet<String> = HashSet::with_capacity(circuit.len());
         let mut swapped: Vec<String> = Vec::with_capacity(8);
+        self.progress.start(45);
         for i in 0..45 {
             let mut trace = Some(HashSet::new());
             evaluate(&circuit, &format!("z{i:02}"), &mut trace);
@@ -287,33 +300,19 @@ impl<'input> Solver<'input> for SolverImpl {
                     }
                 }
             }
+            self.progress.advance(1);
         }
+        self.progress.finish();
 
         swapped.sort_unstable();
 
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Part 2",
-            swapped.join(",").to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1a() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day24-1a.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "4");
-        Ok(())
+            swapped.join(","),
+        )))
     }
 
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day24-1b.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "2024");
-        Ok(())
+    fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
     }
 }
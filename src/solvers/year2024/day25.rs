@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Type {
@@ -20,6 +20,10 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Code Chronicle")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut keys_and_locks = [vec![], vec![]];
         let mut input_type: Option<Type> = None;
@@ -66,23 +70,10 @@ impl<'input> Solver<'input> for SolverImpl {
                     })
             })
             .count();
-        Ok(Solution::with_description("Part 1", result.to_string()))
-    }
-
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description("Part 2", "n/a".to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
 
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day25-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "3");
-        Ok(())
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(None)
     }
 }
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use regex::Regex;
 
 pub struct SolverImpl<'input> {
@@ -6,6 +6,14 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Mull It Over")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Vm]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         Ok(Self { input })
     }
@@ -22,10 +30,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
                     .product::<u64>()
             })
             .sum();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let re = Regex::new(r"(do|don't|mul)\(((\d{1,3}),(\d{1,3}))?\)").unwrap();
         let mut mul_enabled = true;
         let result: u64 = re
@@ -34,41 +42,19 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
                 match &m[1] {
                     "do" => mul_enabled = true,
                     "don't" => mul_enabled = false,
-                    "mul" => {
-                        if mul_enabled {
-                            return m
-                                .iter()
-                                .skip(1)
-                                .flatten()
-                                .filter_map(|c| c.as_str().parse::<u64>().ok())
-                                .product::<u64>();
-                        }
+                    "mul" if mul_enabled => {
+                        return m
+                            .iter()
+                            .skip(1)
+                            .flatten()
+                            .filter_map(|c| c.as_str().parse::<u64>().ok())
+                            .product::<u64>();
                     }
                     _ => {}
                 }
                 0
             })
             .sum();
-        Ok(Solution::with_description("Part 2", result.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day3-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "161");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day3-2.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "48");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
@@ -1,11 +1,19 @@
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 
 pub struct SolverImpl<'input> {
     grid: GridView<&'input [u8]>,
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Ceres Search")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         Ok(Self {
             grid: GridView::from_separated(b'\n', input.as_bytes()),
@@ -13,30 +21,24 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
-        let mut xmas_count = 0usize;
-
-        for row in 0..self.grid.height() {
-            for col in 0..self.grid.width() {
-                xmas_count += [
-                    (0, -1),
-                    (0, 1),
-                    (-1, 0),
-                    (1, 0),
-                    (-1, -1),
-                    (1, 1),
-                    (-1, 1),
-                    (1, -1),
-                ]
-                .iter()
-                .filter(|&direction| self.check_for_xmas((row, col), *direction))
-                .count();
-            }
-        }
-
-        Ok(Solution::with_description("Part 1", xmas_count.to_string()))
+        let grid = &self.grid;
+        let xmas_count: usize = (0..grid.height())
+            .map(|row| grid.row(row))
+            .chain((0..grid.width()).map(|col| grid.col(col)))
+            .chain(grid.diags())
+            .chain(grid.anti_diags())
+            .map(|line| {
+                let line: Vec<_> = line.iter().collect();
+                line.windows(4)
+                    .filter(|&word| word == b"XMAS" || word == b"SAMX")
+                    .count()
+            })
+            .sum();
+
+        Ok(Solution::with_description("Part 1", xmas_count))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut crossmas_count = 0usize;
 
         for row in 1..self.grid.height() - 1 {
@@ -56,47 +58,11 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             }
         }
 
-        Ok(Solution::with_description(
-            "Part 2",
-            crossmas_count.to_string(),
-        ))
+        Ok(Some(Solution::with_description("Part 2", crossmas_count)))
     }
 }
 
 impl SolverImpl<'_> {
-    fn check_for_xmas(&self, (row, col): (usize, usize), direction: (isize, isize)) -> bool {
-        if self.grid[(row, col)] != b'X' {
-            return false;
-        }
-
-        if direction.0 < 0 && row < 3 {
-            return false;
-        }
-        if direction.0 > 0 && row >= self.grid.height() - 3 {
-            return false;
-        }
-        if direction.1 < 0 && col < 3 {
-            return false;
-        }
-        if direction.1 > 0 && col >= self.grid.width() - 3 {
-            return false;
-        }
-
-        for (i, c) in [b'M', b'A', b'S'].iter().enumerate() {
-            if self.grid[(
-                row.checked_add_signed((i + 1) as isize * direction.0)
-                    .unwrap(),
-                col.checked_add_signed((i + 1) as isize * direction.1)
-                    .unwrap(),
-            )] != *c
-            {
-                return false;
-            }
-        }
-
-        true
-    }
-
     fn check_for_mas(&self, (row, col): (usize, usize), direction: (isize, isize)) -> bool {
         if self.grid[(row, col)] != b'A' {
             return false;
@@ -115,23 +81,3 @@ impl SolverImpl<'_> {
         false
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day4-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "18");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day4-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "9");
-        Ok(())
-    }
-}
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
 pub struct PageSet(u128);
@@ -26,6 +26,12 @@ pub struct PageOrder {
     not_before: [PageSet; 100],
 }
 
+impl Default for PageOrder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PageOrder {
     pub fn new() -> Self {
         Self {
@@ -49,6 +55,14 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Print Queue")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut lines = input.lines();
         let mut page_order = PageOrder::new();
@@ -95,10 +109,10 @@ impl<'input> Solver<'input> for SolverImpl {
                 update_order[update_order.len() / 2] as u64
             })
             .sum();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let result: u64 = self
             .page_updates
             .iter()
@@ -126,26 +140,6 @@ impl<'input> Solver<'input> for SolverImpl {
                 0
             })
             .sum();
-        Ok(Solution::with_description("Part 2", result.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day5-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "143");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day5-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "123");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
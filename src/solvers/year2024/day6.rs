@@ -1,98 +1,92 @@
+use crate::datastructures::direction::{Direction4, Pose};
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Progress, Solution, Solver, Tag};
 use std::collections::HashSet;
 use std::ops::{Deref, Index};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Default for Direction {
-    fn default() -> Self {
-        Self::North
-    }
+pub struct SolverImpl<'input> {
+    input: &'input str,
+    /// Positions and directions of the guard on the way out of the map,
+    /// shared by both parts.
+    route: Option<Vec<Pose>>,
+    progress: Progress,
 }
 
-impl Direction {
-    fn vector(&self) -> (isize, isize) {
-        match self {
-            Self::North => (-1, 0),
-            Self::South => (1, 0),
-            Self::East => (0, 1),
-            Self::West => (0, -1),
-        }
+impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Guard Gallivant")
     }
 
-    fn turn(&self) -> Self {
-        match self {
-            Self::North => Self::East,
-            Self::East => Self::South,
-            Self::South => Self::West,
-            Self::West => Self::North,
-        }
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
     }
-}
-
-pub struct SolverImpl<'input> {
-    input: &'input str,
-}
 
-impl<'input> Solver<'input> for SolverImpl<'input> {
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        Ok(Self { input })
+        Ok(Self {
+            input,
+            route: None,
+            progress: Progress::default(),
+        })
     }
 
-    fn solve_part_1(&self) -> anyhow::Result<Solution> {
+    fn precompute(&mut self) -> anyhow::Result<()> {
         let grid = GridView::from_separated(b'\n', self.input.as_bytes());
         let starting_pos = Self::find_starting_pos(&grid)
             .ok_or_else(|| anyhow::anyhow!("no starting position found"))?;
-        let mut pos = starting_pos;
-        let mut direction = Direction::default();
-        let mut distinct_positions = HashSet::with_capacity(grid.height() * grid.width());
-        distinct_positions.insert(pos);
-        while let Some((new_pos, new_direction)) = Self::next_pos(&grid, pos, direction) {
-            pos = new_pos;
-            direction = new_direction;
-            distinct_positions.insert(pos);
-        }
+        self.route = Some(
+            std::iter::successors(Some(Pose::new(starting_pos, Direction4::Up)), |&pose| {
+                Self::next_pose(&grid, pose)
+            })
+            .collect(),
+        );
+        Ok(())
+    }
+
+    fn solve_part_1(&self) -> anyhow::Result<Solution> {
+        let distinct_positions: HashSet<_> = self.route()?.iter().map(|pose| pose.pos).collect();
         Ok(Solution::with_description(
             "Part 1",
-            distinct_positions.len().to_string(),
+            distinct_positions.len(),
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        let route = self.route()?;
         let input: Vec<u8> = self.input.as_bytes().to_owned();
         let mut grid = GridView::from_separated_vec(b'\n', input);
-        let starting_pos = Self::find_starting_pos(&grid)
-            .ok_or_else(|| anyhow::anyhow!("no starting position found"))?;
-        let mut pos = starting_pos;
-        let mut direction = Direction::default();
+        self.progress.start(route.len() as u64 - 1);
+
         let mut obstructions = HashSet::new();
         let mut visited = HashSet::new();
-        visited.insert(pos);
-        while let Some((new_pos, new_direction)) = Self::next_pos(&grid, pos, direction) {
+        visited.insert(route[0].pos);
+        for (&pose, &Pose { pos: new_pos, .. }) in route.iter().zip(&route[1..]) {
             grid[new_pos] = b'#';
-            if !visited.contains(&new_pos) && self.check_is_loop(&grid, pos, direction) {
+            if !visited.contains(&new_pos) && self.check_is_loop(&grid, pose) {
                 obstructions.insert(new_pos);
             }
             grid[new_pos] = b'.';
             visited.insert(new_pos);
-            pos = new_pos;
-            direction = new_direction;
+            self.progress.advance(1);
         }
-        Ok(Solution::with_description(
+        self.progress.finish();
+        Ok(Some(Solution::with_description(
             "Part 2",
-            obstructions.len().to_string(),
-        ))
+            obstructions.len(),
+        )))
+    }
+
+    fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
     }
 }
 
 impl SolverImpl<'_> {
+    fn route(&self) -> anyhow::Result<&[Pose]> {
+        self.route
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("route must be precomputed"))
+    }
+
     fn find_starting_pos<T>(grid: &GridView<T>) -> Option<(usize, usize)>
     where
         T: Deref,
@@ -108,68 +102,28 @@ impl SolverImpl<'_> {
         None
     }
 
-    fn next_pos<T>(
-        grid: &GridView<T>,
-        pos: (usize, usize),
-        direction: Direction,
-    ) -> Option<((usize, usize), Direction)>
+    fn next_pose<T>(grid: &GridView<T>, pose: Pose) -> Option<Pose>
     where
         T: Deref,
         T::Target: Index<usize, Output = u8>,
     {
-        let (row, col) = pos;
-        let (drow, dcol) = direction.vector();
-        row.checked_add_signed(drow).and_then(|new_row| {
-            col.checked_add_signed(dcol).and_then(|new_col| {
-                if new_row < grid.height() && new_col < grid.width() {
-                    if grid[(new_row, new_col)] == b'#' {
-                        return Self::next_pos(grid, pos, direction.turn());
-                    }
-                    Some(((new_row, new_col), direction))
-                } else {
-                    None
-                }
-            })
-        })
+        let next = pose.step(grid.size())?;
+        if grid[next.pos] == b'#' {
+            return Self::next_pose(grid, pose.turn_right());
+        }
+        Some(next)
     }
 
-    fn check_is_loop(
-        &self,
-        grid: &GridView<Vec<u8>>,
-        starting_pos: (usize, usize),
-        direction: Direction,
-    ) -> bool {
-        let mut pos = starting_pos;
-        let mut direction = direction;
-        let mut distinct_positions = HashSet::with_capacity(grid.height() * grid.width());
-        distinct_positions.insert((pos, direction));
-        while let Some((new_pos, new_direction)) = Self::next_pos(grid, pos, direction) {
-            pos = new_pos;
-            direction = new_direction;
-            if !distinct_positions.insert((pos, direction)) {
+    fn check_is_loop(&self, grid: &GridView<Vec<u8>>, starting_pose: Pose) -> bool {
+        let mut pose = starting_pose;
+        let mut distinct_poses = HashSet::with_capacity(grid.height() * grid.width());
+        distinct_poses.insert(pose);
+        while let Some(new_pose) = Self::next_pose(grid, pose) {
+            pose = new_pose;
+            if !distinct_poses.insert(pose) {
                 return true;
             }
         }
         false
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day6-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "41");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day6-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "6");
-        Ok(())
-    }
-}
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 
 #[derive(Clone, Debug)]
 struct Equation {
@@ -51,6 +51,10 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Bridge Repair")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let equations = input
             .lines()
@@ -85,36 +89,16 @@ impl<'input> Solver<'input> for SolverImpl {
             .filter(|eq| eq.can_be_fulfilled())
             .map(|eq| eq.test_value)
             .sum();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let result: u64 = self
             .equations
             .iter()
             .filter(|eq| eq.can_be_fulfilled_with_concat())
             .map(|eq| eq.test_value)
             .sum();
-        Ok(Solution::with_description("Part 2", result.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day7-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "3749");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day7-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "11387");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
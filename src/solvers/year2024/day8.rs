@@ -1,17 +1,25 @@
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
+use crate::datastructures::point::P2;
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use std::collections::{HashMap, HashSet};
 
 type Frequency = u8;
-type Location = (isize, isize);
+type Location = P2<isize>;
 
 pub struct SolverImpl {
     antennas: HashMap<Frequency, Vec<Location>>,
-    width: isize,
-    height: isize,
+    size: Location,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Resonant Collinearity")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let grid = GridView::from_separated(b'\n', input.as_bytes());
         let mut antennas: HashMap<Frequency, Vec<Location>> = HashMap::new();
@@ -22,68 +30,52 @@ impl<'input> Solver<'input> for SolverImpl {
                     antennas
                         .entry(frequency)
                         .or_default()
-                        .push((row as isize, col as isize));
+                        .push(P2(row as isize, col as isize));
                 }
             }
         }
         Ok(Self {
             antennas,
-            width: grid.width() as isize,
-            height: grid.height() as isize,
+            size: P2(grid.height() as isize, grid.width() as isize),
         })
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         let antinodes: HashSet<Location> = self
             .antennas
-            .iter()
-            .flat_map(|(_, loc)| {
+            .values()
+            .flat_map(|loc| {
                 loc.iter().flat_map(|loc_a| {
                     loc.iter()
                         .filter(|&loc_b| loc_b != loc_a)
-                        .map(|loc_b| {
-                            let d_row = loc_a.0 - loc_b.0;
-                            let d_col = loc_a.1 - loc_b.1;
-                            (loc_a.0 + d_row, loc_a.1 + d_col)
-                        })
+                        .map(|&loc_b| *loc_a + (*loc_a - loc_b))
                         .collect::<Vec<_>>()
                 })
             })
             .collect();
         let result = antinodes
             .iter()
-            .filter(|antinode| {
-                0 <= antinode.0
-                    && antinode.0 < self.height
-                    && 0 <= antinode.1
-                    && antinode.1 < self.width
-            })
+            .filter(|&&antinode| self.contains(antinode))
             .count();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         // 1252 too high
         let antinodes: HashSet<Location> = self
             .antennas
-            .iter()
-            .flat_map(|(_, loc)| {
+            .values()
+            .flat_map(|loc| {
                 loc.iter().flat_map(|loc_a| {
                     loc.iter()
                         .filter(|&loc_b| loc_b != loc_a)
-                        .flat_map(|loc_b| {
-                            let d_row = loc_a.0 - loc_b.0;
-                            let d_col = loc_a.1 - loc_b.1;
+                        .flat_map(|&loc_b| {
+                            let delta = *loc_a - loc_b;
                             let mut antinodes = Vec::new();
                             let mut antinode_candidate = *loc_a;
-                            while 0 <= antinode_candidate.0
-                                && antinode_candidate.0 < self.height
-                                && 0 <= antinode_candidate.1
-                                && antinode_candidate.1 < self.width
-                            {
+                            while self.contains(antinode_candidate) {
                                 antinodes.push(antinode_candidate);
-                                antinode_candidate =
-                                    (antinode_candidate.0 + d_row, antinode_candidate.1 + d_col);
+                                antinode_candidate += delta;
                             }
                             antinodes
                         })
@@ -91,29 +83,12 @@ impl<'input> Solver<'input> for SolverImpl {
                 })
             })
             .collect();
-        Ok(Solution::with_description(
-            "Part 2",
-            antinodes.len().to_string(),
-        ))
+        Ok(Some(Solution::with_description("Part 2", antinodes.len())))
     }
 }
 
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day8-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "14");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day8-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "34");
-        Ok(())
+impl SolverImpl {
+    fn contains(&self, location: Location) -> bool {
+        0 <= location.0 && location.0 < self.size.0 && 0 <= location.1 && location.1 < self.size.1
     }
 }
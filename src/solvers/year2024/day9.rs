@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum MapEntry {
@@ -29,8 +29,12 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Disk Fragmenter")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
-        let disk_map = input.trim().as_bytes().iter().map(|c| c - b'0').collect();
+        let disk_map = input.as_bytes().iter().map(|c| c - b'0').collect();
         Ok(Self { disk_map })
     }
 
@@ -69,10 +73,10 @@ impl<'input> Solver<'input> for SolverImpl {
                 }
             }
         }
-        Ok(Solution::with_description("Part 1", checksum.to_string()))
+        Ok(Solution::with_description("Part 1", checksum))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let disk_map = self.disk_map.clone();
         let mut disk_moved = vec![false; disk_map.len()];
         let mut end_pointer = disk_map.len() - 1;
@@ -116,26 +120,6 @@ impl<'input> Solver<'input> for SolverImpl {
             }
         }
 
-        Ok(Solution::with_description("Part 2", checksum.to_string()))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day9-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "1928");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day9-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "2858");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", checksum)))
     }
 }
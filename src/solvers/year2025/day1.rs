@@ -1,11 +1,18 @@
-use crate::solvers::{Solution, Solver};
-use anyhow::anyhow;
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver, Tag};
 
 pub struct SolverImpl {
     instructions: Vec<i32>,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Secret Entrance")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         Ok(Self {
             instructions: input
@@ -35,10 +42,10 @@ impl<'input> Solver<'input> for SolverImpl {
                 }
                 (dial, zero_count)
             });
-        Ok(Solution::with_description("Password", password.to_string()))
+        Ok(Solution::with_description("Password", password))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let (dial, mut password) = self.instructions.iter().fold((50, 0), |acc, value| {
             let (mut dial, mut zero_count) = acc;
             if dial == 0 && *value < 0 {
@@ -61,10 +68,10 @@ impl<'input> Solver<'input> for SolverImpl {
         if dial == 0 {
             password += 1;
         }
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Password with method 0x434C49434B",
-            password.to_string(),
-        ))
+            password,
+        )))
     }
 }
 
@@ -73,28 +80,10 @@ fn parse_line(line: &str) -> anyhow::Result<i32> {
     let sign = match direction {
         "L" => -1,
         "R" => 1,
-        _ => Err(anyhow!("invalid direction: {}", direction))?,
+        _ => Err(InputError::new(direction, "a direction L or R"))?,
     };
-    let value: i32 = distance.parse()?;
+    let value: i32 = distance
+        .parse()
+        .map_err(|_| InputError::new(distance, "a distance"))?;
     Ok(sign * value)
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day1-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "3");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day1-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "6");
-        Ok(())
-    }
-}
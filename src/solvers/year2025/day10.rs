@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use anyhow::anyhow;
 use itertools::Itertools;
 use std::collections::{BTreeSet, HashMap};
@@ -16,6 +16,14 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Factory")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let machines = input
             .lines()
@@ -55,10 +63,10 @@ impl<'input> Solver<'input> for SolverImpl {
                     .expect("no solution for machine")
             })
             .sum();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let result: usize = self
             .machines
             .iter()
@@ -71,7 +79,7 @@ impl<'input> Solver<'input> for SolverImpl {
                     .expect("no solution for machine")
             })
             .sum();
-        Ok(Solution::with_description("Part 2", result.to_string()))
+        Ok(Some(Solution::with_description("Part 2", result)))
     }
 }
 
@@ -204,23 +212,3 @@ fn parse_button(input: &str) -> anyhow::Result<Indicators> {
         .iter()
         .fold(0, |acc, &light| acc | (1 << light)))
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day10-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "7");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day10-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "33");
-        Ok(())
-    }
-}
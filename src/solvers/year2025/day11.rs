@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use anyhow::anyhow;
 use std::collections::HashMap;
 
@@ -7,6 +7,14 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Reactor")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let outputs = input
             .lines()
@@ -25,13 +33,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         let n_paths = PathCounter::new(&self.outputs, "out").count_paths("you");
-        Ok(Solution::with_description(
-            "Paths to `out`",
-            n_paths.to_string(),
-        ))
+        Ok(Solution::with_description("Paths to `out`", n_paths))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut pc = PathCounter::new(&self.outputs, "out");
         let n_dac_to_out = pc.count_paths("dac");
         let n_fft_to_out = pc.count_paths("fft");
@@ -41,10 +46,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
         let n_svr_to_fft = PathCounter::new(&self.outputs, "fft").count_paths("svr");
         let n_paths =
             n_svr_to_dac * n_dac_to_fft * n_fft_to_out + n_svr_to_fft * n_fft_to_dac * n_dac_to_out;
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Paths with `fft` and `dac`",
-            n_paths.to_string(),
-        ))
+            n_paths,
+        )))
     }
 }
 
@@ -84,23 +89,3 @@ impl<'a> PathCounter<'a> {
         count
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day11-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "5");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day11-2.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "2");
-        Ok(())
-    }
-}
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 use anyhow::anyhow;
 
 struct Region {
@@ -6,11 +6,16 @@ struct Region {
     counts: Vec<u64>,
 }
 
+#[register_solver(year = 2025, day = 12)]
 pub struct SolverImpl {
     regions: Vec<Region>,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Christmas Tree Farm")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let regions: Vec<Region> = input
             .lines()
@@ -38,13 +43,10 @@ impl<'input> Solver<'input> for SolverImpl {
                 region.counts.iter().map(|&c| 9 * c).sum::<u64>() <= region.area.0 * region.area.1
             })
             .count();
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        Ok(Solution::with_description(
-            "Part 2",
-            "no part 2".to_string(),
-        ))
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        Ok(None)
     }
 }
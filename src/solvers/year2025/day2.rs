@@ -1,13 +1,20 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 
 pub struct SolverImpl {
     ranges: Vec<(u64, u64)>,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Gift Shop")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Math]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let ranges = input
-            .trim()
             .split(',')
             .map(|range_def| {
                 let range = range_def.split_once('-').expect("invalid range");
@@ -24,13 +31,10 @@ impl<'input> Solver<'input> for SolverImpl {
             .copied()
             .map(sum_invalid_ids_in_range)
             .sum();
-        Ok(Solution::with_description(
-            "Part 1",
-            invalid_id_sum.to_string(),
-        ))
+        Ok(Solution::with_description("Part 1", invalid_id_sum))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let invalid_id_sum: u64 = self
             .ranges
             .iter()
@@ -40,10 +44,7 @@ impl<'input> Solver<'input> for SolverImpl {
                     .sum::<u64>()
             })
             .sum();
-        Ok(Solution::with_description(
-            "Part 2",
-            invalid_id_sum.to_string(),
-        ))
+        Ok(Some(Solution::with_description("Part 2", invalid_id_sum)))
     }
 }
 
@@ -73,7 +74,7 @@ fn is_repeating(value: u64) -> bool {
     let base10_str = value.to_string();
     let base10_bytes = base10_str.as_bytes();
     for i in 1..base10_bytes.len() {
-        if base10_bytes.len() % i != 0 {
+        if !base10_bytes.len().is_multiple_of(i) {
             continue;
         }
         if (i..base10_bytes.len())
@@ -85,23 +86,3 @@ fn is_repeating(value: u64) -> bool {
     }
     false
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day2-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "1227775554");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day2-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "4174379265");
-        Ok(())
-    }
-}
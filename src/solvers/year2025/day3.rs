@@ -1,10 +1,14 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver};
 
 pub struct SolverImpl<'input> {
     banks: Vec<&'input [u8]>,
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Lobby")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         Ok(Self {
             banks: input.lines().map(str::trim).map(str::as_bytes).collect(),
@@ -13,23 +17,20 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         let output_voltage: u64 = self.banks.iter().copied().map(max_joltage).sum();
-        Ok(Solution::with_description(
-            "Output voltage",
-            output_voltage.to_string(),
-        ))
+        Ok(Solution::with_description("Output voltage", output_voltage))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let output_voltage: u64 = self
             .banks
             .iter()
             .copied()
             .map(max_joltage_with_override)
             .sum();
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Output voltage with override",
-            output_voltage.to_string(),
-        ))
+            output_voltage,
+        )))
     }
 }
 
@@ -63,23 +64,3 @@ fn next_max(bank: &[u8]) -> (usize, u8) {
         },
     )
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day3-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "357");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day3-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "3121910778619");
-        Ok(())
-    }
-}
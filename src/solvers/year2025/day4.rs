@@ -1,12 +1,20 @@
 use crate::datastructures::grid::GridView;
 use crate::datastructures::iterators::SurroundIterator2d;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 
 pub struct SolverImpl<'input> {
     grid: GridView<&'input [u8]>,
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Printing Department")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let grid = GridView::from_separated(b'\n', input.as_bytes());
         Ok(Self { grid })
@@ -15,11 +23,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
         let n_accessible = self
             .grid
-            .iter()
-            .enumerate()
-            .filter(|&(i, value)| {
+            .indexed_iter()
+            .filter(|&(pos, value)| {
                 value == b'@'
-                    && SurroundIterator2d::new(self.grid.nth_index(i), self.grid.size())
+                    && SurroundIterator2d::new(pos, self.grid.size())
                         .filter(|&neighbor_idx| self.grid[neighbor_idx] == b'@')
                         .count()
                         < 4
@@ -27,27 +34,26 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             .count();
         Ok(Solution::with_description(
             "Number of accessible paper rolls",
-            n_accessible.to_string(),
+            n_accessible,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let mut grid = self.grid.to_owned();
         let mut total_removed = 0;
         let mut has_removed = true;
         while has_removed {
             has_removed = false;
             let to_remove: Vec<_> = grid
-                .iter()
-                .enumerate()
-                .filter(|&(i, value)| {
+                .indexed_iter()
+                .filter(|&(pos, value)| {
                     value == b'@'
-                        && SurroundIterator2d::new(grid.nth_index(i), grid.size())
+                        && SurroundIterator2d::new(pos, grid.size())
                             .filter(|&neighbor_idx| grid[neighbor_idx] == b'@')
                             .count()
                             < 4
                 })
-                .map(|(i, _)| grid.nth_index(i))
+                .map(|(pos, _)| pos)
                 .collect();
             total_removed += to_remove.len();
             for idx in to_remove {
@@ -55,29 +61,6 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
                 has_removed = true;
             }
         }
-        Ok(Solution::with_description(
-            "Part 2",
-            total_removed.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day4-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "13");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day4-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "43");
-        Ok(())
+        Ok(Some(Solution::with_description("Part 2", total_removed)))
     }
 }
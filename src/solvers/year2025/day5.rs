@@ -1,28 +1,27 @@
-use crate::solvers::{Solution, Solver};
-use std::collections::HashSet;
+use crate::datastructures::ranges::RangeSet;
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver};
+use std::ops::Range;
 
 pub struct SolverImpl {
-    ranges: Vec<(u64, u64)>,
+    fresh_ids: RangeSet,
     ingredient_ids: Vec<u64>,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Cafeteria")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let mut lines = input.lines();
-        let ranges: Vec<_> = lines
+        let fresh_ids = lines
             .by_ref()
             .take_while(|line| !line.is_empty())
-            .map(|line| {
-                if let Some((lower_bound, upper_bound)) = line.split_once('-') {
-                    Ok((lower_bound.parse::<u64>()?, upper_bound.parse::<u64>()?))
-                } else {
-                    Err(anyhow::anyhow!("invalid range"))
-                }
-            })
-            .collect::<anyhow::Result<_>>()?;
+            .map(parse_range)
+            .collect::<Result<_, _>>()?;
         let ingredient_ids: Vec<_> = lines.map(|line| line.parse()).collect::<Result<_, _>>()?;
         Ok(Self {
-            ranges,
+            fresh_ids,
             ingredient_ids,
         })
     }
@@ -31,68 +30,63 @@ impl<'input> Solver<'input> for SolverImpl {
         let num_fresh = self
             .ingredient_ids
             .iter()
-            .filter(|&&ingredient_id| self.is_fresh(ingredient_id))
+            .filter(|&&ingredient_id| self.fresh_ids.contains(ingredient_id))
             .count();
         Ok(Solution::with_description(
             "Fresh ingredients count",
-            num_fresh.to_string(),
+            num_fresh,
         ))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        let mut merged_ranges: HashSet<(u64, u64)> = HashSet::with_capacity(self.ranges.len());
-        let mut queue: Vec<_> = self.ranges.iter().rev().copied().collect();
-        while let Some(range) = queue.pop() {
-            if let Some(ovelapping_range) = merged_ranges.iter().copied().find(|merge_candidate| {
-                (merge_candidate.0 <= range.0 && range.0 <= merge_candidate.1)
-                    || (merge_candidate.0 <= range.1 && range.1 <= merge_candidate.1)
-                    || (range.0 <= merge_candidate.0 && merge_candidate.0 <= range.1)
-                    || (range.0 <= merge_candidate.1 && merge_candidate.1 <= range.1)
-            }) {
-                let merged_range = (
-                    ovelapping_range.0.min(range.0),
-                    ovelapping_range.1.max(range.1),
-                );
-                merged_ranges.remove(&ovelapping_range);
-                queue.push(merged_range);
-            } else {
-                merged_ranges.insert(range);
-            }
-        }
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        let num_fresh = self.fresh_ids.total_len();
 
-        let num_fresh: u64 = merged_ranges.iter().map(|(lb, ub)| ub - lb + 1).sum();
-
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Fresh according to ranges",
-            num_fresh.to_string(),
-        ))
+            num_fresh,
+        )))
     }
 }
 
-impl SolverImpl {
-    fn is_fresh(&self, ingredient_id: u64) -> bool {
-        self.ranges.iter().any(|&(lower_bound, upper_bound)| {
-            ingredient_id >= lower_bound && ingredient_id <= upper_bound
-        })
-    }
+/// Parses an inclusive range like `3-5` into the half-open range `3..6`.
+fn parse_range(line: &str) -> Result<Range<u64>, InputError> {
+    let (lower_bound, upper_bound) = line
+        .split_once('-')
+        .ok_or_else(|| InputError::new(line, "a range like 3-5"))?;
+    let lower_bound = lower_bound
+        .parse::<u64>()
+        .map_err(|_| InputError::new(lower_bound, "a lower bound"))?;
+    let end = upper_bound
+        .parse::<u64>()
+        .ok()
+        .filter(|&upper_bound| upper_bound >= lower_bound)
+        .and_then(|upper_bound| upper_bound.checked_add(1))
+        .ok_or_else(|| {
+            InputError::new(
+                upper_bound,
+                "an upper bound between the lower bound and u64::MAX - 1",
+            )
+        })?;
+    Ok(lower_bound..end)
 }
 
 #[cfg(test)]
 mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
+    use super::parse_range;
+    use rstest::rstest;
 
     #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day5-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "3");
-        Ok(())
+    fn test_parse_range() {
+        assert_eq!(parse_range("3-5").unwrap(), 3..6);
+        assert_eq!(parse_range("7-7").unwrap(), 7..8);
     }
 
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day5-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "14");
-        Ok(())
+    #[rstest]
+    #[case("3")]
+    #[case("x-5")]
+    #[case("5-3")]
+    #[case("0-18446744073709551615")]
+    fn test_parse_invalid_range(#[case] line: &str) {
+        assert!(parse_range(line).is_err());
     }
 }
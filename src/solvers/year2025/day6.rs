@@ -1,5 +1,5 @@
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use anyhow::anyhow;
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -37,6 +37,14 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Trash Compactor")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         Ok(Self { input })
     }
@@ -77,13 +85,10 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
                 }
             })
             .sum();
-        Ok(Solution::with_description(
-            "Grand total",
-            result.to_string(),
-        ))
+        Ok(Solution::with_description("Grand total", result))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let grid = GridView::from_separated(b'\n', self.input.as_bytes());
         let mut operand_stack = Vec::with_capacity(4);
         let mut result: u64 = 0;
@@ -104,29 +109,9 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
                 operand_stack.clear();
             }
         }
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Grand total, part 2",
-            result.to_string(),
-        ))
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day6-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "4277556");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day6-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "3263827");
-        Ok(())
+            result,
+        )))
     }
 }
@@ -1,5 +1,5 @@
 use crate::datastructures::grid::GridView;
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, Tag};
 use anyhow::anyhow;
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -9,6 +9,14 @@ pub struct SolverImpl<'input> {
 }
 
 impl<'input> Solver<'input> for SolverImpl<'input> {
+    fn title() -> Option<&'static str> {
+        Some("Laboratories")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Grid]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let grid = GridView::from_separated(b'\n', input.as_bytes());
         let start_col = grid
@@ -42,19 +50,13 @@ impl<'input> Solver<'input> for SolverImpl<'input> {
             }
             beam_cols = next_beam_cols;
         }
-        Ok(Solution::with_description(
-            "Beam splits",
-            n_splits.to_string(),
-        ))
+        Ok(Solution::with_description("Beam splits", n_splits))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let n_timelines =
             QuantumTachyonManifold::new(&self.grid).count_timelines((0, self.start_col));
-        Ok(Solution::with_description(
-            "Timelines",
-            n_timelines.to_string(),
-        ))
+        Ok(Some(Solution::with_description("Timelines", n_timelines)))
     }
 }
 
@@ -93,23 +95,3 @@ impl<'input> QuantumTachyonManifold<'input> {
         n_timelines
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day7-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "21");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day7-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "40");
-        Ok(())
-    }
-}
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{MaybeSolution, Solution, Solver, SolverParams, Tag};
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::num::NonZeroUsize;
@@ -7,9 +7,19 @@ type Pos = (i64, i64, i64);
 
 pub struct SolverImpl {
     junction_boxes: Vec<Pos>,
+    /// Number of connections to make for part 1.
+    connections: usize,
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Playground")
+    }
+
+    fn tags() -> &'static [Tag] {
+        &[Tag::Graph]
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let junction_boxes = input
             .lines()
@@ -24,15 +34,23 @@ impl<'input> Solver<'input> for SolverImpl {
                 Ok((coordinates[0], coordinates[1], coordinates[2]))
             })
             .collect::<Result<_, _>>()?;
-        Ok(Self { junction_boxes })
+        Ok(Self {
+            junction_boxes,
+            connections: 1000,
+        })
+    }
+
+    fn set_params(&mut self, params: &SolverParams) -> anyhow::Result<()> {
+        self.connections = params.get("connections", self.connections)?;
+        Ok(())
     }
 
     fn solve_part_1(&self) -> anyhow::Result<Solution> {
-        self.make_connections(1000)
+        self.make_connections(self.connections)
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
-        self.make_single_circuit()
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
+        self.make_single_circuit().map(Some)
     }
 }
 
@@ -88,7 +106,7 @@ impl SolverImpl {
         circuit_sizes.sort();
         let result: usize = circuit_sizes.iter().rev().take(3).product();
 
-        Ok(Solution::with_description("Part 1", result.to_string()))
+        Ok(Solution::with_description("Part 1", result))
     }
 
     fn make_single_circuit(&self) -> anyhow::Result<Solution> {
@@ -138,7 +156,7 @@ impl SolverImpl {
                 let pos_a = self.junction_boxes[idx_a];
                 let pos_b = self.junction_boxes[idx_b];
                 let result = pos_a.0 * pos_b.0;
-                return Ok(Solution::with_description("Part 2", result.to_string()));
+                return Ok(Solution::with_description("Part 2", result));
             }
         }
 
@@ -149,23 +167,3 @@ impl SolverImpl {
 fn dist_sq(a: &Pos, b: &Pos) -> i64 {
     (a.0 - b.0).pow(2) + (a.1 - b.1).pow(2) + (a.2 - b.2).pow(2)
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day8-1.example"))?;
-        assert_eq!(solver.make_connections(10)?.solution, "40");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day8-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "25272");
-        Ok(())
-    }
-}
@@ -1,4 +1,4 @@
-use crate::solvers::{Solution, Solver};
+use crate::solvers::{InputError, MaybeSolution, Solution, Solver};
 use anyhow::anyhow;
 
 type Pos = (u64, u64);
@@ -8,12 +8,23 @@ pub struct SolverImpl {
 }
 
 impl<'input> Solver<'input> for SolverImpl {
+    fn title() -> Option<&'static str> {
+        Some("Movie Theater")
+    }
+
     fn new(input: &'input str) -> anyhow::Result<Self> {
         let red_tiles = input
             .lines()
             .map(|line| {
-                let (x, y) = line.split_once(',').ok_or(anyhow!("invalid input"))?;
-                Ok((x.parse::<u64>()?, y.parse::<u64>()?))
+                let (x, y) = line
+                    .split_once(',')
+                    .ok_or_else(|| InputError::new(line, "a position like 7,1"))?;
+                let coordinate = |value: &str| {
+                    value
+                        .parse::<u64>()
+                        .map_err(|_| InputError::new(value, "a coordinate"))
+                };
+                Ok((coordinate(x)?, coordinate(y)?))
             })
             .collect::<anyhow::Result<Vec<_>>>()?;
 
@@ -32,13 +43,10 @@ impl<'input> Solver<'input> for SolverImpl {
             })
             .max()
             .ok_or(anyhow!("no solution found"))?;
-        Ok(Solution::with_description(
-            "Largest area",
-            largest_area.to_string(),
-        ))
+        Ok(Solution::with_description("Largest area", largest_area))
     }
 
-    fn solve_part_2(&self) -> anyhow::Result<Solution> {
+    fn solve_part_2(&self) -> anyhow::Result<MaybeSolution> {
         let largest_area = self
             .red_tiles
             .iter()
@@ -63,10 +71,10 @@ impl<'input> Solver<'input> for SolverImpl {
             })
             .max()
             .ok_or(anyhow!("no solution found"))?;
-        Ok(Solution::with_description(
+        Ok(Some(Solution::with_description(
             "Largest area with only red and green tiles",
-            largest_area.to_string(),
-        ))
+            largest_area,
+        )))
     }
 }
 
@@ -85,23 +93,3 @@ fn intersects(rect: (Pos, Pos), line: (Pos, Pos)) -> bool {
         panic!("invalid tile shape");
     }
 }
-
-#[cfg(test)]
-mod test {
-    use super::SolverImpl;
-    use crate::solvers::Solver;
-
-    #[test]
-    fn test_example_part_1() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day9-1.example"))?;
-        assert_eq!(solver.solve_part_1()?.solution, "50");
-        Ok(())
-    }
-
-    #[test]
-    fn test_example_part_2() -> anyhow::Result<()> {
-        let solver = SolverImpl::new(include_str!("./day9-1.example"))?;
-        assert_eq!(solver.solve_part_2()?.solution, "24");
-        Ok(())
-    }
-}
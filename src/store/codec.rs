@@ -0,0 +1,72 @@
+//! Encoding of cache entries.
+
+use std::io::{Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder};
+use sha2::{Digest, Sha256};
+
+use super::StoredEntry;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+}
+
+pub const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Prefix of the line with the checksum that preceded the content of entries
+/// written by earlier versions.
+const LEGACY_CHECKSUM_PREFIX: &str = "sha256:";
+
+/// Compresses an entry. Returns the encoded entry and the checksum of the
+/// content to store alongside it.
+pub fn encode(content: &[u8], compression: Compression) -> anyhow::Result<(Vec<u8>, String)> {
+    let checksum = hash(content);
+    let encoded = match compression {
+        Compression::None => content.to_vec(),
+        Compression::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::best());
+            encoder.write_all(content)?;
+            encoder.finish()?
+        }
+    };
+    Ok((encoded, checksum))
+}
+
+/// Decodes an entry, decompressing it if it starts with the gzip magic bytes
+/// and verifying its checksum if it has one. Entries written by earlier
+/// versions have no checksum or have it on a line preceding the content.
+pub fn decode(entry: StoredEntry) -> anyhow::Result<Vec<u8>> {
+    let decoded = if entry.content.starts_with(&GZIP_MAGIC) {
+        let mut decoded = Vec::new();
+        GzDecoder::new(entry.content.as_slice()).read_to_end(&mut decoded)?;
+        decoded
+    } else {
+        entry.content
+    };
+    let (checksum, content) = match entry.checksum {
+        Some(checksum) => (checksum, decoded),
+        None => match decoded.strip_prefix(LEGACY_CHECKSUM_PREFIX.as_bytes()) {
+            Some(checksummed) => {
+                let newline = checksummed
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .ok_or_else(|| anyhow::anyhow!("truncated checksum"))?;
+                let checksum = std::str::from_utf8(&checksummed[..newline])?;
+                (checksum.into(), checksummed[newline + 1..].to_vec())
+            }
+            None => return Ok(decoded),
+        },
+    };
+    if hash(&content) != checksum {
+        return Err(anyhow::anyhow!("checksum mismatch"));
+    }
+    Ok(content)
+}
+
+/// Hex encoded SHA-256 hash of the content.
+fn hash(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
@@ -0,0 +1,313 @@
+//! Store keeping each entry in a file.
+
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use lazy_static::lazy_static;
+use regex::Regex;
+use tracing::info;
+
+use super::{EntryInfo, Namespace, Store, StoredEntry};
+
+/// Stores each entry as a file in a directory per namespace below the root.
+/// The checksum of an entry is kept in a hidden file `.<key>.sha256` next to
+/// it.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    root: PathBuf,
+}
+
+impl FileStore {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn path(&self, namespace: Namespace) -> PathBuf {
+        self.root.join(namespace.name())
+    }
+
+    fn entry_path(&self, namespace: Namespace, key: &str) -> PathBuf {
+        self.path(namespace).join(key)
+    }
+
+    fn checksum_path(&self, namespace: Namespace, key: &str) -> PathBuf {
+        self.path(namespace).join(format!(".{key}.sha256"))
+    }
+
+    /// Moves inputs and the answer store from the flat layout of earlier
+    /// versions, where they were stored directly in the root, into their
+    /// namespaces.
+    pub fn migrate_flat_layout(&self) -> anyhow::Result<()> {
+        lazy_static! {
+            static ref INPUT_FILE: Regex = Regex::new(r"^\d{4}-\d{2}$").unwrap();
+        }
+
+        let Ok(entries) = std::fs::read_dir(&self.root) else {
+            return Ok(());
+        };
+        for entry in entries {
+            let entry = entry?;
+            let file_name = entry.file_name();
+            let namespace = match file_name.to_str() {
+                Some(name) if INPUT_FILE.is_match(name) => Namespace::Inputs,
+                Some("answers.json") => Namespace::Answers,
+                _ => continue,
+            };
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let directory = self.path(namespace);
+            let target = directory.join(&file_name);
+            if target.exists() {
+                continue;
+            }
+            info!(from = %entry.path().display(), to = %target.display(), "migrating cache entry");
+            std::fs::create_dir_all(&directory)
+                .with_context(|| format!("creating cache directory {}", directory.display()))?;
+            std::fs::rename(entry.path(), &target)
+                .with_context(|| format!("moving {}", entry.path().display()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Store for FileStore {
+    fn get(&self, namespace: Namespace, key: &str) -> anyhow::Result<Option<StoredEntry>> {
+        let path = self.entry_path(namespace, key);
+        let content = match std::fs::read(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err).context(format!("read from {}", path.display())),
+        };
+        let modified = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .with_context(|| format!("modification time of {}", path.display()))?;
+        let checksum_path = self.checksum_path(namespace, key);
+        let checksum = match std::fs::read_to_string(&checksum_path) {
+            Ok(checksum) => Some(checksum.trim_end().into()),
+            Err(err) if err.kind() == ErrorKind::NotFound => None,
+            Err(err) => {
+                return Err(err).context(format!("read from {}", checksum_path.display()));
+            }
+        };
+        Ok(Some(StoredEntry {
+            content,
+            checksum,
+            modified,
+        }))
+    }
+
+    fn put_with_checksum(
+        &self,
+        namespace: Namespace,
+        key: &str,
+        content: &[u8],
+        checksum: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let directory = self.path(namespace);
+        if !directory.exists() {
+            info!(directory = %directory.display(), "creating cache directory");
+            std::fs::create_dir_all(&directory)
+                .with_context(|| format!("creating cache directory {}", directory.display()))?;
+        }
+        // An entry without a checksum is valid, whereas one with the checksum
+        // of other content counts as corrupt. Removing the old checksum first
+        // and writing the new one last keeps the entry valid if a write fails
+        // or the process crashes in between, which matters for entries that
+        // cannot be refetched, like the answer store.
+        let checksum_path = self.checksum_path(namespace, key);
+        remove_file_if_exists(&checksum_path)?;
+        write_atomically(&directory.join(key), content)?;
+        match checksum {
+            Some(checksum) => write_atomically(&checksum_path, checksum.as_bytes()),
+            None => Ok(()),
+        }
+    }
+
+    fn remove(&self, namespace: Namespace, key: &str) -> anyhow::Result<()> {
+        remove_file_if_exists(&self.entry_path(namespace, key))?;
+        remove_file_if_exists(&self.checksum_path(namespace, key))
+    }
+
+    fn list(&self, namespace: Namespace) -> anyhow::Result<Vec<EntryInfo>> {
+        let directory = self.path(namespace);
+        let entries = match std::fs::read_dir(&directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err).context(format!("listing {}", directory.display()));
+            }
+        };
+        let mut infos = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            let Ok(key) = entry.file_name().into_string() else {
+                continue;
+            };
+            // Skip temporary files of writes in progress.
+            if key.starts_with('.') {
+                continue;
+            }
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            infos.push(EntryInfo {
+                key,
+                size: metadata.len(),
+                modified: metadata.modified()?,
+            });
+        }
+        infos.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(infos)
+    }
+
+    fn contains(&self, namespace: Namespace, key: &str) -> anyhow::Result<bool> {
+        Ok(self.entry_path(namespace, key).exists())
+    }
+}
+
+fn remove_file_if_exists(path: &Path) -> anyhow::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != ErrorKind::NotFound => {
+            Err(err).context(format!("removing file {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Path of a temporary file in the same directory as `path`, which can be
+/// atomically renamed to `path`.
+fn temporary_path(path: &Path) -> PathBuf {
+    let mut file_name = std::ffi::OsString::from(".");
+    file_name.push(path.file_name().unwrap_or_default());
+    file_name.push(format!(".{}.tmp", std::process::id()));
+    path.with_file_name(file_name)
+}
+
+/// Replaces the file at `path` with `content` without ever exposing a
+/// partially written file.
+fn write_atomically(path: &Path, content: &[u8]) -> anyhow::Result<()> {
+    let temporary = temporary_path(path);
+    let result = (|| {
+        let mut file = std::fs::File::create(&temporary)?;
+        std::io::Write::write_all(&mut file, content)?;
+        file.sync_all()?;
+        std::fs::rename(&temporary, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temporary);
+    }
+    result.with_context(|| format!("writing {}", path.display()))
+}
+
+#[cfg(test)]
+pub mod test {
+    use super::FileStore;
+    use crate::store::{Namespace, Store};
+
+    /// Checks the behavior shared by all stores.
+    pub fn check_store(store: &dyn Store) {
+        assert_eq!(store.get(Namespace::Inputs, "2023-01").unwrap(), None);
+        assert!(!store.contains(Namespace::Inputs, "2023-01").unwrap());
+
+        store.put(Namespace::Inputs, "2023-01", b"first").unwrap();
+        store.put(Namespace::Inputs, "2023-01", b"second").unwrap();
+        store.put(Namespace::Pages, "2023-01", b"page").unwrap();
+        let entry = store.get(Namespace::Inputs, "2023-01").unwrap().unwrap();
+        assert_eq!(entry.content, b"second");
+        assert_eq!(entry.checksum, None);
+        assert!(entry.modified.elapsed().unwrap().as_secs() < 60);
+        assert!(store.contains(Namespace::Inputs, "2023-01").unwrap());
+        store.put(Namespace::Inputs, "2023-02", b"").unwrap();
+        let keys: Vec<_> = store
+            .list(Namespace::Inputs)
+            .unwrap()
+            .into_iter()
+            .map(|info| (info.key, info.size))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![("2023-01".to_string(), 6), ("2023-02".to_string(), 0)]
+        );
+        store.remove(Namespace::Inputs, "2023-02").unwrap();
+
+        store
+            .put_with_checksum(Namespace::Inputs, "2023-01", b"third", Some("abc"))
+            .unwrap();
+        let entry = store.get(Namespace::Inputs, "2023-01").unwrap().unwrap();
+        assert_eq!(entry.checksum.as_deref(), Some("abc"));
+        assert_eq!(store.list(Namespace::Inputs).unwrap().len(), 1);
+        store.put(Namespace::Inputs, "2023-01", b"fourth").unwrap();
+        let entry = store.get(Namespace::Inputs, "2023-01").unwrap().unwrap();
+        assert_eq!(entry.checksum, None);
+        store
+            .put_with_checksum(Namespace::Inputs, "2023-01", b"fifth", Some("def"))
+            .unwrap();
+
+        store.remove(Namespace::Inputs, "2023-01").unwrap();
+        store.remove(Namespace::Inputs, "2023-01").unwrap();
+        assert_eq!(store.get(Namespace::Inputs, "2023-01").unwrap(), None);
+        assert!(store.contains(Namespace::Pages, "2023-01").unwrap());
+        assert!(store.list(Namespace::Answers).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_file_store() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = FileStore::new(directory.path());
+        check_store(&store);
+        // No temporary files are left behind.
+        assert_eq!(
+            std::fs::read_dir(store.path(Namespace::Inputs))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_failed_write_leaves_no_stale_checksum() {
+        let directory = tempfile::tempdir().unwrap();
+        let store = FileStore::new(directory.path());
+        store
+            .put_with_checksum(Namespace::Answers, "answers.json", b"old", Some("abc"))
+            .unwrap();
+        // Occupy the temporary file of the content so that writing it fails.
+        let entry_path = store.entry_path(Namespace::Answers, "answers.json");
+        std::fs::create_dir(super::temporary_path(&entry_path)).unwrap();
+
+        assert!(store
+            .put_with_checksum(Namespace::Answers, "answers.json", b"new", Some("def"))
+            .is_err());
+        let entry = store
+            .get(Namespace::Answers, "answers.json")
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.content, b"old");
+        assert_eq!(entry.checksum, None);
+    }
+
+    #[test]
+    fn test_migrate_flat_layout() {
+        let directory = tempfile::tempdir().unwrap();
+        let root = directory.path();
+        std::fs::write(root.join("2023-01"), "input").unwrap();
+        std::fs::write(root.join("answers.json"), "{}").unwrap();
+        std::fs::create_dir(root.join("pages")).unwrap();
+        std::fs::write(root.join("pages").join("2023-01"), "page").unwrap();
+
+        let store = FileStore::new(root);
+        store.migrate_flat_layout().unwrap();
+
+        let input = store.get(Namespace::Inputs, "2023-01").unwrap().unwrap();
+        assert_eq!(input.content, b"input");
+        assert!(store.contains(Namespace::Answers, "answers.json").unwrap());
+        assert!(store.contains(Namespace::Pages, "2023-01").unwrap());
+        assert!(!root.join("2023-01").exists());
+        assert!(!root.join("answers.json").exists());
+    }
+}
@@ -0,0 +1,168 @@
+//! Storage of the entries cached by the `aoc` command line tool.
+//!
+//! Entries are grouped by [`Namespace`] and kept in a [`Store`], either as
+//! files or, with the `sqlite` feature, in a single SQLite database. The
+//! benchmarks read the cached inputs through this module, too.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use clap::ValueEnum;
+use tracing::warn;
+
+mod codec;
+mod file;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+
+pub use codec::{decode, encode, Compression, GZIP_MAGIC};
+pub use file::FileStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStore;
+
+/// Environment variable overriding the cache directory.
+pub const CACHE_DIR_ENV_VAR: &str = "AOC_CACHE_DIR";
+
+/// Environment variable selecting the [`Backend`].
+pub const CACHE_BACKEND_ENV_VAR: &str = "AOC_CACHE_BACKEND";
+
+/// Environment variable selecting the profile, which has its own cache.
+pub const PROFILE_ENV_VAR: &str = "AOC_PROFILE";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Backend {
+    /// One file per entry, in a directory per namespace.
+    #[default]
+    File,
+    /// A single SQLite database.
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+}
+
+impl Backend {
+    /// Opens the store of the backend in the cache directory `root`.
+    pub fn open(self, root: &Path) -> anyhow::Result<Arc<dyn Store>> {
+        Ok(match self {
+            Self::File => {
+                let store = FileStore::new(root);
+                store.migrate_flat_layout()?;
+                Arc::new(store)
+            }
+            #[cfg(feature = "sqlite")]
+            Self::Sqlite => Arc::new(SqliteStore::open(root.join("cache.sqlite3"))?),
+        })
+    }
+}
+
+/// Kinds of cached data, stored separately from each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Namespace {
+    Inputs,
+    Pages,
+    Answers,
+    Leaderboards,
+}
+
+impl Namespace {
+    pub const ALL: [Self; 4] = [Self::Inputs, Self::Pages, Self::Answers, Self::Leaderboards];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|namespace| namespace.name() == name)
+    }
+
+    /// Whether entries can be fetched again after deleting them. Verified
+    /// answers cannot.
+    pub fn is_refetchable(self) -> bool {
+        self != Self::Answers
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Inputs => "inputs",
+            Self::Pages => "pages",
+            Self::Answers => "answers",
+            Self::Leaderboards => "leaderboards",
+        }
+    }
+}
+
+/// Directory of the cache of the profile, below `cache_dir_override` or, by
+/// default, `aoc` in the user's cache directory.
+pub fn root(cache_dir_override: Option<PathBuf>, profile: Option<&str>) -> PathBuf {
+    let root = cache_dir_override.unwrap_or_else(|| {
+        dirs::cache_dir().map_or_else(
+            || {
+                warn!("couldn't locate cache directory, using ./aoc-cache");
+                "./aoc-cache".into()
+            },
+            |cache_base| cache_base.join("aoc"),
+        )
+    });
+    match profile {
+        Some(profile) => root.join("profiles").join(profile),
+        None => root,
+    }
+}
+
+/// Key of the input of a day in [`Namespace::Inputs`].
+pub fn input_key(year: i32, day: u32) -> String {
+    format!("{year:04}-{day:02}")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredEntry {
+    pub content: Vec<u8>,
+    /// Checksum of the decoded content, stored next to the entry.
+    pub checksum: Option<String>,
+    /// Time the entry was last written.
+    pub modified: SystemTime,
+}
+
+/// Metadata of a stored entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryInfo {
+    pub key: String,
+    /// Size of the stored, possibly compressed, content in bytes.
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// Storage of raw entries by namespace and key.
+pub trait Store: Send + Sync {
+    fn get(&self, namespace: Namespace, key: &str) -> anyhow::Result<Option<StoredEntry>>;
+
+    /// Replaces the entry and its checksum. Readers never observe a partially
+    /// written entry.
+    fn put_with_checksum(
+        &self,
+        namespace: Namespace,
+        key: &str,
+        content: &[u8],
+        checksum: Option<&str>,
+    ) -> anyhow::Result<()>;
+
+    /// Replaces the entry with one without checksum.
+    fn put(&self, namespace: Namespace, key: &str, content: &[u8]) -> anyhow::Result<()> {
+        self.put_with_checksum(namespace, key, content, None)
+    }
+
+    /// Removes the entry if it exists.
+    fn remove(&self, namespace: Namespace, key: &str) -> anyhow::Result<()>;
+
+    /// Metadata of all entries in the namespace, ordered by key.
+    fn list(&self, namespace: Namespace) -> anyhow::Result<Vec<EntryInfo>>;
+
+    fn contains(&self, namespace: Namespace, key: &str) -> anyhow::Result<bool> {
+        Ok(self.get(namespace, key)?.is_some())
+    }
+}
+
+/// Reads and decodes the entry, verifying its checksum.
+pub fn read(store: &dyn Store, namespace: Namespace, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+    store.get(namespace, key)?.map(decode).transpose()
+}
@@ -0,0 +1,154 @@
+//! Store keeping all cache entries in a single SQLite database.
+
+use std::{
+    path::Path,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use super::{EntryInfo, Namespace, Store, StoredEntry};
+
+pub struct SqliteStore {
+    connection: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    pub fn open<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating cache directory {}", parent.display()))?;
+        }
+        let connection = Connection::open(path)
+            .with_context(|| format!("opening cache database {}", path.display()))?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS entries (
+                namespace TEXT NOT NULL,
+                key TEXT NOT NULL,
+                content BLOB NOT NULL,
+                checksum TEXT,
+                modified_ms INTEGER NOT NULL,
+                PRIMARY KEY (namespace, key)
+            )",
+        )?;
+        // Databases of earlier versions have no checksum column.
+        let has_checksum = connection
+            .prepare("SELECT name FROM pragma_table_info('entries') WHERE name = 'checksum'")?
+            .exists([])?;
+        if !has_checksum {
+            connection.execute_batch("ALTER TABLE entries ADD COLUMN checksum TEXT")?;
+        }
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+}
+
+impl Store for SqliteStore {
+    fn get(&self, namespace: Namespace, key: &str) -> anyhow::Result<Option<StoredEntry>> {
+        let connection = self.connection.lock().unwrap();
+        let entry = connection
+            .query_row(
+                "SELECT content, checksum, modified_ms FROM entries
+                WHERE namespace = ?1 AND key = ?2",
+                params![namespace.name(), key],
+                |row| {
+                    let modified_ms: u64 = row.get(2)?;
+                    Ok(StoredEntry {
+                        content: row.get(0)?,
+                        checksum: row.get(1)?,
+                        modified: UNIX_EPOCH + Duration::from_millis(modified_ms),
+                    })
+                },
+            )
+            .optional()?;
+        Ok(entry)
+    }
+
+    fn put_with_checksum(
+        &self,
+        namespace: Namespace,
+        key: &str,
+        content: &[u8],
+        checksum: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let modified_ms = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+        self.connection.lock().unwrap().execute(
+            "INSERT OR REPLACE INTO entries (namespace, key, content, checksum, modified_ms)
+            VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![namespace.name(), key, content, checksum, modified_ms],
+        )?;
+        Ok(())
+    }
+
+    fn remove(&self, namespace: Namespace, key: &str) -> anyhow::Result<()> {
+        self.connection.lock().unwrap().execute(
+            "DELETE FROM entries WHERE namespace = ?1 AND key = ?2",
+            params![namespace.name(), key],
+        )?;
+        Ok(())
+    }
+
+    fn list(&self, namespace: Namespace) -> anyhow::Result<Vec<EntryInfo>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare(
+            "SELECT key, length(content), modified_ms FROM entries
+            WHERE namespace = ?1 ORDER BY key",
+        )?;
+        let infos = statement
+            .query_map(params![namespace.name()], |row| {
+                let modified_ms: u64 = row.get(2)?;
+                Ok(EntryInfo {
+                    key: row.get(0)?,
+                    size: row.get(1)?,
+                    modified: UNIX_EPOCH + Duration::from_millis(modified_ms),
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(infos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SqliteStore;
+    use crate::store::{file::test::check_store, Namespace, Store};
+    use rusqlite::Connection;
+
+    #[test]
+    fn test_sqlite_store() {
+        let directory = tempfile::tempdir().unwrap();
+        check_store(&SqliteStore::open(directory.path().join("cache.sqlite3")).unwrap());
+    }
+
+    #[test]
+    fn test_adds_checksum_column() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("cache.sqlite3");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE entries (
+                    namespace TEXT NOT NULL,
+                    key TEXT NOT NULL,
+                    content BLOB NOT NULL,
+                    modified_ms INTEGER NOT NULL,
+                    PRIMARY KEY (namespace, key)
+                );
+                INSERT INTO entries VALUES ('leaderboards', '2022-1.json', X'31', 0);",
+            )
+            .unwrap();
+
+        let store = SqliteStore::open(&path).unwrap();
+        let entry = store
+            .get(Namespace::Leaderboards, "2022-1.json")
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.content, b"1");
+        assert_eq!(entry.checksum, None);
+        check_store(&store);
+    }
+}
@@ -0,0 +1,254 @@
+//! Visualizations of solver state, e.g. for write-ups.
+//!
+//! Frames are rendered to deterministic text or plain PPM images so that they
+//! can be compared against committed golden files. For `solve --visualize`,
+//! they are drawn to the terminal or written as PNG and GIF files.
+
+use std::{
+    fmt::Write,
+    fs::File,
+    io::BufWriter,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+use ansi_term::Colour::RGB;
+use anyhow::Context;
+
+use crate::datastructures::grid::GridView;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    Text(String),
+    Image(Image),
+}
+
+impl Frame {
+    /// Text frame with one line per row of the grid.
+    pub fn grid<T: Deref<Target = [u8]>>(grid: &GridView<T>) -> Self {
+        Self::Text(String::from_utf8_lossy(&grid.encode_text()).into_owned())
+    }
+
+    /// Renders the frame, returning the file extension and content.
+    pub fn render(&self) -> (&'static str, String) {
+        match self {
+            Self::Text(text) => ("txt", text.clone()),
+            Self::Image(image) => ("ppm", image.to_ppm()),
+        }
+    }
+}
+
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    pub fn set(&mut self, (row, col): (usize, usize), color: Rgb) {
+        assert!(row < self.height && col < self.width, "pixel out of bounds");
+        self.pixels[row * self.width + col] = color;
+    }
+
+    fn pixel(&self, (row, col): (usize, usize)) -> Rgb {
+        self.pixels[row * self.width + col]
+    }
+
+    /// Draws the image with colored half blocks, two pixel rows per line.
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+        for row in (0..self.height).step_by(2) {
+            for col in 0..self.width {
+                let [r, g, b] = self.pixel((row, col));
+                let [br, bg, bb] = if row + 1 < self.height {
+                    self.pixel((row + 1, col))
+                } else {
+                    [0, 0, 0]
+                };
+                write!(ansi, "{}", RGB(r, g, b).on(RGB(br, bg, bb)).paint("▀")).unwrap();
+            }
+            ansi.push('\n');
+        }
+        ansi
+    }
+
+    pub fn write_png(&self, path: &Path) -> anyhow::Result<()> {
+        let mut encoder = png::Encoder::new(
+            BufWriter::new(File::create(path)?),
+            self.width.try_into()?,
+            self.height.try_into()?,
+        );
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder
+            .write_header()?
+            .write_image_data(self.pixels.as_flattened())?;
+        Ok(())
+    }
+
+    /// Encodes the image as plain-text PPM (P3), one row of pixels per line.
+    pub fn to_ppm(&self) -> String {
+        let mut ppm = format!("P3\n{} {}\n255\n", self.width, self.height);
+        for row in self.pixels.chunks(self.width.max(1)) {
+            let line = row
+                .iter()
+                .map(|[r, g, b]| format!("{r} {g} {b}"))
+                .collect::<Vec<_>>()
+                .join("  ");
+            writeln!(ppm, "{line}").unwrap();
+        }
+        ppm
+    }
+}
+
+/// Writes images as an animated GIF. All images must have the same size.
+pub fn write_gif(images: &[&Image], path: &Path) -> anyhow::Result<()> {
+    let Some(first) = images.first() else {
+        return Ok(());
+    };
+    let (width, height) = (first.width.try_into()?, first.height.try_into()?);
+    let mut encoder = gif::Encoder::new(BufWriter::new(File::create(path)?), width, height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    for image in images {
+        anyhow::ensure!(
+            image.width == first.width && image.height == first.height,
+            "all frames of an animation must have the same size"
+        );
+        let mut frame = gif::Frame::from_rgb_speed(width, height, image.pixels.as_flattened(), 10);
+        frame.delay = 10;
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+/// Prints the frames to the terminal.
+pub fn print_frames(frames: &[Frame]) {
+    for frame in frames {
+        match frame {
+            Frame::Text(text) => print!("{text}"),
+            Frame::Image(image) => print!("{}", image.to_ansi()),
+        }
+        println!();
+    }
+}
+
+/// Writes the frames to `directory` as `<name>.<n>.txt` for text frames and
+/// `<name>.png` or, for several images, an animated `<name>.gif`. Returns the
+/// paths of the written files.
+pub fn write_frames(
+    directory: &Path,
+    name: &str,
+    frames: &[Frame],
+) -> anyhow::Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(directory)
+        .with_context(|| format!("creating {}", directory.display()))?;
+    let mut written = Vec::new();
+    let mut images = Vec::new();
+    for (i, frame) in frames.iter().enumerate() {
+        match frame {
+            Frame::Text(text) => {
+                let path = directory.join(format!("{name}.{i}.txt"));
+                std::fs::write(&path, text)?;
+                written.push(path);
+            }
+            Frame::Image(image) => images.push(image),
+        }
+    }
+    match images.as_slice() {
+        [] => {}
+        [image] => {
+            let path = directory.join(format!("{name}.png"));
+            image.write_png(&path)?;
+            written.push(path);
+        }
+        images => {
+            let path = directory.join(format!("{name}.gif"));
+            write_gif(images, &path)?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+/// Compares the rendered frames against golden files `<base>.<n>.<ext>`
+/// relative to the crate root. Setting the `UPDATE_GOLDENS` environment
+/// variable writes the current rendering to the golden files instead.
+#[cfg(test)]
+pub fn assert_goldens(base: &str, frames: &[Frame]) {
+    let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    for (i, frame) in frames.iter().enumerate() {
+        let (extension, actual) = frame.render();
+        let path = root.join(format!("{base}.{i}.{extension}"));
+        if std::env::var_os("UPDATE_GOLDENS").is_some() {
+            std::fs::write(&path, &actual).expect("cannot write golden file");
+            continue;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "missing golden file {}, run with UPDATE_GOLDENS=1 to create it",
+                path.display()
+            )
+        });
+        assert!(
+            actual == expected,
+            "frame {i} differs from golden file {}, run with UPDATE_GOLDENS=1 to update it\n\
+             --- expected\n{expected}\n--- actual\n{actual}",
+            path.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{write_frames, Frame, Image};
+    use crate::datastructures::grid::GridView;
+
+    #[test]
+    fn test_grid_frame() {
+        let grid = GridView::from_separated(b'\n', b"#.\n.#");
+        assert_eq!(Frame::grid(&grid), Frame::Text("#.\n.#\n".into()));
+    }
+
+    #[test]
+    fn test_to_ppm() {
+        let mut image = Image::new(2, 1, [0, 0, 0]);
+        image.set((0, 1), [255, 128, 0]);
+        assert_eq!(image.to_ppm(), "P3\n2 1\n255\n0 0 0  255 128 0\n");
+    }
+
+    #[test]
+    fn test_write_frames() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let image = Image::new(3, 2, [0, 0, 0]);
+        let frames = [Frame::Text("..\n".into()), Frame::Image(image)];
+        let written = write_frames(directory.path(), "2023-10", &frames)?;
+        assert_eq!(
+            written,
+            [
+                directory.path().join("2023-10.0.txt"),
+                directory.path().join("2023-10.png")
+            ]
+        );
+        assert!(std::fs::read(&written[1])?.starts_with(b"\x89PNG"));
+
+        let frames = [
+            Frame::Image(Image::new(3, 2, [0, 0, 0])),
+            Frame::Image(Image::new(3, 2, [255, 0, 0])),
+        ];
+        let written = write_frames(directory.path(), "2024-14", &frames)?;
+        assert_eq!(written, [directory.path().join("2024-14.gif")]);
+        assert!(std::fs::read(&written[0])?.starts_with(b"GIF89a"));
+        Ok(())
+    }
+}
@@ -0,0 +1,217 @@
+//! Watched execution of solvers, so that a misbehaving solver fails a single
+//! part instead of the whole run.
+//!
+//! The solver runs on a separate thread watched by the calling thread. Panics
+//! are caught and reported as errors, and inputs above a size limit are
+//! rejected up-front. The heap allocations of the solver thread are counted
+//! against a memory budget, which requires the `count-allocations` feature.
+//!
+//! This is not a sandbox: a thread cannot be killed, and an allocator must not
+//! unwind, so a solver exceeding its time or memory budget is merely abandoned
+//! and keeps running until it finishes or the process exits. Allocations of
+//! threads spawned by the solver are not counted.
+
+use std::{
+    any::Any,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::counters::{self, HeapBudget};
+
+/// Interval in which the watching thread checks the memory budget.
+const MEMORY_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub max_input_bytes: Option<usize>,
+    pub time_budget: Option<Duration>,
+    /// Maximum heap usage of the solver thread.
+    pub max_memory_bytes: Option<u64>,
+}
+
+#[derive(Debug)]
+pub enum WatchdogError {
+    InputTooLarge { size: usize, limit: usize },
+    Timeout(Duration),
+    OutOfMemory { limit: u64 },
+    Panicked(String),
+    Failed(anyhow::Error),
+}
+
+impl Display for WatchdogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InputTooLarge { size, limit } => {
+                write!(f, "input of {size} bytes exceeds limit of {limit} bytes")
+            }
+            Self::Timeout(budget) => write!(f, "time budget of {budget:?} exceeded"),
+            Self::OutOfMemory { limit } => write!(f, "memory limit of {limit} bytes exceeded"),
+            Self::Panicked(message) => write!(f, "solver panicked: {message}"),
+            Self::Failed(err) => write!(f, "{err:#}"),
+        }
+    }
+}
+
+impl std::error::Error for WatchdogError {}
+
+/// Runs `f` on `input` on a watched thread within the given limits.
+pub fn run<T, F>(limits: &Limits, input: Vec<u8>, f: F) -> Result<T, WatchdogError>
+where
+    T: Send + 'static,
+    F: FnOnce(&[u8]) -> anyhow::Result<T> + Send + 'static,
+{
+    if let Some(limit) = limits.max_input_bytes {
+        if input.len() > limit {
+            return Err(WatchdogError::InputTooLarge {
+                size: input.len(),
+                limit,
+            });
+        }
+    }
+
+    let heap_budget = limits
+        .max_memory_bytes
+        .map(|limit| Arc::new(HeapBudget::new(limit)));
+    let check_heap_budget = || match (&heap_budget, limits.max_memory_bytes) {
+        (Some(budget), Some(limit)) if budget.exceeded() => {
+            Err(WatchdogError::OutOfMemory { limit })
+        }
+        _ => Ok(()),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let thread_budget = heap_budget.clone();
+    thread::Builder::new()
+        .name("watched-solver".into())
+        .spawn(move || {
+            let run = || panic::catch_unwind(AssertUnwindSafe(|| f(&input)));
+            let result = match &thread_budget {
+                Some(budget) => counters::with_heap_budget(budget, run),
+                None => run(),
+            };
+            // The receiver is gone if a budget was exceeded.
+            let _ = sender.send(result);
+        })
+        .map_err(|err| WatchdogError::Failed(err.into()))?;
+
+    let deadline = limits.time_budget.map(|budget| Instant::now() + budget);
+    let result = loop {
+        let mut wait = deadline.map_or(Duration::MAX, |deadline| {
+            deadline.saturating_duration_since(Instant::now())
+        });
+        if heap_budget.is_some() {
+            wait = wait.min(MEMORY_POLL_INTERVAL);
+        }
+        match receiver.recv_timeout(wait) {
+            Ok(result) => break result,
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(err @ RecvTimeoutError::Disconnected) => {
+                return Err(WatchdogError::Failed(err.into()))
+            }
+        }
+        check_heap_budget()?;
+        if let (Some(deadline), Some(budget)) = (deadline, limits.time_budget) {
+            if Instant::now() >= deadline {
+                return Err(WatchdogError::Timeout(budget));
+            }
+        }
+    };
+    // The budget may have been exceeded only temporarily.
+    check_heap_budget()?;
+    match result {
+        Ok(result) => result.map_err(WatchdogError::Failed),
+        Err(payload) => Err(WatchdogError::Panicked(panic_message(payload.as_ref()))),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{run, Limits, WatchdogError};
+
+    #[test]
+    fn test_returns_result() {
+        let result = run(&Limits::default(), "42".into(), |input| {
+            Ok(std::str::from_utf8(input)?.parse::<u32>()?)
+        });
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[test]
+    fn test_rejects_large_input() {
+        let limits = Limits {
+            max_input_bytes: Some(1),
+            ..Limits::default()
+        };
+        let result = run(&limits, "42".into(), |_| Ok(()));
+        assert!(matches!(
+            result,
+            Err(WatchdogError::InputTooLarge { size: 2, limit: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_isolates_panics() {
+        let result: Result<(), _> = run(&Limits::default(), Vec::new(), |_| panic!("boom"));
+        assert!(matches!(result, Err(WatchdogError::Panicked(message)) if message == "boom"));
+    }
+
+    #[test]
+    fn test_enforces_time_budget() {
+        let limits = Limits {
+            time_budget: Some(Duration::from_millis(10)),
+            ..Limits::default()
+        };
+        let result = run(&limits, Vec::new(), |_| {
+            std::thread::sleep(Duration::from_millis(500));
+            Ok(())
+        });
+        assert!(matches!(result, Err(WatchdogError::Timeout(_))));
+    }
+
+    #[cfg(feature = "count-allocations")]
+    #[test]
+    fn test_enforces_memory_budget() {
+        let limits = Limits {
+            max_memory_bytes: Some(1 << 20),
+            ..Limits::default()
+        };
+        let allocate =
+            |bytes: usize| move |_: &[u8]| Ok(std::hint::black_box(vec![0u8; bytes]).len());
+        assert_eq!(
+            run(&limits, Vec::new(), allocate(1 << 16)).unwrap(),
+            1 << 16
+        );
+        let result = run(&limits, Vec::new(), allocate(1 << 21));
+        assert!(matches!(
+            result,
+            Err(WatchdogError::OutOfMemory { limit }) if limit == 1 << 20
+        ));
+    }
+
+    #[test]
+    fn test_propagates_errors() {
+        let result: Result<(), _> = run(&Limits::default(), Vec::new(), |_| {
+            Err(anyhow::anyhow!("invalid input"))
+        });
+        assert!(matches!(result, Err(WatchdogError::Failed(_))));
+    }
+}
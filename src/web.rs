@@ -0,0 +1,58 @@
+//! WebAssembly bindings used by the browser front-end in `web/`.
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::solvers::{self, Solution};
+
+#[derive(Serialize)]
+struct Answer {
+    description: &'static str,
+    answer: String,
+}
+
+impl From<&Solution> for Answer {
+    fn from(solution: &Solution) -> Self {
+        Self {
+            description: solution.description(),
+            answer: solution.answer_str().into_owned(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Answers {
+    title: Option<&'static str>,
+    part1: Answer,
+    part2: Option<Answer>,
+}
+
+fn to_js_error(err: anyhow::Error) -> JsError {
+    JsError::new(&format!("{err:#}"))
+}
+
+/// Solves both parts of a day and returns the answers as JSON:
+/// `{"title": …, "part1": {"description": …, "answer": …}, "part2": … | null}`.
+#[wasm_bindgen]
+pub fn solve(year: i32, day: u32, input: &str) -> Result<String, JsError> {
+    let input = solvers::normalize_input(year, day, input.into());
+    let answers = (|| -> anyhow::Result<Answers> {
+        let mut solver = solvers::new_solver_from_bytes(year, day, &input)?;
+        solver.precompute()?;
+        let part1 = solver.solve_part_1()?;
+        let part2 = solver.solve_part_2_with(&part1)?;
+        Ok(Answers {
+            title: solvers::solver_info(year, day).and_then(|info| info.title),
+            part1: (&part1).into(),
+            part2: part2.as_ref().map(Answer::from),
+        })
+    })()
+    .map_err(to_js_error)?;
+    Ok(serde_json::to_string(&answers)?)
+}
+
+/// Days with a solver as JSON array of `[year, day]` pairs.
+#[wasm_bindgen(js_name = solverDays)]
+pub fn solver_days() -> String {
+    serde_json::to_string(&solvers::solver_days()).expect("days are serializable")
+}